@@ -0,0 +1,168 @@
+//! Submission of readings to `nodara_iot_bridge` as unsigned extrinsics.
+//!
+//! Readings are authorised by their device signature, so the gateway needs no account of its own: each reading is
+//! wrapped in an unsigned `IoTBridge::submit_iot_data` extrinsic and a whole batch is sent to the node as a single
+//! JSON-RPC batch of `author_submitExtrinsic` calls.
+
+use crate::reading::SignedReading;
+use parity_scale_codec::{Compact, Encode};
+use serde_json::{json, Value};
+use std::fmt;
+
+/// Version byte of an unsigned extrinsic (format version 4, no signature).
+const UNSIGNED_EXTRINSIC_V4: u8 = 0x04;
+
+/// Call index of `submit_iot_data` in `nodara_iot_bridge`.
+pub const SUBMIT_IOT_DATA_CALL_INDEX: u8 = 3;
+
+/// What happened to one reading of a batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The node accepted the extrinsic into its pool (or already had it). Pooled is not included: the pool may
+    /// still drop it.
+    Accepted,
+    /// The device's on-chain nonce has moved past the reading: it was included after an earlier submission, or its
+    /// nonce is stale.
+    Outdated,
+    /// The node will never accept the reading (stale nonce, bad signature, malformed payload, ...).
+    Rejected(String),
+    /// The reading was refused for now (e.g., the device is rate limited) and should be retried later.
+    Retry(String),
+}
+
+/// Failure to reach the node at all; the whole batch should be retried.
+#[derive(Debug)]
+pub struct TransportError(pub String);
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transport error: {}", self.0)
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+/// A connection to the chain able to submit batches of readings.
+pub trait ChainClient {
+    /// Submits a batch, returning one outcome per reading, in order.
+    async fn submit_batch(&self, batch: &[SignedReading]) -> Result<Vec<Outcome>, TransportError>;
+}
+
+/// Encodes a reading as an unsigned `submit_iot_data` extrinsic.
+pub fn encode_extrinsic(pallet_index: u8, reading: &SignedReading) -> Vec<u8> {
+    let mut body = vec![UNSIGNED_EXTRINSIC_V4, pallet_index, SUBMIT_IOT_DATA_CALL_INDEX];
+    body.extend(reading.encode_call_args());
+    let mut extrinsic = Compact(body.len() as u32).encode();
+    extrinsic.extend(body);
+    extrinsic
+}
+
+/// Classifies a JSON-RPC error returned by `author_submitExtrinsic`.
+///
+/// Transaction pool errors carry the validity error in `data`. `ExhaustsResources` is how the bridge reports a
+/// rate-limited device, and `Future`/`Priority is too low` mean another reading of the device is still pooled;
+/// those are retried. `Stale` means the device nonce moved past the reading. `BadProof` and invalid calls are final.
+pub fn classify_error(error: &Value) -> Outcome {
+    let message = error.get("message").and_then(Value::as_str).unwrap_or_default();
+    let data = error.get("data").and_then(Value::as_str).unwrap_or_default();
+    let detail = format!("{} {}", message, data);
+    let detail = detail.trim().to_string();
+    if detail.contains("Already Imported") {
+        Outcome::Accepted
+    } else if detail.contains("outdated") {
+        Outcome::Outdated
+    } else if detail.contains("exhaust the block limits")
+        || detail.contains("Priority is too low")
+        || detail.contains("in the future")
+        || detail.contains("Immediately Dropped")
+    {
+        Outcome::Retry(detail)
+    } else {
+        Outcome::Rejected(detail)
+    }
+}
+
+/// JSON-RPC client of a Nodara node.
+pub struct RpcClient {
+    http: reqwest::Client,
+    url: String,
+    pallet_index: u8,
+}
+
+impl RpcClient {
+    /// Creates a client for the node's HTTP RPC endpoint; `pallet_index` is the index of `IoTBridge` in the runtime.
+    pub fn new(url: String, pallet_index: u8) -> Self {
+        RpcClient { http: reqwest::Client::new(), url, pallet_index }
+    }
+}
+
+impl ChainClient for RpcClient {
+    async fn submit_batch(&self, batch: &[SignedReading]) -> Result<Vec<Outcome>, TransportError> {
+        let requests: Vec<Value> = batch
+            .iter()
+            .enumerate()
+            .map(|(id, reading)| {
+                let extrinsic = format!("0x{}", hex::encode(encode_extrinsic(self.pallet_index, reading)));
+                json!({ "jsonrpc": "2.0", "id": id, "method": "author_submitExtrinsic", "params": [extrinsic] })
+            })
+            .collect();
+        let response = self
+            .http
+            .post(&self.url)
+            .json(&requests)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| TransportError(e.to_string()))?;
+        let responses: Vec<Value> = response.json().await.map_err(|e| TransportError(e.to_string()))?;
+
+        // Batch responses may come back in any order; match them to readings by ID.
+        let mut outcomes = vec![Outcome::Retry("no response from node".to_string()); batch.len()];
+        for response in responses {
+            let Some(id) = response.get("id").and_then(Value::as_u64).map(|id| id as usize) else { continue };
+            if id >= outcomes.len() {
+                continue;
+            }
+            outcomes[id] = match response.get("error") {
+                Some(error) => classify_error(error),
+                None => Outcome::Accepted,
+            };
+        }
+        Ok(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reading::reading;
+    use parity_scale_codec::Decode;
+
+    #[test]
+    fn test_extrinsic_encoding() {
+        let reading = reading("Meter001", 42);
+        let extrinsic = encode_extrinsic(12, &reading);
+        let input = &mut &extrinsic[..];
+        let len = Compact::<u32>::decode(input).unwrap().0 as usize;
+        assert_eq!(len, input.len());
+        assert_eq!(&input[..3], &[UNSIGNED_EXTRINSIC_V4, 12, SUBMIT_IOT_DATA_CALL_INDEX]);
+        *input = &input[3..];
+        assert_eq!(Vec::<u8>::decode(input).unwrap(), reading.payload);
+        assert_eq!(Vec::<u8>::decode(input).unwrap(), reading.device_id);
+        assert_eq!(u64::decode(input).unwrap(), 42);
+        assert_eq!(*input, &reading.signature[..]);
+    }
+
+    #[test]
+    fn test_pool_errors_are_classified() {
+        let error = |data: &str| json!({ "code": 1010, "message": "Invalid Transaction", "data": data });
+        assert_eq!(classify_error(&error("Transaction is outdated")), Outcome::Outdated);
+        assert!(matches!(classify_error(&error("Transaction has a bad signature")), Outcome::Rejected(_)));
+        assert!(matches!(
+            classify_error(&error("Transaction would exhaust the block limits")),
+            Outcome::Retry(_)
+        ));
+        let imported = json!({ "code": 1013, "message": "Transaction Already Imported" });
+        assert_eq!(classify_error(&imported), Outcome::Accepted);
+    }
+}
@@ -0,0 +1,141 @@
+//! # Nodara IoT Gateway - Offchain Relay for nodara_iot_bridge
+//!
+//! Bridges field devices to Nodara BIOSPHÈRE QUANTIC. The gateway subscribes to an MQTT broker and/or accepts HTTP
+//! POSTs of signed device readings, persists them in a local queue, and relays them in batches to
+//! `nodara_iot_bridge` as unsigned `submit_iot_data` extrinsics authorised by the device signatures.
+//!
+//! ## Key Features:
+//! - **MQTT and HTTP Ingestion:** MQTT messages are acknowledged only once persisted; HTTP clients get `202 Accepted`.
+//! - **Local Persistent Queue:** Readings are synced to disk and survive gateway restarts, power loss and node
+//!   outages; they leave the queue only once included on chain or rejected for good.
+//! - **Backpressure:** When the queue is full the gateway stops consuming from the broker and answers HTTP with `503`.
+//! - **Batching and Retry:** Readings are sent as JSON-RPC batches (at most one pending reading per device, in nonce
+//!   order) with exponential backoff on failures; readings the chain will never accept are dropped and logged.
+//!
+//! ## Version:
+//! March 2025 – Nodara BIOSPHÈRE QUANTIC Legendary Edition
+
+use clap::Parser;
+use std::error::Error;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod chain;
+mod queue;
+mod reading;
+mod relay;
+mod source;
+
+use chain::RpcClient;
+use queue::PersistentQueue;
+use relay::RelayConfig;
+use source::MqttSource;
+
+/// Nodara IoT Gateway - relays signed device readings to nodara_iot_bridge
+#[derive(Parser)]
+#[command(author, version, about = "Nodara BIOSPHÈRE QUANTIC IoT Gateway", long_about = None)]
+struct Cli {
+    /// HTTP JSON-RPC endpoint of a Nodara node.
+    #[arg(long, env = "NODARA_RPC_URL", default_value = "http://127.0.0.1:9933")]
+    rpc_url: String,
+
+    /// Index of the IoTBridge pallet in the runtime.
+    #[arg(long, default_value_t = 12)]
+    pallet_index: u8,
+
+    /// MQTT broker as host:port; disables MQTT ingestion when omitted.
+    #[arg(long)]
+    mqtt_broker: Option<String>,
+
+    /// MQTT topic filter carrying device readings.
+    #[arg(long, default_value = "nodara/devices/+/readings")]
+    mqtt_topic: String,
+
+    /// MQTT client ID; keep it stable so the broker can redeliver unacknowledged messages.
+    #[arg(long, default_value = "nodara-iot-gateway")]
+    mqtt_client_id: String,
+
+    /// Address for the HTTP ingestion endpoint (`POST /readings`); disables HTTP ingestion when omitted.
+    #[arg(long)]
+    http_listen: Option<SocketAddr>,
+
+    /// Directory of the local persistent queue.
+    #[arg(long, default_value = "./iot-gateway-queue")]
+    queue_dir: PathBuf,
+
+    /// Maximum number of queued readings before backpressure applies.
+    #[arg(long, default_value_t = 10_000)]
+    queue_capacity: usize,
+
+    /// Maximum number of readings per batch.
+    #[arg(long, default_value_t = 64)]
+    batch_size: usize,
+
+    /// Maximum wait, in milliseconds, before a partial batch is sent.
+    #[arg(long, default_value_t = 2_000)]
+    batch_interval_ms: u64,
+
+    /// Initial retry delay, in milliseconds, after a failed submission.
+    #[arg(long, default_value_t = 1_000)]
+    retry_base_ms: u64,
+
+    /// Maximum retry delay, in milliseconds.
+    #[arg(long, default_value_t = 60_000)]
+    retry_max_ms: u64,
+
+    /// Time, in milliseconds, a pooled reading is given to be included before it is submitted again to check on it.
+    #[arg(long, default_value_t = 12_000)]
+    inclusion_timeout_ms: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    if cli.mqtt_broker.is_none() && cli.http_listen.is_none() {
+        eprintln!("Nothing to ingest: pass --mqtt-broker and/or --http-listen.");
+        process::exit(2);
+    }
+
+    let queue = Arc::new(PersistentQueue::open(&cli.queue_dir, cli.queue_capacity)?);
+    println!("Persistent queue opened at {} ({} pending readings).", cli.queue_dir.display(), queue.len());
+
+    if let Some(broker) = &cli.mqtt_broker {
+        let (host, port) = broker.rsplit_once(':').ok_or("--mqtt-broker must be host:port")?;
+        let mut mqtt = MqttSource::connect(&cli.mqtt_client_id, host, port.parse()?, &cli.mqtt_topic).await?;
+        println!("Subscribed to {} on {}.", cli.mqtt_topic, broker);
+        let queue = queue.clone();
+        tokio::spawn(async move {
+            if let Err(e) = source::ingest(&mut mqtt, &queue).await {
+                eprintln!("MQTT ingestion stopped: {}", e);
+                process::exit(1);
+            }
+        });
+    }
+
+    if let Some(addr) = cli.http_listen {
+        println!("Accepting readings on http://{}/readings.", addr);
+        let queue = queue.clone();
+        tokio::spawn(async move {
+            if let Err(e) = source::serve_http(addr, queue).await {
+                eprintln!("HTTP ingestion stopped: {}", e);
+                process::exit(1);
+            }
+        });
+    }
+
+    let client = RpcClient::new(cli.rpc_url.clone(), cli.pallet_index);
+    let config = RelayConfig {
+        batch_size: cli.batch_size.max(1),
+        batch_interval: Duration::from_millis(cli.batch_interval_ms),
+        retry_base: Duration::from_millis(cli.retry_base_ms),
+        retry_max: Duration::from_millis(cli.retry_max_ms.max(cli.retry_base_ms)),
+        inclusion_timeout: Duration::from_millis(cli.inclusion_timeout_ms),
+    };
+    println!("Relaying readings to {}.", cli.rpc_url);
+    relay::run(&queue, &client, &config).await?;
+    Ok(())
+}
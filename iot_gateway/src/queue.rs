@@ -0,0 +1,276 @@
+//! Local persistent queue of readings awaiting submission.
+//!
+//! Each reading is stored as its own file, named after its sequence number, in the queue directory. Files are
+//! written to a temporary name, synced and renamed into place, and the directory is synced, so a reading reported as
+//! persisted survives a crash or power loss and a crash never leaves a partially written entry. The queue is
+//! reloaded from disk on start. The queue is bounded: producers wait for space (backpressure) instead of letting the
+//! backlog grow without limit while the chain is unreachable.
+//!
+//! Readings stay queued until they are seen included on chain; readings in the node's pool are only marked as
+//! submitted, in memory, so that they are resubmitted (or found included) after a restart.
+
+use crate::reading::SignedReading;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// Sequence number of a queued reading; readings are submitted in sequence order per device.
+pub type Seq = u64;
+
+/// Bounded, file-backed FIFO of signed readings.
+pub struct PersistentQueue {
+    dir: PathBuf,
+    capacity: usize,
+    state: Mutex<QueueState>,
+    /// Signalled whenever entries are removed.
+    space: Notify,
+    /// Signalled whenever entries are added.
+    items: Notify,
+}
+
+struct QueueState {
+    next_seq: Seq,
+    entries: BTreeMap<Seq, SignedReading>,
+    /// When each reading accepted into the node's pool was last submitted.
+    submitted: HashMap<Seq, Instant>,
+}
+
+impl PersistentQueue {
+    /// Opens the queue stored in `dir`, creating the directory if needed and reloading any pending entries.
+    pub fn open(dir: impl AsRef<Path>, capacity: usize) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let mut entries = BTreeMap::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            // Leftover temporary files belong to writes that never completed.
+            if path.extension().is_some_and(|ext| ext == "tmp") {
+                fs::remove_file(&path)?;
+                continue;
+            }
+            let Some(seq) = path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse::<Seq>().ok())
+            else {
+                continue;
+            };
+            let reading = serde_json::from_slice(&fs::read(&path)?)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))?;
+            entries.insert(seq, reading);
+        }
+        let next_seq = entries.keys().next_back().map_or(0, |last| last + 1);
+        Ok(PersistentQueue {
+            dir,
+            capacity,
+            state: Mutex::new(QueueState { next_seq, entries, submitted: HashMap::new() }),
+            space: Notify::new(),
+            items: Notify::new(),
+        })
+    }
+
+    /// Number of queued readings.
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Whether the queue is empty.
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Persists a reading. Fails with `WouldBlock` when the queue is full; use `push_wait` to wait for space.
+    pub fn try_push(&self, reading: &SignedReading) -> io::Result<Seq> {
+        let mut state = self.state.lock().unwrap();
+        if state.entries.len() >= self.capacity {
+            return Err(io::Error::new(io::ErrorKind::WouldBlock, "queue is full"));
+        }
+        let seq = state.next_seq;
+        let path = self.entry_path(seq);
+        let tmp = path.with_extension("tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec(reading)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp, &path)?;
+        // Sync the directory too, so the rename itself is durable.
+        File::open(&self.dir)?.sync_all()?;
+        state.next_seq += 1;
+        state.entries.insert(seq, reading.clone());
+        drop(state);
+        self.items.notify_waiters();
+        Ok(seq)
+    }
+
+    /// Persists a reading, waiting for space while the queue is full.
+    pub async fn push_wait(&self, reading: &SignedReading) -> io::Result<Seq> {
+        loop {
+            let space = self.space.notified();
+            match self.try_push(reading) {
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => space.await,
+                result => return result,
+            }
+        }
+    }
+
+    /// Waits until at least `count` readings are ready to be sent (see `next_batch`).
+    pub async fn wait_for_items(&self, count: usize, resubmit_after: Duration) {
+        loop {
+            let items = self.items.notified();
+            if self.next_batch(count, resubmit_after).len() >= count {
+                return;
+            }
+            items.await;
+        }
+    }
+
+    /// Returns up to `max` readings, at most one per device: the pending reading with the lowest nonce, whatever
+    /// order the readings arrived in, so that each device's readings reach the chain in nonce order and one at a
+    /// time. Devices are served in the order their selected readings were queued. A device whose selected reading
+    /// was submitted less than `resubmit_after` ago is skipped while that reading waits for inclusion.
+    pub fn next_batch(&self, max: usize, resubmit_after: Duration) -> Vec<(Seq, SignedReading)> {
+        let state = self.state.lock().unwrap();
+        let mut lowest: HashMap<&[u8], (Seq, &SignedReading)> = HashMap::new();
+        for (seq, reading) in state.entries.iter() {
+            lowest
+                .entry(reading.device_id.as_slice())
+                .and_modify(|selected| {
+                    if reading.nonce < selected.1.nonce {
+                        *selected = (*seq, reading);
+                    }
+                })
+                .or_insert((*seq, reading));
+        }
+        let mut batch: Vec<_> = lowest
+            .into_values()
+            .filter(|(seq, _)| state.submitted.get(seq).is_none_or(|at| at.elapsed() >= resubmit_after))
+            .map(|(seq, reading)| (seq, reading.clone()))
+            .collect();
+        batch.sort_unstable_by_key(|(seq, _)| *seq);
+        batch.truncate(max);
+        batch
+    }
+
+    /// Marks a reading as accepted into the node's pool. It stays queued until it is seen included.
+    pub fn mark_submitted(&self, seq: Seq) {
+        self.state.lock().unwrap().submitted.insert(seq, Instant::now());
+    }
+
+    /// Whether a reading was accepted into the node's pool since the queue was opened.
+    pub fn was_submitted(&self, seq: Seq) -> bool {
+        self.state.lock().unwrap().submitted.contains_key(&seq)
+    }
+
+    /// Removes a reading once it has been included on chain or permanently rejected.
+    pub fn remove(&self, seq: Seq) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.submitted.remove(&seq);
+        if state.entries.remove(&seq).is_some() {
+            match fs::remove_file(self.entry_path(seq)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        drop(state);
+        self.space.notify_waiters();
+        Ok(())
+    }
+
+    fn entry_path(&self, seq: Seq) -> PathBuf {
+        self.dir.join(format!("{:020}.json", seq))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reading::reading;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_queue_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let queue = PersistentQueue::open(dir.path(), 10).unwrap();
+            queue.try_push(&reading("Meter001", 1)).unwrap();
+            let second = queue.try_push(&reading("Meter001", 2)).unwrap();
+            queue.try_push(&reading("Meter002", 1)).unwrap();
+            queue.remove(second).unwrap();
+        }
+        let queue = PersistentQueue::open(dir.path(), 10).unwrap();
+        assert_eq!(queue.len(), 2);
+        let batch: Vec<_> = queue.next_batch(10, Duration::ZERO).into_iter().map(|(_, r)| (r.device_id, r.nonce)).collect();
+        assert_eq!(batch, vec![(b"Meter001".to_vec(), 1), (b"Meter002".to_vec(), 1)]);
+        // New entries continue after the highest persisted sequence number.
+        assert_eq!(queue.try_push(&reading("Meter003", 1)).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_batches_hold_one_reading_per_device() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = PersistentQueue::open(dir.path(), 10).unwrap();
+        for nonce in 1..=3 {
+            queue.try_push(&reading("Meter001", nonce)).unwrap();
+        }
+        queue.try_push(&reading("Meter002", 1)).unwrap();
+        let batch: Vec<_> = queue.next_batch(10, Duration::ZERO).into_iter().map(|(_, r)| (r.device_id, r.nonce)).collect();
+        assert_eq!(batch, vec![(b"Meter001".to_vec(), 1), (b"Meter002".to_vec(), 1)]);
+    }
+
+    #[test]
+    fn test_batches_follow_nonce_order_not_arrival_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = PersistentQueue::open(dir.path(), 10).unwrap();
+        // MQTT redelivery and parallel HTTP posts can queue a device's readings out of order.
+        queue.try_push(&reading("Meter001", 3)).unwrap();
+        queue.try_push(&reading("Meter002", 7)).unwrap();
+        let first = queue.try_push(&reading("Meter001", 1)).unwrap();
+        queue.try_push(&reading("Meter001", 2)).unwrap();
+        let batch: Vec<_> = queue.next_batch(10, Duration::ZERO).into_iter().map(|(_, r)| (r.device_id, r.nonce)).collect();
+        assert_eq!(batch, vec![(b"Meter002".to_vec(), 7), (b"Meter001".to_vec(), 1)]);
+        queue.remove(first).unwrap();
+        let batch: Vec<_> = queue.next_batch(1, Duration::ZERO).into_iter().map(|(_, r)| (r.device_id, r.nonce)).collect();
+        assert_eq!(batch, vec![(b"Meter002".to_vec(), 7)]);
+        let batch: Vec<_> = queue.next_batch(10, Duration::ZERO).into_iter().map(|(_, r)| (r.device_id, r.nonce)).collect();
+        assert_eq!(batch, vec![(b"Meter002".to_vec(), 7), (b"Meter001".to_vec(), 2)]);
+    }
+
+    #[test]
+    fn test_submitted_readings_hold_their_device_until_resubmission() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = PersistentQueue::open(dir.path(), 10).unwrap();
+        let first = queue.try_push(&reading("Meter001", 1)).unwrap();
+        queue.try_push(&reading("Meter001", 2)).unwrap();
+        queue.try_push(&reading("Meter002", 1)).unwrap();
+        queue.mark_submitted(first);
+        let hour = Duration::from_secs(3_600);
+        // Meter001 waits for its pooled reading to be included; its next reading is not sent meanwhile.
+        let batch: Vec<_> = queue.next_batch(10, hour).into_iter().map(|(_, r)| (r.device_id, r.nonce)).collect();
+        assert_eq!(batch, vec![(b"Meter002".to_vec(), 1)]);
+        assert_eq!(queue.len(), 3);
+        // Once the wait is over, the pooled reading is submitted again to check on it.
+        let batch: Vec<_> = queue.next_batch(10, Duration::ZERO).into_iter().map(|(_, r)| (r.device_id, r.nonce)).collect();
+        assert_eq!(batch, vec![(b"Meter001".to_vec(), 1), (b"Meter002".to_vec(), 1)]);
+        queue.remove(first).unwrap();
+        assert!(!queue.was_submitted(first));
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_applies_backpressure() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = Arc::new(PersistentQueue::open(dir.path(), 1).unwrap());
+        let first = queue.try_push(&reading("Meter001", 1)).unwrap();
+        assert_eq!(queue.try_push(&reading("Meter001", 2)).unwrap_err().kind(), io::ErrorKind::WouldBlock);
+
+        let producer = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.push_wait(&reading("Meter001", 2)).await.unwrap() })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!producer.is_finished());
+        queue.remove(first).unwrap();
+        producer.await.unwrap();
+        assert_eq!(queue.len(), 1);
+    }
+}
@@ -0,0 +1,140 @@
+//! Signed device readings as received from devices and relayed to `nodara_iot_bridge`.
+
+use parity_scale_codec::Encode;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A reading signed by a device, ready to be submitted as `IoTBridge::submit_iot_data`.
+///
+/// On the wire (MQTT message or HTTP body) readings are JSON objects whose byte fields are hex strings, with or
+/// without a `0x` prefix:
+///
+/// ```json
+/// { "device_id": "0x4d65746572303031", "nonce": 42, "payload": "0x6608", "signature": "0x01..." }
+/// ```
+///
+/// `signature` is the SCALE encoding of a `MultiSignature` (variant byte followed by the signature) over
+/// `signing_payload(device_id, nonce, payload)`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedReading {
+    #[serde(with = "hex_bytes")]
+    pub device_id: Vec<u8>,
+    pub nonce: u64,
+    #[serde(with = "hex_bytes")]
+    pub payload: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub signature: Vec<u8>,
+}
+
+/// Reason a message could not be parsed into readings.
+#[derive(Debug)]
+pub enum ReadingError {
+    /// The message is not valid JSON or misses a field.
+    InvalidJson(serde_json::Error),
+    /// The device ID is empty.
+    EmptyDeviceId,
+    /// The signature is not a SCALE-encoded `MultiSignature`.
+    InvalidSignature,
+}
+
+impl fmt::Display for ReadingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadingError::InvalidJson(e) => write!(f, "invalid reading JSON: {}", e),
+            ReadingError::EmptyDeviceId => write!(f, "empty device ID"),
+            ReadingError::InvalidSignature => write!(f, "signature is not a SCALE-encoded MultiSignature"),
+        }
+    }
+}
+
+impl std::error::Error for ReadingError {}
+
+impl SignedReading {
+    /// Parses a message holding either one reading or an array of readings.
+    pub fn parse_message(message: &[u8]) -> Result<Vec<SignedReading>, ReadingError> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OneOrMany {
+            One(SignedReading),
+            Many(Vec<SignedReading>),
+        }
+
+        let readings = match serde_json::from_slice(message).map_err(ReadingError::InvalidJson)? {
+            OneOrMany::One(reading) => vec![reading],
+            OneOrMany::Many(readings) => readings,
+        };
+        for reading in &readings {
+            reading.validate()?;
+        }
+        Ok(readings)
+    }
+
+    /// Performs the cheap structural checks; signatures and nonces are checked on-chain.
+    pub fn validate(&self) -> Result<(), ReadingError> {
+        if self.device_id.is_empty() {
+            return Err(ReadingError::EmptyDeviceId);
+        }
+        let expected_len = match self.signature.first() {
+            Some(0) | Some(1) => 65, // Ed25519 / Sr25519: variant + 64 bytes.
+            Some(2) => 66,           // Ecdsa: variant + 65 bytes.
+            _ => return Err(ReadingError::InvalidSignature),
+        };
+        if self.signature.len() != expected_len {
+            return Err(ReadingError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    /// SCALE-encodes the `submit_iot_data` arguments, in call order.
+    pub fn encode_call_args(&self) -> Vec<u8> {
+        let mut args = (&self.payload, &self.device_id, self.nonce).encode();
+        // Already a SCALE-encoded `MultiSignature`.
+        args.extend_from_slice(&self.signature);
+        args
+    }
+}
+
+/// Serde helpers for hex-encoded byte fields.
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s.trim_start_matches("0x")).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Builds a structurally valid reading with a dummy sr25519 signature.
+#[cfg(test)]
+pub(crate) fn reading(device: &str, nonce: u64) -> SignedReading {
+    let mut signature = vec![1u8];
+    signature.extend_from_slice(&[7u8; 64]);
+    SignedReading { device_id: device.as_bytes().to_vec(), nonce, payload: vec![0x66, 0x08], signature }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_and_batched_messages() {
+        let one = serde_json::to_vec(&reading("Meter001", 1)).unwrap();
+        assert_eq!(SignedReading::parse_message(&one).unwrap(), vec![reading("Meter001", 1)]);
+
+        let many = serde_json::to_vec(&vec![reading("Meter001", 2), reading("Meter002", 1)]).unwrap();
+        assert_eq!(SignedReading::parse_message(&many).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_messages() {
+        assert!(matches!(SignedReading::parse_message(b"not json"), Err(ReadingError::InvalidJson(_))));
+        let mut bad = reading("Meter001", 1);
+        bad.signature.pop();
+        let message = serde_json::to_vec(&bad).unwrap();
+        assert!(matches!(SignedReading::parse_message(&message), Err(ReadingError::InvalidSignature)));
+    }
+}
@@ -0,0 +1,224 @@
+//! Relays queued readings to the chain in batches, retrying with exponential backoff.
+//!
+//! A reading the node accepts into its pool stays queued, as the pool may still drop it. Once `inclusion_timeout`
+//! has passed it is submitted again: the node then reports it outdated if it was included (the device nonce moved
+//! past it), reports it already imported if it is still pooled, or pools it again if it had been dropped.
+
+use crate::chain::{ChainClient, Outcome};
+use crate::queue::PersistentQueue;
+use std::io;
+use std::time::Duration;
+
+/// Batching and retry settings.
+#[derive(Clone, Debug)]
+pub struct RelayConfig {
+    /// Maximum number of readings per batch.
+    pub batch_size: usize,
+    /// Maximum time to wait for a full batch before sending a partial one.
+    pub batch_interval: Duration,
+    /// Delay before the first retry after a failed submission.
+    pub retry_base: Duration,
+    /// Upper bound of the retry delay.
+    pub retry_max: Duration,
+    /// Time a pooled reading is given to be included before it is submitted again to check on it.
+    pub inclusion_timeout: Duration,
+}
+
+/// Doubles `delay`, capped at `max`.
+fn next_backoff(delay: Duration, max: Duration) -> Duration {
+    delay.saturating_mul(2).min(max)
+}
+
+/// Submits one batch and updates the queue. Returns whether every reading was handled (pooled, included or
+/// rejected).
+pub async fn relay_once<C: ChainClient>(queue: &PersistentQueue, client: &C, config: &RelayConfig) -> io::Result<bool> {
+    let batch = queue.next_batch(config.batch_size, config.inclusion_timeout);
+    if batch.is_empty() {
+        return Ok(true);
+    }
+    let readings: Vec<_> = batch.iter().map(|(_, reading)| reading.clone()).collect();
+    let outcomes = match client.submit_batch(&readings).await {
+        Ok(outcomes) => outcomes,
+        Err(e) => {
+            eprintln!("Batch of {} readings not submitted: {}", readings.len(), e);
+            return Ok(false);
+        }
+    };
+    let mut complete = true;
+    for ((seq, reading), outcome) in batch.iter().zip(outcomes) {
+        match outcome {
+            Outcome::Accepted => queue.mark_submitted(*seq),
+            Outcome::Outdated => {
+                // Outdated after being pooled means included; otherwise the nonce was already used on chain.
+                if !queue.was_submitted(*seq) {
+                    eprintln!(
+                        "Dropping reading {} of device 0x{}: nonce already used on chain",
+                        reading.nonce,
+                        hex::encode(&reading.device_id)
+                    );
+                }
+                queue.remove(*seq)?;
+            }
+            Outcome::Rejected(reason) => {
+                eprintln!(
+                    "Dropping reading {} of device 0x{}: {}",
+                    reading.nonce,
+                    hex::encode(&reading.device_id),
+                    reason
+                );
+                queue.remove(*seq)?;
+            }
+            Outcome::Retry(_) => complete = false,
+        }
+    }
+    Ok(complete)
+}
+
+/// Relays readings until an I/O error occurs on the queue.
+///
+/// A batch is sent as soon as `batch_size` readings are queued or `batch_interval` has elapsed. After a failed or
+/// partially deferred batch the relay backs off exponentially, from `retry_base` up to `retry_max`.
+pub async fn run<C: ChainClient>(queue: &PersistentQueue, client: &C, config: &RelayConfig) -> io::Result<()> {
+    let mut backoff = config.retry_base;
+    loop {
+        let ready = queue.wait_for_items(config.batch_size, config.inclusion_timeout);
+        let _ = tokio::time::timeout(config.batch_interval, ready).await;
+        if relay_once(queue, client, config).await? {
+            backoff = config.retry_base;
+        } else {
+            tokio::time::sleep(backoff).await;
+            backoff = next_backoff(backoff, config.retry_max);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::TransportError;
+    use crate::reading::{reading, SignedReading};
+    use std::collections::{HashSet, VecDeque};
+    use std::sync::Mutex;
+
+    /// (device ID, nonce) of each reading in a submitted batch.
+    type SubmittedBatch = Vec<(Vec<u8>, u64)>;
+
+    /// Chain stand-in replaying scripted responses and recording submitted batches. Once the script runs out,
+    /// new readings are pooled and readings pooled before are reported included (outdated).
+    struct ScriptedChain {
+        responses: Mutex<VecDeque<Result<Vec<Outcome>, TransportError>>>,
+        batches: Mutex<Vec<SubmittedBatch>>,
+        pooled: Mutex<HashSet<(Vec<u8>, u64)>>,
+    }
+
+    impl ScriptedChain {
+        fn new(responses: Vec<Result<Vec<Outcome>, TransportError>>) -> Self {
+            ScriptedChain {
+                responses: Mutex::new(responses.into()),
+                batches: Mutex::new(Vec::new()),
+                pooled: Mutex::new(HashSet::new()),
+            }
+        }
+    }
+
+    impl ChainClient for ScriptedChain {
+        async fn submit_batch(&self, batch: &[SignedReading]) -> Result<Vec<Outcome>, TransportError> {
+            let keys: SubmittedBatch = batch.iter().map(|r| (r.device_id.clone(), r.nonce)).collect();
+            self.batches.lock().unwrap().push(keys.clone());
+            let mut pooled = self.pooled.lock().unwrap();
+            let outcomes = self.responses.lock().unwrap().pop_front().unwrap_or_else(|| {
+                Ok(keys.iter().map(|key| if pooled.contains(key) { Outcome::Outdated } else { Outcome::Accepted }).collect())
+            })?;
+            for (key, outcome) in keys.into_iter().zip(&outcomes) {
+                if *outcome == Outcome::Accepted {
+                    pooled.insert(key);
+                }
+            }
+            Ok(outcomes)
+        }
+    }
+
+    fn config() -> RelayConfig {
+        RelayConfig {
+            batch_size: 10,
+            batch_interval: Duration::from_millis(5),
+            retry_base: Duration::from_millis(1),
+            retry_max: Duration::from_millis(4),
+            inclusion_timeout: Duration::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_batches_are_retried_in_device_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = PersistentQueue::open(dir.path(), 10).unwrap();
+        queue.try_push(&reading("Meter001", 1)).unwrap();
+        queue.try_push(&reading("Meter001", 2)).unwrap();
+        queue.try_push(&reading("Meter002", 1)).unwrap();
+        let chain = ScriptedChain::new(vec![
+            Err(TransportError("connection refused".to_string())),
+            Ok(vec![Outcome::Retry("rate limited".to_string()), Outcome::Accepted]),
+        ]);
+
+        // The node is unreachable: nothing leaves the queue.
+        assert!(!relay_once(&queue, &chain, &config()).await.unwrap());
+        assert_eq!(queue.len(), 3);
+        // Meter001 is rate limited, Meter002 is pooled but stays queued until it is seen included.
+        assert!(!relay_once(&queue, &chain, &config()).await.unwrap());
+        assert_eq!(queue.len(), 3);
+        for _ in 0..4 {
+            assert!(relay_once(&queue, &chain, &config()).await.unwrap());
+        }
+        assert!(queue.is_empty());
+
+        let meter = |device: &str, nonce| (device.as_bytes().to_vec(), nonce);
+        let batches = chain.batches.lock().unwrap().clone();
+        assert_eq!(batches[2], vec![meter("Meter001", 1), meter("Meter002", 1)]);
+        assert_eq!(batches[3], vec![meter("Meter001", 1)]);
+        assert_eq!(batches[4], vec![meter("Meter001", 2)]);
+        assert_eq!(batches[5], vec![meter("Meter001", 2)]);
+    }
+
+    #[tokio::test]
+    async fn test_pooled_readings_are_kept_until_included() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = PersistentQueue::open(dir.path(), 10).unwrap();
+        queue.try_push(&reading("Meter001", 1)).unwrap();
+        let chain = ScriptedChain::new(vec![
+            Ok(vec![Outcome::Accepted]),
+            // Dropped from the pool without being included: pooled again on resubmission.
+            Ok(vec![Outcome::Accepted]),
+        ]);
+        let waiting = RelayConfig { inclusion_timeout: Duration::from_secs(3_600), ..config() };
+
+        assert!(relay_once(&queue, &chain, &waiting).await.unwrap());
+        // While the reading waits for inclusion, nothing is resubmitted.
+        assert!(relay_once(&queue, &chain, &waiting).await.unwrap());
+        assert_eq!(chain.batches.lock().unwrap().len(), 1);
+        assert_eq!(queue.len(), 1);
+
+        // After the timeout it is submitted again, and only dropped once the chain reports it outdated.
+        assert!(relay_once(&queue, &chain, &config()).await.unwrap());
+        assert_eq!(queue.len(), 1);
+        assert!(relay_once(&queue, &chain, &config()).await.unwrap());
+        assert!(queue.is_empty());
+        assert_eq!(chain.batches.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_readings_are_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = PersistentQueue::open(dir.path(), 10).unwrap();
+        queue.try_push(&reading("Meter001", 1)).unwrap();
+        let chain = ScriptedChain::new(vec![Ok(vec![Outcome::Rejected("Transaction is outdated".to_string())])]);
+        assert!(relay_once(&queue, &chain, &config()).await.unwrap());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let max = Duration::from_secs(8);
+        assert_eq!(next_backoff(Duration::from_secs(2), max), Duration::from_secs(4));
+        assert_eq!(next_backoff(Duration::from_secs(6), max), max);
+    }
+}
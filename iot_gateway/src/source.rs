@@ -0,0 +1,201 @@
+//! Ingestion of device messages from MQTT or HTTP into the persistent queue.
+//!
+//! MQTT messages are acknowledged only once their readings are persisted, so the broker redelivers anything the
+//! gateway had not stored when it stopped. While the queue is full the gateway stops reading from the broker, which
+//! holds further messages back (backpressure); HTTP clients get `503 Service Unavailable` instead.
+
+use crate::queue::PersistentQueue;
+use crate::reading::SignedReading;
+use axum::{extract::State, http::StatusCode, routing::post, Router};
+use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Packet, Publish, QoS};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A stream of raw device messages that must be acknowledged once handled.
+pub trait MessageSource {
+    /// Returns the next message, or `None` once the source is closed.
+    async fn recv(&mut self) -> Option<Vec<u8>>;
+    /// Acknowledges the message last returned by `recv`.
+    async fn ack(&mut self);
+}
+
+/// Moves messages from `source` into `queue` until the source closes.
+///
+/// Malformed messages are logged and acknowledged (redelivery would not fix them); messages whose readings cannot
+/// be persisted are left unacknowledged and the error is returned.
+pub async fn ingest<S: MessageSource>(source: &mut S, queue: &PersistentQueue) -> io::Result<()> {
+    while let Some(message) = source.recv().await {
+        match SignedReading::parse_message(&message) {
+            Ok(readings) => {
+                for reading in &readings {
+                    queue.push_wait(reading).await?;
+                }
+            }
+            Err(e) => eprintln!("Dropping malformed device message: {}", e),
+        }
+        source.ack().await;
+    }
+    Ok(())
+}
+
+/// Messages received from an MQTT broker subscription.
+pub struct MqttSource {
+    client: AsyncClient,
+    event_loop: EventLoop,
+    pending_ack: Option<Publish>,
+}
+
+impl MqttSource {
+    /// Connects to `host:port` and subscribes to `topic` with at-least-once delivery.
+    pub async fn connect(client_id: &str, host: &str, port: u16, topic: &str) -> Result<Self, rumqttc::ClientError> {
+        let mut options = MqttOptions::new(client_id, host, port);
+        options.set_keep_alive(Duration::from_secs(30));
+        // Keep the session so unacknowledged messages are redelivered after a restart.
+        options.set_clean_session(false);
+        options.set_manual_acks(true);
+        let (client, event_loop) = AsyncClient::new(options, 64);
+        client.subscribe(topic, QoS::AtLeastOnce).await?;
+        Ok(MqttSource { client, event_loop, pending_ack: None })
+    }
+}
+
+impl MessageSource for MqttSource {
+    async fn recv(&mut self) -> Option<Vec<u8>> {
+        loop {
+            match self.event_loop.poll().await {
+                Ok(Event::Incoming(Packet::Publish(publish))) => {
+                    let payload = publish.payload.to_vec();
+                    self.pending_ack = Some(publish);
+                    return Some(payload);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    // The event loop reconnects on the next poll.
+                    eprintln!("MQTT connection error: {}; reconnecting", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    }
+
+    async fn ack(&mut self) {
+        if let Some(publish) = self.pending_ack.take() {
+            if let Err(e) = self.client.ack(&publish).await {
+                eprintln!("Failed to acknowledge MQTT message: {}", e);
+            }
+        }
+    }
+}
+
+/// Serves `POST /readings` on `addr`; the body holds one reading or an array of readings.
+pub async fn serve_http(addr: SocketAddr, queue: Arc<PersistentQueue>) -> io::Result<()> {
+    let app = Router::new().route("/readings", post(post_readings)).with_state(queue);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await
+}
+
+async fn post_readings(State(queue): State<Arc<PersistentQueue>>, body: axum::body::Bytes) -> (StatusCode, String) {
+    let readings = match SignedReading::parse_message(&body) {
+        Ok(readings) => readings,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()),
+    };
+    for (accepted, reading) in readings.iter().enumerate() {
+        match queue.try_push(reading) {
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                return (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    format!("queue full; {} of {} readings accepted", accepted, readings.len()),
+                )
+            }
+            Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+    (StatusCode::ACCEPTED, format!("{} readings queued", readings.len()))
+}
+
+/// In-memory stand-in for an MQTT broker, used by tests.
+#[cfg(test)]
+pub mod test_broker {
+    use super::MessageSource;
+    use std::sync::{Arc, Mutex};
+    use tokio::sync::mpsc;
+
+    /// Publishing side of the stand-in broker.
+    #[derive(Clone)]
+    pub struct Broker {
+        sender: mpsc::UnboundedSender<Vec<u8>>,
+        acked: Arc<Mutex<usize>>,
+    }
+
+    /// Subscription side of the stand-in broker.
+    pub struct Subscription {
+        receiver: mpsc::UnboundedReceiver<Vec<u8>>,
+        acked: Arc<Mutex<usize>>,
+    }
+
+    /// Creates a broker with a single subscriber.
+    pub fn broker() -> (Broker, Subscription) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let acked = Arc::new(Mutex::new(0));
+        (Broker { sender, acked: acked.clone() }, Subscription { receiver, acked })
+    }
+
+    impl Broker {
+        pub fn publish(&self, message: Vec<u8>) {
+            self.sender.send(message).unwrap();
+        }
+
+        /// Number of messages acknowledged by the subscriber.
+        pub fn acked(&self) -> usize {
+            *self.acked.lock().unwrap()
+        }
+    }
+
+    impl MessageSource for Subscription {
+        async fn recv(&mut self) -> Option<Vec<u8>> {
+            self.receiver.recv().await
+        }
+
+        async fn ack(&mut self) {
+            *self.acked.lock().unwrap() += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_broker::broker;
+    use super::*;
+    use crate::reading::reading;
+
+    #[tokio::test]
+    async fn test_ingest_acknowledges_only_persisted_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = Arc::new(PersistentQueue::open(dir.path(), 2).unwrap());
+        let (broker, mut subscription) = broker();
+        let ingest = {
+            let queue = queue.clone();
+            tokio::spawn(async move { ingest(&mut subscription, &queue).await })
+        };
+
+        broker.publish(b"garbage".to_vec());
+        broker.publish(serde_json::to_vec(&vec![reading("Meter001", 1), reading("Meter002", 1)]).unwrap());
+        broker.publish(serde_json::to_vec(&reading("Meter001", 2)).unwrap());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        // The malformed message and the first batch are handled; the queue is full, so the third message is held.
+        assert_eq!(queue.len(), 2);
+        assert_eq!(broker.acked(), 2);
+
+        let (seq, _) = queue.next_batch(1, Duration::ZERO).remove(0);
+        queue.remove(seq).unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(queue.len(), 2);
+        assert_eq!(broker.acked(), 3);
+
+        drop(broker);
+        ingest.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,313 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![recursion_limit = "1024"]
+
+//! # Nodara Fee Split Module - Transaction Fee Routing
+//!
+//! This module implements the fee distribution described in the Nodara tokenomics. Transaction fees collected by the
+//! runtime's `OnUnbalanced` handler are split between the reserve fund, the founders, a burn fraction, and the
+//! staking/community reward pool according to governance-adjustable percentages. Every block emits a summary of the
+//! fees it distributed, keeping the flow of fees fully auditable.
+//!
+//! ## Key Advanced Features:
+//! - **Governance-Adjustable Split:**
+//!   Reserve, founders and burn shares are set by the governance origin; the remainder funds staking and community rewards.
+//! - **Pool Crediting:**
+//!   Reserve and reward shares are credited to `nodara_reserve_fund` and `nodara_reward_engine` through the `FeeSink` trait.
+//! - **Per-Block Summary:**
+//!   Fees routed during a block are accumulated and reported once in `on_finalize`.
+//! - **Simulated Formal Verification:**
+//!   Internal invariant checks ensure that the shares of a split always add up to the collected amount.
+//!
+//! ## Module Structure:
+//! - **Storage:**
+//!   - `FeeSplit`: The governance-set split percentages (defaults come from the pallet configuration).
+//!   - `BlockFees`: Fees distributed in the current block, reported and cleared at the end of the block.
+//! - **Core Functions:**
+//!   - `set_fee_split`: Updates the split percentages (governance origin only).
+//!   - `split_fees`: Computes how an amount of fees is divided between the recipients.
+//!   - `split_amount`: Same split for amounts in the runtime's native balance type.
+//!   - `distribute_fees`: Credits the reserve fund and reward pool and records the distribution, or reports why a
+//!     pool rejected it.
+//!   - `distribute_native_fees`: Same for a split in the native balance, rejecting shares that do not fit in the
+//!     pools' `u32` accounting.
+//!
+//! ## Version:
+//! March 2025 – Nodara BIOSPHÈRE QUANTIC Legendary Edition
+
+use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::{EnsureOrigin, Get}};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, CheckedConversion, SaturatedConversion},
+    DispatchError, Percent,
+};
+use sp_std::vec::Vec;
+
+/// Receiver of a share of the transaction fees (e.g., the reserve fund or the reward pool).
+pub trait FeeSink {
+    /// Credits `amount` to the sink, with a descriptive `reason` for its audit log.
+    fn credit(amount: u32, reason: Vec<u8>) -> DispatchResult;
+}
+
+/// Null sink: accepts and discards every credit.
+impl FeeSink for () {
+    fn credit(_amount: u32, _reason: Vec<u8>) -> DispatchResult {
+        Ok(())
+    }
+}
+
+/// Macro to simulate formal invariant checks.
+macro_rules! assert_invariant {
+    ($condition:expr, $msg:expr) => {
+        debug_assert!($condition, "Invariant violation: {}", $msg);
+    };
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+
+    /// Percentages of collected fees routed to each recipient. Whatever is left after the reserve, founders and
+    /// burn shares goes to the staking/community reward pool.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct FeeSplitConfig {
+        /// Share credited to the reserve fund.
+        pub reserve: Percent,
+        /// Share paid to the founders.
+        pub founders: Percent,
+        /// Share removed from circulation.
+        pub burn: Percent,
+    }
+
+    /// Amounts resulting from splitting a quantity of fees. Pool accounting uses `u32`; the runtime splits the
+    /// underlying imbalance in its native `Balance`.
+    #[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct FeeDistribution<Balance = u32> {
+        /// Amount credited to the reserve fund.
+        pub reserve: Balance,
+        /// Amount paid to the founders.
+        pub founders: Balance,
+        /// Amount burned.
+        pub burned: Balance,
+        /// Amount credited to the staking/community reward pool.
+        pub staking: Balance,
+    }
+
+    impl<Balance: AtLeast32BitUnsigned + Copy> FeeDistribution<Balance> {
+        /// Total amount covered by this distribution.
+        pub fn total(&self) -> Balance {
+            self.reserve
+                .saturating_add(self.founders)
+                .saturating_add(self.burned)
+                .saturating_add(self.staking)
+        }
+
+        /// Converts the amounts to the `u32` unit used by the pools' accounting, or `None` if any amount does not fit.
+        pub fn checked(&self) -> Option<FeeDistribution> {
+            Some(FeeDistribution {
+                reserve: self.reserve.checked_into()?,
+                founders: self.founders.checked_into()?,
+                burned: self.burned.checked_into()?,
+                staking: self.staking.checked_into()?,
+            })
+        }
+
+        fn accrue(&mut self, other: &FeeDistribution) {
+            self.reserve = self.reserve.saturating_add(other.reserve);
+            self.founders = self.founders.saturating_add(other.founders);
+            self.burned = self.burned.saturating_add(other.burned);
+            self.staking = self.staking.saturating_add(other.staking);
+        }
+    }
+
+    /// Main pallet structure for fee routing.
+    #[frame_support::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Governance-set split percentages. `None` means the configured defaults apply.
+    #[pallet::storage]
+    pub type FeeSplit<T: Config> = StorageValue<_, FeeSplitConfig, OptionQuery>;
+
+    /// Fees distributed during the current block.
+    #[pallet::storage]
+    #[pallet::getter(fn block_fees)]
+    pub type BlockFees<T: Config> = StorageValue<_, FeeDistribution, ValueQuery>;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The runtime event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Origin allowed to change the fee split (e.g., DAO governance).
+        type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+        /// Receiver of the reserve share (typically `nodara_reserve_fund`).
+        type ReserveFund: FeeSink;
+        /// Receiver of the staking/community share (typically `nodara_reward_engine`).
+        type RewardPool: FeeSink;
+        /// Default reserve share (10% in the tokenomics).
+        #[pallet::constant]
+        type DefaultReserveShare: Get<Percent>;
+        /// Default founders share (5% in the tokenomics).
+        #[pallet::constant]
+        type DefaultFoundersShare: Get<Percent>;
+        /// Default burn share.
+        #[pallet::constant]
+        type DefaultBurnShare: Get<Percent>;
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Emitted when governance changes the fee split (new split).
+        FeeSplitUpdated(FeeSplitConfig),
+        /// Emitted at the end of each block in which fees were distributed (block summary).
+        FeesDistributed(FeeDistribution),
+        /// Emitted when a pool rejected its share and the distribution was not credited (distribution, error).
+        FeeDistributionFailed(FeeDistribution, DispatchError),
+        /// Emitted when a share of the fees does not fit in the pools' `u32` accounting and the distribution was not
+        /// credited (total fees in the native balance).
+        FeeDistributionTooLarge(u128),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The reserve, founders and burn shares add up to more than 100%.
+        InvalidFeeSplit,
+        /// A share of the fees does not fit in the pools' `u32` accounting.
+        FeeAmountTooLarge,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_finalize(_n: BlockNumberFor<T>) {
+            let summary = <BlockFees<T>>::take();
+            if summary.total() > 0 {
+                Self::deposit_event(Event::FeesDistributed(summary));
+            }
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Updates the fee split percentages.
+        ///
+        /// # Parameters:
+        /// - `origin`: Must satisfy `GovernanceOrigin`.
+        /// - `split`: The new split.
+        ///
+        /// # Requirements:
+        /// - The reserve, founders and burn shares must not exceed 100% in total.
+        pub fn set_fee_split(origin: T::Origin, split: FeeSplitConfig) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            let assigned = split.reserve.deconstruct() as u32
+                + split.founders.deconstruct() as u32
+                + split.burn.deconstruct() as u32;
+            ensure!(assigned <= 100, Error::<T>::InvalidFeeSplit);
+            <FeeSplit<T>>::put(split);
+            Self::deposit_event(Event::FeeSplitUpdated(split));
+            Ok(())
+        }
+
+        /// Returns the split currently in force.
+        pub fn fee_split() -> FeeSplitConfig {
+            <FeeSplit<T>>::get().unwrap_or(FeeSplitConfig {
+                reserve: T::DefaultReserveShare::get(),
+                founders: T::DefaultFoundersShare::get(),
+                burn: T::DefaultBurnShare::get(),
+            })
+        }
+
+        /// Computes how `amount` is divided between the recipients. Rounding dust goes to the staking share.
+        pub fn split_fees(amount: u32) -> FeeDistribution {
+            Self::split_amount(amount)
+        }
+
+        /// Same as `split_fees`, for an amount in any balance type (e.g. the runtime's native `Balance`).
+        pub fn split_amount<Balance: AtLeast32BitUnsigned + Copy>(amount: Balance) -> FeeDistribution<Balance> {
+            let split = Self::fee_split();
+            let reserve = split.reserve.mul_floor(amount);
+            let founders = split.founders.mul_floor(amount);
+            let burned = split.burn.mul_floor(amount);
+            let staking = amount.saturating_sub(reserve).saturating_sub(founders).saturating_sub(burned);
+            let distribution = FeeDistribution { reserve, founders, burned, staking };
+            // Invariant check: The shares must add up to the collected amount.
+            assert_invariant!(distribution.total() == amount, "Fee split does not cover the collected amount");
+            distribution
+        }
+
+        /// Credits the reserve and reward shares of `distribution` and adds it to the block summary.
+        ///
+        /// Founders and burn shares are settled by the caller (the runtime's fee handler), which owns the
+        /// underlying imbalance. If the reserve fund rejects its share (e.g., it is at `MaxReserve`), nothing is
+        /// credited or recorded, a `FeeDistributionFailed` event is emitted and the error is returned so that the
+        /// caller does not deposit funds the pools have not accounted for.
+        pub fn distribute_fees(distribution: FeeDistribution) -> DispatchResult {
+            Self::credit_pools(&distribution).map_err(|error| {
+                Self::deposit_event(Event::FeeDistributionFailed(distribution, error));
+                error
+            })?;
+            <BlockFees<T>>::mutate(|summary| summary.accrue(&distribution));
+            Ok(())
+        }
+
+        /// Distributes a split made in the runtime's native balance (see `split_amount`).
+        ///
+        /// The pools account in `u32`. If any share does not fit, nothing is credited or recorded, a
+        /// `FeeDistributionTooLarge` event is emitted and `FeeAmountTooLarge` is returned, so that the caller does
+        /// not deposit funds the pools have not accounted for; amounts are never silently capped.
+        pub fn distribute_native_fees<Balance: AtLeast32BitUnsigned + Copy>(
+            distribution: FeeDistribution<Balance>,
+        ) -> DispatchResult {
+            let Some(converted) = distribution.checked() else {
+                Self::deposit_event(Event::FeeDistributionTooLarge(distribution.total().saturated_into()));
+                return Err(Error::<T>::FeeAmountTooLarge.into());
+            };
+            Self::distribute_fees(converted)
+        }
+
+        /// Credits the reserve share, then the reward share. The reserve goes first because it is the only
+        /// bounded pool, so a rejection leaves both pools untouched.
+        fn credit_pools(distribution: &FeeDistribution) -> DispatchResult {
+            if distribution.reserve > 0 {
+                T::ReserveFund::credit(distribution.reserve, b"Transaction Fees".to_vec())?;
+            }
+            if distribution.staking > 0 {
+                T::RewardPool::credit(distribution.staking, b"Transaction Fees".to_vec())?;
+            }
+            Ok(())
+        }
+    }
+}
+
+// --- Benchmarking Section ---
+//
+// The following benchmarks measure the cost of fee routing, which runs for every transaction.
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking {
+    use super::*;
+    use frame_benchmarking::benchmarks;
+    use frame_system::RawOrigin;
+
+    benchmarks! {
+        set_fee_split {
+            let split = FeeSplitConfig {
+                reserve: Percent::from_percent(10),
+                founders: Percent::from_percent(5),
+                burn: Percent::from_percent(20),
+            };
+        }: {
+            Pallet::<T>::set_fee_split(RawOrigin::Root.into(), split)?;
+        }
+        verify {
+            assert_eq!(Pallet::<T>::fee_split(), split);
+        }
+
+        distribute_fees {
+            let distribution = Pallet::<T>::split_fees(1_000);
+        }: {
+            Pallet::<T>::distribute_fees(distribution)?;
+        }
+        verify {
+            assert_eq!(Pallet::<T>::block_fees().total(), 1_000);
+        }
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::Test);
+}
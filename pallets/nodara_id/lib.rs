@@ -10,13 +10,38 @@
 //!
 //! ## Key Advanced Features:
 //! - **Secure Identity Registration:**  
-//!   Allows users to register with encrypted KYC data and sets a default verification status.
+//!   Allows users to register a salted hash commitment of their KYC data (plus an optional reference to an encrypted
+//!   off-chain blob); no personal data is stored on-chain. New identities start unverified.
+//! - **Selective Disclosure:**  
+//!   The commitment is the Merkle root of salted KYC fields, so a user can reveal a single field off-chain and anyone
+//!   can check it against the on-chain commitment with `verify_disclosure`.
+//! - **Registrar Judgements:**  
+//!   Governance appoints registrars who, for a fee, judge identities as unknown, reasonable, known-good or erroneous.
+//!   An identity is verified only while it holds a reasonable or known-good judgement younger than
+//!   `JudgementValidity`; accounts cannot verify themselves.
 //! - **Periodic Identity Updates:**  
-//!   Supports updates to KYC information and verification status, with full logging of changes.
+//!   Supports updates to the KYC commitment with full logging of changes. Changing the commitment clears all
+//!   judgements, so the new data must be verified again.
 //! - **Simulated Formal Verification:**  
 //!   Internal invariant checks simulate formal verification to ensure data consistency.
 //! - **Immutable Audit Logging:**  
 //!   Every identity event is logged with a timestamp, ensuring full traceability.
+//! - **Decentralized Identifiers (DIDs):**  
+//!   W3C-style DID documents with a controller, typed verification keys (sr25519, ed25519, ECDSA) that can be
+//!   rotated, and service endpoints, usable by people, enterprises and IoT devices alike.
+//! - **Verifiable Credentials:**  
+//!   Issuers registered by governance for specific claim types issue credentials to DIDs, carrying a hash of the
+//!   claims and an optional expiry. Each issuer maintains an on-chain revocation registry, and the `NodaraIdApi`
+//!   runtime API reports a credential's status without a transaction.
+//! - **Sub-Identities:**  
+//!   Devices, bots and hot wallets accept an invitation to link under a primary identity with a label, against a
+//!   per-sub deposit, and share its verification.
+//! - **Social Recovery:**  
+//!   A threshold of pre-registered friends can migrate an identity (with its sub-accounts and reputation) to a new
+//!   account after a delay, so a lost key no longer means a lost identity.
+//! - **Right to Erasure:**  
+//!   Owners can clear their identity at any time; pending fees and deposits are released and the account's history
+//!   entries, which only ever hold commitments, are erased.
 //! - **DAO Governance Integration:**  
 //!   Allows community-driven modifications to KYC criteria and identity policies.
 //! - **Performance Optimizations:**  
@@ -24,12 +49,34 @@
 //!
 //! ## Module Structure:
 //! - **Storage:**
-//!   - `Identities`: A mapping from account IDs to identity data (KYC details and verification status).
-//!   - `IdentityHistory`: An immutable log of all identity events, stored as tuples (timestamp, account, previous status, new status, details).
+//!   - `Identities`: A mapping from account IDs to identity data (KYC commitment and dated registrar judgements).
+//!   - `Registrars`: The registrars appointed by governance, with their accounts and fees.
+//!   - `Dids`: DID documents, keyed by DID identifier.
+//!   - `Issuers`: Credential issuers and the claim types each may issue.
+//!   - `Credentials` / `Revocations`: Issued credentials and each issuer's revocation registry.
+//!   - `SubsOf` / `SuperOf`: Sub-accounts of each primary identity, and the primary and label of each sub-account.
+//!   - `PendingSubs` / `SubDeposits`: Sub-account invitations awaiting acceptance, and the deposit held for each sub.
+//!   - `Recoverable` / `ActiveRecoveries`: Recovery configurations and the recovery attempts in progress.
+//!   - `IdentityHistory`: An immutable log of all identity events, stored as tuples (timestamp, account, previous status, new status, commitment).
 //! - **Core Functions:**
-//!   - `register_identity`: Registers a new identity with provided KYC details.
-//!   - `update_identity`: Updates an existing identity’s KYC details and verification status.
+//!   - `register_identity`: Registers a new identity with a KYC commitment.
+//!   - `update_identity`: Updates an existing identity’s KYC commitment, resetting its judgements if it changed.
+//!   - `add_registrar` / `set_registrar_fee`: Appoint a registrar (governance origin) and let it set its fee.
+//!   - `request_judgement` / `cancel_request`: Reserve a registrar's fee and ask for (or withdraw) a judgement.
+//!   - `provide_judgement`: A registrar judges the commitment it checked and collects the reserved fee.
+//!   - `verify_disclosure`: Checks a revealed KYC field against an identity's commitment.
+//!   - `create_did` / `rotate_did_keys` / `set_did_services` / `set_did_controller` / `deactivate_did`: Manage DID
+//!     documents (controller only).
+//!   - `add_issuer` / `remove_issuer`: Register credential issuers and their claim types (governance origin).
+//!   - `issue_credential` / `revoke_credential` / `credential_status`: Credential lifecycle and status checks.
+//!   - `clear_identity`: Removes the caller's identity and its history entries, releasing reserved funds.
+//!   - `is_identity_verified`: Whether an identity holds an unexpired verifying judgement.
+//!   - `add_sub` / `accept_sub` / `remove_sub` / `quit_sub`: Invite, accept and unlink sub-accounts.
+//!   - `create_recovery` / `initiate_recovery` / `vouch_recovery` / `claim_recovery` / `cancel_recovery`: Social
+//!     recovery of an identity to a new account.
 //!   - `verify_invariants`: (Internal) Ensures that critical invariants hold after each operation.
+//! - **Integration:**
+//!   - `IdentityProvider`: Exposes verification status and identity membership to other modules (e.g., reputation).
 //!
 //! ## Version:
 //! March 2025 – Nodara BIOSPHÈRE QUANTIC Legendary Edition
@@ -39,10 +86,73 @@
 //! - Enhanced formal verification through dedicated tools.
 //! - Parallel offchain processing for bulk identity verifications.
 
-use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::Get};
+use frame_support::{
+    dispatch::DispatchResult,
+    pallet_prelude::*,
+    traits::{BalanceStatus, Currency, EnsureOrigin, Get, ReservableCurrency},
+};
+use codec::Codec;
+use sp_runtime::traits::{Hash, Saturating, Zero};
 use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 
+/// Read-only access to identity status, consumed by pallets that must tie their logic to real identities
+/// (e.g., reputation, to resist Sybil accounts).
+pub trait IdentityProvider<AccountId> {
+    /// Returns whether `who` holds a verified identity.
+    fn is_verified(who: &AccountId) -> bool;
+    /// Returns the account holding the identity `who` belongs to, or `None` if `who` has no identity.
+    fn identity_of(who: &AccountId) -> Option<AccountId>;
+}
+
+/// Hook invoked when an identity moves to a new account through social recovery, so that state keyed by the old
+/// account (e.g., reputation) can follow it.
+pub trait OnIdentityMigrated<AccountId> {
+    /// Called after the identity of `old` has been moved to `new`.
+    fn on_identity_migrated(old: &AccountId, new: &AccountId);
+}
+
+/// No-op hook.
+impl<AccountId> OnIdentityMigrated<AccountId> for () {
+    fn on_identity_migrated(_old: &AccountId, _new: &AccountId) {}
+}
+
+/// Null provider: no account has an identity.
+impl<AccountId> IdentityProvider<AccountId> for () {
+    fn is_verified(_who: &AccountId) -> bool {
+        false
+    }
+
+    fn identity_of(_who: &AccountId) -> Option<AccountId> {
+        None
+    }
+}
+
+/// Status of a verifiable credential, as reported by `credential_status` and the `NodaraIdApi` runtime API.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CredentialStatus {
+    /// Issued by a registered issuer to an active DID, not expired and not revoked.
+    Valid,
+    /// Past its expiry block.
+    Expired,
+    /// Revoked by its issuer.
+    Revoked,
+    /// The issuer was removed, or is no longer registered for the credential's claim type.
+    IssuerRemoved,
+    /// The subject DID has been deactivated.
+    SubjectDeactivated,
+    /// No credential with this identifier was issued.
+    Unknown,
+}
+
+sp_api::decl_runtime_apis! {
+    /// Runtime API for verifying credentials issued through `nodara_id`.
+    pub trait NodaraIdApi<Hash> where Hash: Codec {
+        /// Returns the status of the credential `credential_id`.
+        fn credential_status(credential_id: Hash) -> CredentialStatus;
+    }
+}
+
 /// Macro to simulate formal invariant checks. In a production system, these would be replaced with rigorous formal proofs.
 macro_rules! assert_invariant {
     ($condition:expr, $msg:expr) => {
@@ -54,13 +164,149 @@ macro_rules! assert_invariant {
 pub mod pallet {
     use super::*;
 
-    /// Structure to store identity data.
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// Index of a registrar in `Registrars`.
+    pub type RegistrarIndex = u32;
+
+    /// A registrar's opinion of an identity.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum Judgement<Balance> {
+        /// A judgement was requested and the fee is reserved; the registrar has not decided yet.
+        FeePaid(Balance),
+        /// The registrar could not establish the identity.
+        Unknown,
+        /// The KYC details appear reasonable, but were not checked in depth.
+        Reasonable,
+        /// The registrar has verified the KYC details.
+        KnownGood,
+        /// The KYC details are wrong or fraudulent.
+        Erroneous,
+    }
+
+    impl<Balance> Judgement<Balance> {
+        /// Whether the judgement makes an identity verified.
+        pub fn is_verifying(&self) -> bool {
+            matches!(self, Judgement::Reasonable | Judgement::KnownGood)
+        }
+
+        /// Whether the judgement blocks a new request to the same registrar: a request is pending, or the
+        /// registrar has reached a definitive (known-good or erroneous) opinion.
+        pub fn is_sticky(&self) -> bool {
+            matches!(self, Judgement::FeePaid(_) | Judgement::KnownGood | Judgement::Erroneous)
+        }
+
+        /// Whether the judgement is still awaiting the registrar.
+        pub fn is_pending(&self) -> bool {
+            matches!(self, Judgement::FeePaid(_))
+        }
+    }
+
+    /// A registrar appointed by governance.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct RegistrarInfo<AccountId, Balance> {
+        /// Account that issues judgements and receives fees.
+        pub account: AccountId,
+        /// Fee charged for a judgement.
+        pub fee: Balance,
+    }
+
+    /// Signature scheme of a DID verification key.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum KeyType {
+        /// Schnorr signatures over Ristretto (Substrate's default).
+        Sr25519,
+        /// Ed25519 signatures.
+        Ed25519,
+        /// ECDSA signatures over secp256k1.
+        Ecdsa,
+    }
+
+    /// A verification key of a DID document.
     #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-    pub struct IdentityData {
-        /// Encrypted KYC details (e.g., in JSON format).
-        pub kyc_details: Vec<u8>,
-        /// Verification status: true if verified, false otherwise.
-        pub verified: bool,
+    pub struct DidKey {
+        /// Signature scheme of the key.
+        pub key_type: KeyType,
+        /// Encoded public key.
+        pub public_key: Vec<u8>,
+    }
+
+    /// A service endpoint of a DID document (e.g., a device gateway or a credential hub).
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ServiceEndpoint {
+        /// Identifier of the service within the document.
+        pub id: Vec<u8>,
+        /// Type of the service.
+        pub service_type: Vec<u8>,
+        /// URL of the service.
+        pub endpoint: Vec<u8>,
+    }
+
+    /// A DID document.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct DidDocument<AccountId, BlockNumber> {
+        /// Account allowed to update the document.
+        pub controller: AccountId,
+        /// Verification keys; the first key is the authentication key.
+        pub keys: Vec<DidKey>,
+        /// Service endpoints.
+        pub services: Vec<ServiceEndpoint>,
+        /// Block of the last change.
+        pub updated_at: BlockNumber,
+        /// Whether the DID has been deactivated; deactivated documents cannot change or receive credentials.
+        pub deactivated: bool,
+    }
+
+    /// A verifiable credential. The claims themselves stay off-chain; only their hash is recorded.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Credential<AccountId, Hash, BlockNumber> {
+        /// Issuer of the credential.
+        pub issuer: AccountId,
+        /// DID the credential is about.
+        pub subject: Hash,
+        /// Type of the claims (e.g., `KycAml`, `DeviceCertification`).
+        pub claim_type: Vec<u8>,
+        /// Hash of the claims document.
+        pub claims_hash: Hash,
+        /// Block of issuance.
+        pub issued_at: BlockNumber,
+        /// Block after which the credential is expired, if any.
+        pub expires_at: Option<BlockNumber>,
+    }
+
+    /// Social recovery configuration of an identity.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct RecoveryConfig<AccountId, BlockNumber> {
+        /// Friends who may vouch for a rescuer, sorted.
+        pub friends: Vec<AccountId>,
+        /// Number of vouching friends required.
+        pub threshold: u16,
+        /// Blocks that must pass between initiating and claiming a recovery.
+        pub delay: BlockNumber,
+    }
+
+    /// A recovery attempt in progress.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ActiveRecovery<AccountId, Balance, BlockNumber> {
+        /// Block at which the attempt was initiated.
+        pub created: BlockNumber,
+        /// Deposit reserved from the rescuer.
+        pub deposit: Balance,
+        /// Friends who have vouched, sorted.
+        pub friends: Vec<AccountId>,
+    }
+
+    /// Structure to store identity data. Only a commitment to the KYC data is kept on-chain.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct IdentityData<Hash, Balance, BlockNumber> {
+        /// Merkle root of the salted KYC fields, each leaf being `hash((field, value, salt))`.
+        pub kyc_commitment: Hash,
+        /// Optional reference to an encrypted off-chain KYC blob (e.g., a content identifier); never the data itself.
+        pub kyc_reference: Vec<u8>,
+        /// Judgements requested from or given by registrars, with the block they were requested or given at,
+        /// sorted by registrar index.
+        pub judgements: Vec<(RegistrarIndex, Judgement<Balance>, BlockNumber)>,
     }
 
     /// Main pallet structure for decentralized identity management.
@@ -70,109 +316,957 @@ pub mod pallet {
     /// Storage mapping for identities, from account ID to IdentityData.
     #[pallet::storage]
     #[pallet::getter(fn identities)]
-    pub type Identities<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, IdentityData, OptionQuery>;
+    pub type Identities<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, IdentityData<T::Hash, BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+    /// Registrars appointed by governance, indexed by `RegistrarIndex`.
+    #[pallet::storage]
+    #[pallet::getter(fn registrars)]
+    pub type Registrars<T: Config> = StorageValue<_, Vec<RegistrarInfo<T::AccountId, BalanceOf<T>>>, ValueQuery>;
 
     /// Storage for logging identity events.
-    /// Each record: (timestamp, account, previous verification status, new verification status, details)
+    /// Each record: (timestamp, account, previous verification status, new verification status, KYC commitment)
     #[pallet::storage]
     #[pallet::getter(fn identity_history)]
-    pub type IdentityHistory<T: Config> = StorageValue<_, Vec<(u64, T::AccountId, bool, bool, Vec<u8>)>, ValueQuery>;
+    pub type IdentityHistory<T: Config> = StorageValue<_, Vec<(u64, T::AccountId, bool, bool, T::Hash)>, ValueQuery>;
+
+    /// DID documents, keyed by DID identifier.
+    #[pallet::storage]
+    #[pallet::getter(fn dids)]
+    pub type Dids<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, DidDocument<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+    /// Number of DIDs created so far, used to derive unique identifiers.
+    #[pallet::storage]
+    pub type DidNonce<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Credential issuers and the claim types each may issue.
+    #[pallet::storage]
+    #[pallet::getter(fn issuers)]
+    pub type Issuers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Vec<Vec<u8>>, OptionQuery>;
+
+    /// Issued credentials, keyed by credential identifier.
+    #[pallet::storage]
+    #[pallet::getter(fn credentials)]
+    pub type Credentials<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, Credential<T::AccountId, T::Hash, T::BlockNumber>, OptionQuery>;
+
+    /// Revocation registry of each issuer: (issuer, credential ID) to the block of revocation.
+    #[pallet::storage]
+    #[pallet::getter(fn revocations)]
+    pub type Revocations<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::Hash, T::BlockNumber, OptionQuery>;
+
+    /// Sub-accounts linked under each primary identity.
+    #[pallet::storage]
+    #[pallet::getter(fn subs_of)]
+    pub type SubsOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Vec<T::AccountId>, ValueQuery>;
+
+    /// Primary identity and label of each sub-account.
+    #[pallet::storage]
+    #[pallet::getter(fn super_of)]
+    pub type SuperOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (T::AccountId, Vec<u8>), OptionQuery>;
+
+    /// Sub-account invitations awaiting the sub's acceptance: (primary, sub-account) to (label, reserved deposit).
+    #[pallet::storage]
+    #[pallet::getter(fn pending_subs)]
+    pub type PendingSubs<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        (Vec<u8>, BalanceOf<T>),
+        OptionQuery,
+    >;
+
+    /// Deposit held for each linked sub-account: sub-account to (depositor, deposit).
+    #[pallet::storage]
+    #[pallet::getter(fn sub_deposits)]
+    pub type SubDeposits<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+    /// Recovery configuration of each recoverable identity.
+    #[pallet::storage]
+    #[pallet::getter(fn recoverable)]
+    pub type Recoverable<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, RecoveryConfig<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+    /// Recovery attempts in progress: (lost account, rescuer) to the attempt.
+    #[pallet::storage]
+    #[pallet::getter(fn active_recoveries)]
+    pub type ActiveRecoveries<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        ActiveRecovery<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+        OptionQuery,
+    >;
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The runtime event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-        /// Default verification status for new identities.
-        #[pallet::constant]
-        type DefaultVerification: Get<bool>;
-        /// Maximum allowed length for KYC details.
+        /// Maximum allowed length for the encrypted KYC blob reference.
         #[pallet::constant]
         type MaxKycLength: Get<u32>;
+        /// Currency in which registrar fees are paid.
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// Origin allowed to appoint registrars (e.g., DAO governance).
+        type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+        /// Maximum number of registrars.
+        #[pallet::constant]
+        type MaxRegistrars: Get<u32>;
+        /// Maximum number of keys, and of service endpoints, in a DID document.
+        #[pallet::constant]
+        type MaxDidEntries: Get<u32>;
+        /// Maximum length of a key, service field or claim type.
+        #[pallet::constant]
+        type MaxDidFieldLength: Get<u32>;
+        /// Maximum number of sub-accounts per identity, pending invitations included.
+        #[pallet::constant]
+        type MaxSubAccounts: Get<u32>;
+        /// Deposit reserved from the primary for each invited or linked sub-account; returned when it is unlinked.
+        #[pallet::constant]
+        type SubAccountDeposit: Get<BalanceOf<Self>>;
+        /// Maximum number of recovery friends.
+        #[pallet::constant]
+        type MaxFriends: Get<u32>;
+        /// Deposit reserved from a rescuer when initiating a recovery; forfeited to the owner if they cancel it.
+        #[pallet::constant]
+        type RecoveryDeposit: Get<BalanceOf<Self>>;
+        /// Number of blocks a reasonable or known-good judgement remains valid; zero means judgements never expire.
+        #[pallet::constant]
+        type JudgementValidity: Get<Self::BlockNumber>;
+        /// Hook notified when an identity is recovered to a new account (typically `nodara_reputation`).
+        type OnIdentityMigrated: OnIdentityMigrated<Self::AccountId>;
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// Emitted when an identity is registered: (account, kyc_details, verification status).
-        IdentityRegistered(T::AccountId, Vec<u8>, bool),
-        /// Emitted when an identity is updated: (account, new kyc_details, previous status, new status).
-        IdentityUpdated(T::AccountId, Vec<u8>, bool, bool),
+        /// Emitted when an identity is registered: (account, KYC commitment, verification status).
+        IdentityRegistered(T::AccountId, T::Hash, bool),
+        /// Emitted when an identity is updated: (account, new KYC commitment, previous status, new status).
+        IdentityUpdated(T::AccountId, T::Hash, bool, bool),
+        /// Emitted when governance appoints a registrar: (registrar index, registrar account).
+        RegistrarAdded(RegistrarIndex, T::AccountId),
+        /// Emitted when a registrar changes its fee: (registrar index, new fee).
+        RegistrarFeeSet(RegistrarIndex, BalanceOf<T>),
+        /// Emitted when an account requests a judgement: (account, registrar index).
+        JudgementRequested(T::AccountId, RegistrarIndex),
+        /// Emitted when an account withdraws a pending request: (account, registrar index).
+        JudgementUnrequested(T::AccountId, RegistrarIndex),
+        /// Emitted when a registrar judges an identity: (account, registrar index, judgement).
+        JudgementGiven(T::AccountId, RegistrarIndex, Judgement<BalanceOf<T>>),
+        /// Emitted when a DID is created: (DID, controller).
+        DidCreated(T::Hash, T::AccountId),
+        /// Emitted when a DID document changes (keys, services or controller): (DID).
+        DidUpdated(T::Hash),
+        /// Emitted when a DID is deactivated: (DID).
+        DidDeactivated(T::Hash),
+        /// Emitted when governance registers or updates an issuer: (issuer, claim types).
+        IssuerAdded(T::AccountId, Vec<Vec<u8>>),
+        /// Emitted when governance removes an issuer: (issuer).
+        IssuerRemoved(T::AccountId),
+        /// Emitted when a credential is issued: (credential ID, issuer, subject DID, claim type).
+        CredentialIssued(T::Hash, T::AccountId, T::Hash, Vec<u8>),
+        /// Emitted when an issuer revokes a credential: (credential ID, issuer).
+        CredentialRevoked(T::Hash, T::AccountId),
+        /// Emitted when a primary invites a sub-account: (primary, sub-account, label).
+        SubInvited(T::AccountId, T::AccountId, Vec<u8>),
+        /// Emitted when a pending invitation is withdrawn: (primary, sub-account).
+        SubInvitationCancelled(T::AccountId, T::AccountId),
+        /// Emitted when a sub-account accepts its invitation and is linked: (primary, sub-account, label).
+        SubAdded(T::AccountId, T::AccountId, Vec<u8>),
+        /// Emitted when a sub-account is unlinked: (primary, sub-account).
+        SubRemoved(T::AccountId, T::AccountId),
+        /// Emitted when an identity configures social recovery: (account).
+        RecoveryCreated(T::AccountId),
+        /// Emitted when a rescuer starts a recovery: (lost account, rescuer).
+        RecoveryInitiated(T::AccountId, T::AccountId),
+        /// Emitted when a friend vouches for a rescuer: (lost account, rescuer, friend).
+        RecoveryVouched(T::AccountId, T::AccountId, T::AccountId),
+        /// Emitted when an identity is moved to the rescuer: (lost account, rescuer).
+        IdentityRecovered(T::AccountId, T::AccountId),
+        /// Emitted when the owner cancels a recovery attempt: (lost account, rescuer).
+        RecoveryCancelled(T::AccountId, T::AccountId),
+        /// Emitted when an owner clears its identity: (account).
+        IdentityCleared(T::AccountId),
     }
 
     #[pallet::error]
     pub enum Error<T> {
-        /// KYC details exceed the maximum allowed length.
+        /// The KYC blob reference exceeds the maximum allowed length.
         KycTooLong,
         /// Identity already exists for the account.
         IdentityAlreadyExists,
         /// Identity not found for update.
         IdentityNotFound,
+        /// The maximum number of registrars has been reached.
+        TooManyRegistrars,
+        /// No registrar exists at the given index, or the caller is not that registrar.
+        InvalidRegistrar,
+        /// The registrar's fee is higher than the maximum the requester accepted.
+        FeeChanged,
+        /// The registrar has already given a final judgement; it cannot be requested or withdrawn.
+        StickyJudgement,
+        /// No pending judgement request from this account to this registrar.
+        JudgementNotRequested,
+        /// `FeePaid` cannot be given as a judgement.
+        InvalidJudgement,
+        /// The free balance does not cover the registrar's fee.
+        InsufficientBalance,
+        /// The judged commitment is not the identity's current KYC commitment.
+        CommitmentMismatch,
+        /// No DID document exists with this identifier.
+        DidNotFound,
+        /// The caller does not control the DID.
+        NotDidController,
+        /// The DID has been deactivated.
+        DidDeactivated,
+        /// A DID document must have between one and `MaxDidEntries` keys, and at most `MaxDidEntries` services.
+        InvalidDidEntries,
+        /// A key, service field or claim type exceeds `MaxDidFieldLength`.
+        DidFieldTooLong,
+        /// The caller is not an issuer for the claim type.
+        NotAuthorizedIssuer,
+        /// A credential with the same content was already issued.
+        CredentialAlreadyExists,
+        /// No credential exists with this identifier.
+        CredentialNotFound,
+        /// Only the issuer can revoke a credential.
+        NotCredentialIssuer,
+        /// The credential has already been revoked.
+        AlreadyRevoked,
+        /// The account already has an identity or is already a sub-account.
+        AlreadyLinked,
+        /// The identity has reached `MaxSubAccounts`.
+        TooManySubAccounts,
+        /// The account is not a sub-account of the caller.
+        NotSubAccount,
+        /// No pending invitation from this primary to the caller.
+        SubNotInvited,
+        /// Friends must be 1..=`MaxFriends` distinct accounts and the threshold 1..=friends.
+        InvalidRecoveryConfig,
+        /// The identity has already configured recovery.
+        AlreadyRecoverable,
+        /// The account has not configured recovery.
+        NotRecoverable,
+        /// This rescuer has already started recovering the account.
+        RecoveryAlreadyStarted,
+        /// No recovery of the account by this rescuer is in progress.
+        RecoveryNotStarted,
+        /// The caller is not a recovery friend of the account.
+        NotFriend,
+        /// The friend has already vouched for this rescuer.
+        AlreadyVouched,
+        /// Not enough friends have vouched yet.
+        ThresholdNotReached,
+        /// The recovery delay has not passed yet.
+        RecoveryDelayPending,
     }
 
     impl<T: Config> Pallet<T> {
-        /// Registers a new identity with the provided KYC details.
+        /// Registers a new identity with a commitment to its KYC data.
         ///
         /// # Parameters:
         /// - `origin`: The account submitting the registration.
-        /// - `kyc_details`: Encrypted KYC data.
+        /// - `kyc_commitment`: Merkle root of the salted KYC fields, computed off-chain.
+        /// - `kyc_reference`: Optional reference to an encrypted off-chain KYC blob (may be empty).
         ///
         /// # Requirements:
-        /// - The length of `kyc_details` must not exceed `MaxKycLength`.
+        /// - The length of `kyc_reference` must not exceed `MaxKycLength`.
         /// - No identity must already exist for the account.
-        pub fn register_identity(origin: T::Origin, kyc_details: Vec<u8>) -> DispatchResult {
+        pub fn register_identity(origin: T::Origin, kyc_commitment: T::Hash, kyc_reference: Vec<u8>) -> DispatchResult {
             let account = ensure_signed(origin)?;
-            ensure!(kyc_details.len() as u32 <= T::MaxKycLength::get(), Error::<T>::KycTooLong);
+            ensure!(kyc_reference.len() as u32 <= T::MaxKycLength::get(), Error::<T>::KycTooLong);
             ensure!(!Identities::<T>::contains_key(&account), Error::<T>::IdentityAlreadyExists);
-            let identity = IdentityData {
-                kyc_details: kyc_details.clone(),
-                verified: T::DefaultVerification::get(),
-            };
+            ensure!(!SuperOf::<T>::contains_key(&account), Error::<T>::AlreadyLinked);
+            let identity = IdentityData { kyc_commitment, kyc_reference, judgements: Vec::new() };
             <Identities<T>>::insert(&account, identity);
             let timestamp = Self::current_timestamp();
-            <IdentityHistory<T>>::mutate(|history| {
-                history.push((timestamp, account.clone(), false, T::DefaultVerification::get(), kyc_details.clone()))
-            });
-            Self::deposit_event(Event::IdentityRegistered(account, kyc_details, T::DefaultVerification::get()));
-            // Invariant check: Identity should now exist with the default verification status.
+            <IdentityHistory<T>>::mutate(|history| history.push((timestamp, account.clone(), false, false, kyc_commitment)));
+            Self::deposit_event(Event::IdentityRegistered(account.clone(), kyc_commitment, false));
+            // Invariant check: Identity should now exist, unverified.
             assert_invariant!(Identities::<T>::contains_key(&account), "Identity registration failed");
             Ok(())
         }
 
-        /// Updates an existing identity with new KYC details and verification status.
+        /// Updates an existing identity with a new KYC commitment and blob reference.
+        ///
+        /// If the commitment changes, every judgement is cleared (pending fees are returned) and the identity
+        /// becomes unverified until a registrar judges the new commitment.
         ///
         /// # Parameters:
         /// - `origin`: The account submitting the update.
-        /// - `new_kyc_details`: Updated encrypted KYC data.
-        /// - `new_verified`: Updated verification status.
+        /// - `new_commitment`: Merkle root of the updated salted KYC fields.
+        /// - `new_reference`: Reference to the updated encrypted off-chain KYC blob (may be empty).
         ///
         /// # Requirements:
-        /// - The length of `new_kyc_details` must not exceed `MaxKycLength`.
+        /// - The length of `new_reference` must not exceed `MaxKycLength`.
         /// - An identity must already exist for the account.
-        pub fn update_identity(origin: T::Origin, new_kyc_details: Vec<u8>, new_verified: bool) -> DispatchResult {
+        pub fn update_identity(origin: T::Origin, new_commitment: T::Hash, new_reference: Vec<u8>) -> DispatchResult {
             let account = ensure_signed(origin)?;
-            ensure!(new_kyc_details.len() as u32 <= T::MaxKycLength::get(), Error::<T>::KycTooLong);
+            ensure!(new_reference.len() as u32 <= T::MaxKycLength::get(), Error::<T>::KycTooLong);
             Identities::<T>::try_mutate(&account, |maybe_identity| -> DispatchResult {
                 let identity = maybe_identity.as_mut().ok_or(Error::<T>::IdentityNotFound)?;
-                let prev_verified = identity.verified;
-                identity.kyc_details = new_kyc_details.clone();
-                identity.verified = new_verified;
+                let prev_verified = Self::holds_valid_judgement(identity);
+                let commitment_changed = identity.kyc_commitment != new_commitment;
+                if commitment_changed {
+                    for (_, judgement, _) in identity.judgements.drain(..) {
+                        if let Judgement::FeePaid(fee) = judgement {
+                            T::Currency::unreserve(&account, fee);
+                        }
+                    }
+                }
+                identity.kyc_commitment = new_commitment;
+                identity.kyc_reference = new_reference;
+                let new_verified = Self::holds_valid_judgement(identity);
                 let timestamp = Self::current_timestamp();
                 <IdentityHistory<T>>::mutate(|history| {
-                    history.push((timestamp, account.clone(), prev_verified, new_verified, new_kyc_details.clone()))
+                    history.push((timestamp, account.clone(), prev_verified, new_verified, new_commitment))
                 });
-                Self::deposit_event(Event::IdentityUpdated(account.clone(), new_kyc_details, prev_verified, new_verified));
-                // Invariant check: Ensure that the identity update reflects the new verification status.
-                assert_invariant!(identity.verified == new_verified, "Identity update did not apply new verification status");
+                Self::deposit_event(Event::IdentityUpdated(account.clone(), new_commitment, prev_verified, new_verified));
+                // Invariant check: A changed commitment must not keep any judgement.
+                assert_invariant!(!commitment_changed || identity.judgements.is_empty(), "Stale judgements kept after commitment change");
                 Ok(())
             })
         }
 
+        /// Appoints a new registrar.
+        ///
+        /// # Requirements:
+        /// - `origin` must satisfy `GovernanceOrigin`.
+        /// - Fewer than `MaxRegistrars` registrars may exist.
+        pub fn add_registrar(origin: T::Origin, account: T::AccountId, fee: BalanceOf<T>) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            let index = Registrars::<T>::try_mutate(|registrars| -> Result<RegistrarIndex, DispatchError> {
+                ensure!((registrars.len() as u32) < T::MaxRegistrars::get(), Error::<T>::TooManyRegistrars);
+                registrars.push(RegistrarInfo { account: account.clone(), fee });
+                Ok(registrars.len() as RegistrarIndex - 1)
+            })?;
+            Self::deposit_event(Event::RegistrarAdded(index, account));
+            Ok(())
+        }
+
+        /// Sets the fee the calling registrar charges for future judgements.
+        pub fn set_registrar_fee(origin: T::Origin, index: RegistrarIndex, fee: BalanceOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Registrars::<T>::try_mutate(|registrars| -> DispatchResult {
+                let registrar = registrars
+                    .get_mut(index as usize)
+                    .filter(|registrar| registrar.account == who)
+                    .ok_or(Error::<T>::InvalidRegistrar)?;
+                registrar.fee = fee;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::RegistrarFeeSet(index, fee));
+            Ok(())
+        }
+
+        /// Requests a judgement from a registrar, reserving its fee.
+        ///
+        /// # Parameters:
+        /// - `registrar`: Index of the registrar.
+        /// - `max_fee`: Highest fee the caller accepts, protecting against fee changes.
+        ///
+        /// # Requirements:
+        /// - The caller must have an identity, with no pending request to or definitive judgement from the registrar.
+        pub fn request_judgement(origin: T::Origin, registrar: RegistrarIndex, max_fee: BalanceOf<T>) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            let info = Registrars::<T>::get().get(registrar as usize).cloned().ok_or(Error::<T>::InvalidRegistrar)?;
+            ensure!(info.fee <= max_fee, Error::<T>::FeeChanged);
+            Identities::<T>::try_mutate(&account, |maybe_identity| -> DispatchResult {
+                let identity = maybe_identity.as_mut().ok_or(Error::<T>::IdentityNotFound)?;
+                let position = identity.judgements.binary_search_by_key(&registrar, |(index, _, _)| *index);
+                let now = frame_system::Pallet::<T>::block_number();
+                if let Ok(position) = position {
+                    let (_, judgement, given_at) = &identity.judgements[position];
+                    ensure!(
+                        !judgement.is_sticky() || (judgement.is_verifying() && Self::is_expired(*given_at)),
+                        Error::<T>::StickyJudgement
+                    );
+                }
+                T::Currency::reserve(&account, info.fee).map_err(|_| Error::<T>::InsufficientBalance)?;
+                let entry = (registrar, Judgement::FeePaid(info.fee), now);
+                match position {
+                    Ok(position) => identity.judgements[position] = entry,
+                    Err(position) => identity.judgements.insert(position, entry),
+                }
+                Ok(())
+            })?;
+            Self::deposit_event(Event::JudgementRequested(account, registrar));
+            Ok(())
+        }
+
+        /// Withdraws a pending judgement request and returns the reserved fee.
+        pub fn cancel_request(origin: T::Origin, registrar: RegistrarIndex) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            Identities::<T>::try_mutate(&account, |maybe_identity| -> DispatchResult {
+                let identity = maybe_identity.as_mut().ok_or(Error::<T>::IdentityNotFound)?;
+                let position = identity
+                    .judgements
+                    .binary_search_by_key(&registrar, |(index, _, _)| *index)
+                    .map_err(|_| Error::<T>::JudgementNotRequested)?;
+                let fee = match identity.judgements[position].1 {
+                    Judgement::FeePaid(fee) => fee,
+                    _ => return Err(Error::<T>::StickyJudgement.into()),
+                };
+                T::Currency::unreserve(&account, fee);
+                identity.judgements.remove(position);
+                Ok(())
+            })?;
+            Self::deposit_event(Event::JudgementUnrequested(account, registrar));
+            Ok(())
+        }
+
+        /// Gives a registrar's judgement on an identity that requested one, transferring the reserved fee to the
+        /// registrar. The registrar attests to `commitment`, the KYC commitment it checked the disclosed data against.
+        ///
+        /// # Requirements:
+        /// - The caller must be the registrar at `registrar`, and `target` must have a pending request to it.
+        /// - `commitment` must be the identity's current KYC commitment.
+        /// - `judgement` must not be `FeePaid`.
+        pub fn provide_judgement(
+            origin: T::Origin,
+            registrar: RegistrarIndex,
+            target: T::AccountId,
+            commitment: T::Hash,
+            judgement: Judgement<BalanceOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!judgement.is_pending(), Error::<T>::InvalidJudgement);
+            let info = Registrars::<T>::get()
+                .get(registrar as usize)
+                .cloned()
+                .filter(|info| info.account == who)
+                .ok_or(Error::<T>::InvalidRegistrar)?;
+            Identities::<T>::try_mutate(&target, |maybe_identity| -> DispatchResult {
+                let identity = maybe_identity.as_mut().ok_or(Error::<T>::IdentityNotFound)?;
+                ensure!(identity.kyc_commitment == commitment, Error::<T>::CommitmentMismatch);
+                let position = identity
+                    .judgements
+                    .binary_search_by_key(&registrar, |(index, _, _)| *index)
+                    .map_err(|_| Error::<T>::JudgementNotRequested)?;
+                let fee = match identity.judgements[position].1 {
+                    Judgement::FeePaid(fee) => fee,
+                    _ => return Err(Error::<T>::JudgementNotRequested.into()),
+                };
+                T::Currency::repatriate_reserved(&target, &info.account, fee, BalanceStatus::Free)?;
+                let prev_verified = Self::holds_valid_judgement(identity);
+                identity.judgements[position] = (registrar, judgement, frame_system::Pallet::<T>::block_number());
+                let new_verified = Self::holds_valid_judgement(identity);
+                let timestamp = Self::current_timestamp();
+                <IdentityHistory<T>>::mutate(|history| {
+                    history.push((timestamp, target.clone(), prev_verified, new_verified, commitment))
+                });
+                Ok(())
+            })?;
+            Self::deposit_event(Event::JudgementGiven(target, registrar, judgement));
+            Ok(())
+        }
+
+        /// Creates a DID controlled by the caller.
+        ///
+        /// # Parameters:
+        /// - `keys`: Verification keys; the first one is the authentication key.
+        /// - `services`: Service endpoints.
+        ///
+        /// # Requirements:
+        /// - Between one and `MaxDidEntries` keys, at most `MaxDidEntries` services, fields within `MaxDidFieldLength`.
+        pub fn create_did(origin: T::Origin, keys: Vec<DidKey>, services: Vec<ServiceEndpoint>) -> DispatchResult {
+            let controller = ensure_signed(origin)?;
+            Self::ensure_valid_keys(&keys)?;
+            Self::ensure_valid_services(&services)?;
+            let nonce = DidNonce::<T>::mutate(|nonce| {
+                *nonce = nonce.saturating_add(1);
+                *nonce
+            });
+            let did = T::Hashing::hash_of(&(b"did:nodara", &controller, nonce));
+            let document = DidDocument {
+                controller: controller.clone(),
+                keys,
+                services,
+                updated_at: frame_system::Pallet::<T>::block_number(),
+                deactivated: false,
+            };
+            Dids::<T>::insert(did, document);
+            Self::deposit_event(Event::DidCreated(did, controller));
+            Ok(())
+        }
+
+        /// Replaces the verification keys of a DID (key rotation).
+        pub fn rotate_did_keys(origin: T::Origin, did: T::Hash, keys: Vec<DidKey>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_valid_keys(&keys)?;
+            Self::mutate_did(&who, did, |document| document.keys = keys)
+        }
+
+        /// Replaces the service endpoints of a DID.
+        pub fn set_did_services(origin: T::Origin, did: T::Hash, services: Vec<ServiceEndpoint>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_valid_services(&services)?;
+            Self::mutate_did(&who, did, |document| document.services = services)
+        }
+
+        /// Transfers control of a DID to another account.
+        pub fn set_did_controller(origin: T::Origin, did: T::Hash, controller: T::AccountId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::mutate_did(&who, did, |document| document.controller = controller)
+        }
+
+        /// Permanently deactivates a DID.
+        pub fn deactivate_did(origin: T::Origin, did: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::mutate_did(&who, did, |document| document.deactivated = true)?;
+            Self::deposit_event(Event::DidDeactivated(did));
+            Ok(())
+        }
+
+        /// Registers `issuer` for `claim_types`, replacing any previous registration.
+        ///
+        /// # Requirements:
+        /// - `origin` must satisfy `GovernanceOrigin`.
+        pub fn add_issuer(origin: T::Origin, issuer: T::AccountId, claim_types: Vec<Vec<u8>>) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(
+                claim_types.iter().all(|claim_type| claim_type.len() as u32 <= T::MaxDidFieldLength::get()),
+                Error::<T>::DidFieldTooLong
+            );
+            Issuers::<T>::insert(&issuer, claim_types.clone());
+            Self::deposit_event(Event::IssuerAdded(issuer, claim_types));
+            Ok(())
+        }
+
+        /// Removes an issuer. Credentials it already issued are reported as `IssuerRemoved` from then on.
+        pub fn remove_issuer(origin: T::Origin, issuer: T::AccountId) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            Issuers::<T>::remove(&issuer);
+            Self::deposit_event(Event::IssuerRemoved(issuer));
+            Ok(())
+        }
+
+        /// Issues a credential of `claim_type` to the DID `subject`.
+        ///
+        /// # Parameters:
+        /// - `claims_hash`: Hash of the off-chain claims document.
+        /// - `expires_at`: Optional block after which the credential is expired.
+        ///
+        /// # Requirements:
+        /// - The caller must be an issuer registered for `claim_type`.
+        /// - `subject` must be an active DID.
+        pub fn issue_credential(
+            origin: T::Origin,
+            subject: T::Hash,
+            claim_type: Vec<u8>,
+            claims_hash: T::Hash,
+            expires_at: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            let claim_types = Issuers::<T>::get(&issuer).ok_or(Error::<T>::NotAuthorizedIssuer)?;
+            ensure!(claim_types.contains(&claim_type), Error::<T>::NotAuthorizedIssuer);
+            let document = Dids::<T>::get(subject).ok_or(Error::<T>::DidNotFound)?;
+            ensure!(!document.deactivated, Error::<T>::DidDeactivated);
+            let credential_id = T::Hashing::hash_of(&(&issuer, subject, &claim_type, claims_hash));
+            ensure!(!Credentials::<T>::contains_key(credential_id), Error::<T>::CredentialAlreadyExists);
+            let credential = Credential {
+                issuer: issuer.clone(),
+                subject,
+                claim_type: claim_type.clone(),
+                claims_hash,
+                issued_at: frame_system::Pallet::<T>::block_number(),
+                expires_at,
+            };
+            Credentials::<T>::insert(credential_id, credential);
+            Self::deposit_event(Event::CredentialIssued(credential_id, issuer, subject, claim_type));
+            Ok(())
+        }
+
+        /// Adds a credential to its issuer's revocation registry. Only the issuer can revoke.
+        pub fn revoke_credential(origin: T::Origin, credential_id: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let credential = Credentials::<T>::get(credential_id).ok_or(Error::<T>::CredentialNotFound)?;
+            ensure!(credential.issuer == who, Error::<T>::NotCredentialIssuer);
+            ensure!(!Revocations::<T>::contains_key(&who, credential_id), Error::<T>::AlreadyRevoked);
+            Revocations::<T>::insert(&who, credential_id, frame_system::Pallet::<T>::block_number());
+            Self::deposit_event(Event::CredentialRevoked(credential_id, who));
+            Ok(())
+        }
+
+        /// Returns the status of a credential at the current block. Backs the `NodaraIdApi` runtime API.
+        ///
+        /// A credential is only valid while its issuer is still registered for its claim type and its subject DID
+        /// is active.
+        pub fn credential_status(credential_id: T::Hash) -> CredentialStatus {
+            let Some(credential) = Credentials::<T>::get(credential_id) else {
+                return CredentialStatus::Unknown;
+            };
+            if Revocations::<T>::contains_key(&credential.issuer, credential_id) {
+                return CredentialStatus::Revoked;
+            }
+            let issuer_registered = Issuers::<T>::get(&credential.issuer)
+                .map_or(false, |claim_types| claim_types.contains(&credential.claim_type));
+            if !issuer_registered {
+                return CredentialStatus::IssuerRemoved;
+            }
+            if Dids::<T>::get(credential.subject).map_or(true, |document| document.deactivated) {
+                return CredentialStatus::SubjectDeactivated;
+            }
+            match credential.expires_at {
+                Some(expiry) if frame_system::Pallet::<T>::block_number() > expiry => CredentialStatus::Expired,
+                _ => CredentialStatus::Valid,
+            }
+        }
+
+        /// Invites `sub` to link under the caller's identity with a `label` (e.g., "meter-42", "hot wallet"),
+        /// reserving `SubAccountDeposit`. The link only takes effect once `sub` calls `accept_sub`.
+        ///
+        /// # Requirements:
+        /// - The caller must have an identity with fewer than `MaxSubAccounts` sub-accounts and pending invitations.
+        /// - `sub` must have no identity, must not be linked already and must not hold an invitation from the caller.
+        pub fn add_sub(origin: T::Origin, sub: T::AccountId, label: Vec<u8>) -> DispatchResult {
+            let primary = ensure_signed(origin)?;
+            ensure!(Identities::<T>::contains_key(&primary), Error::<T>::IdentityNotFound);
+            ensure!(label.len() as u32 <= T::MaxDidFieldLength::get(), Error::<T>::DidFieldTooLong);
+            ensure!(
+                sub != primary
+                    && !Identities::<T>::contains_key(&sub)
+                    && !SuperOf::<T>::contains_key(&sub)
+                    && !PendingSubs::<T>::contains_key(&primary, &sub),
+                Error::<T>::AlreadyLinked
+            );
+            let slots = SubsOf::<T>::decode_len(&primary).unwrap_or(0) + PendingSubs::<T>::iter_prefix(&primary).count();
+            ensure!((slots as u32) < T::MaxSubAccounts::get(), Error::<T>::TooManySubAccounts);
+            let deposit = T::SubAccountDeposit::get();
+            T::Currency::reserve(&primary, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+            PendingSubs::<T>::insert(&primary, &sub, (label.clone(), deposit));
+            Self::deposit_event(Event::SubInvited(primary, sub, label));
+            Ok(())
+        }
+
+        /// Accepts an invitation from `primary`, linking the caller as its sub-account.
+        ///
+        /// # Requirements:
+        /// - `primary` must have invited the caller and must still have an identity.
+        /// - The caller must have no identity and must not be linked already.
+        pub fn accept_sub(origin: T::Origin, primary: T::AccountId) -> DispatchResult {
+            let sub = ensure_signed(origin)?;
+            let (label, deposit) = PendingSubs::<T>::get(&primary, &sub).ok_or(Error::<T>::SubNotInvited)?;
+            ensure!(Identities::<T>::contains_key(&primary), Error::<T>::IdentityNotFound);
+            ensure!(
+                !Identities::<T>::contains_key(&sub) && !SuperOf::<T>::contains_key(&sub),
+                Error::<T>::AlreadyLinked
+            );
+            PendingSubs::<T>::remove(&primary, &sub);
+            SubsOf::<T>::mutate(&primary, |subs| subs.push(sub.clone()));
+            SuperOf::<T>::insert(&sub, (primary.clone(), label.clone()));
+            SubDeposits::<T>::insert(&sub, (primary.clone(), deposit));
+            Self::deposit_event(Event::SubAdded(primary, sub, label));
+            Ok(())
+        }
+
+        /// Unlinks one of the caller's sub-accounts, or withdraws a pending invitation to `sub`. The deposit is
+        /// returned either way.
+        pub fn remove_sub(origin: T::Origin, sub: T::AccountId) -> DispatchResult {
+            let primary = ensure_signed(origin)?;
+            if let Some((_, deposit)) = PendingSubs::<T>::take(&primary, &sub) {
+                T::Currency::unreserve(&primary, deposit);
+                Self::deposit_event(Event::SubInvitationCancelled(primary, sub));
+                return Ok(());
+            }
+            let (owner, _) = SuperOf::<T>::get(&sub).ok_or(Error::<T>::NotSubAccount)?;
+            ensure!(owner == primary, Error::<T>::NotSubAccount);
+            Self::unlink_sub(&primary, &sub);
+            Ok(())
+        }
+
+        /// Unlinks the caller from its primary identity.
+        pub fn quit_sub(origin: T::Origin) -> DispatchResult {
+            let sub = ensure_signed(origin)?;
+            let (primary, _) = SuperOf::<T>::get(&sub).ok_or(Error::<T>::NotSubAccount)?;
+            Self::unlink_sub(&primary, &sub);
+            Ok(())
+        }
+
+        /// Makes the caller's identity recoverable by `threshold` of `friends` after `delay` blocks.
+        pub fn create_recovery(
+            origin: T::Origin,
+            mut friends: Vec<T::AccountId>,
+            threshold: u16,
+            delay: T::BlockNumber,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Identities::<T>::contains_key(&who), Error::<T>::IdentityNotFound);
+            ensure!(!Recoverable::<T>::contains_key(&who), Error::<T>::AlreadyRecoverable);
+            friends.sort();
+            friends.dedup();
+            ensure!(
+                !friends.is_empty()
+                    && friends.len() as u32 <= T::MaxFriends::get()
+                    && threshold >= 1
+                    && threshold as usize <= friends.len(),
+                Error::<T>::InvalidRecoveryConfig
+            );
+            Recoverable::<T>::insert(&who, RecoveryConfig { friends, threshold, delay });
+            Self::deposit_event(Event::RecoveryCreated(who));
+            Ok(())
+        }
+
+        /// Starts recovering `lost` to the caller, reserving `RecoveryDeposit`.
+        pub fn initiate_recovery(origin: T::Origin, lost: T::AccountId) -> DispatchResult {
+            let rescuer = ensure_signed(origin)?;
+            ensure!(Recoverable::<T>::contains_key(&lost), Error::<T>::NotRecoverable);
+            ensure!(!ActiveRecoveries::<T>::contains_key(&lost, &rescuer), Error::<T>::RecoveryAlreadyStarted);
+            let deposit = T::RecoveryDeposit::get();
+            T::Currency::reserve(&rescuer, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+            let recovery = ActiveRecovery {
+                created: frame_system::Pallet::<T>::block_number(),
+                deposit,
+                friends: Vec::new(),
+            };
+            ActiveRecoveries::<T>::insert(&lost, &rescuer, recovery);
+            Self::deposit_event(Event::RecoveryInitiated(lost, rescuer));
+            Ok(())
+        }
+
+        /// Vouches, as a recovery friend of `lost`, for `rescuer`.
+        pub fn vouch_recovery(origin: T::Origin, lost: T::AccountId, rescuer: T::AccountId) -> DispatchResult {
+            let friend = ensure_signed(origin)?;
+            let config = Recoverable::<T>::get(&lost).ok_or(Error::<T>::NotRecoverable)?;
+            ensure!(config.friends.binary_search(&friend).is_ok(), Error::<T>::NotFriend);
+            ActiveRecoveries::<T>::try_mutate(&lost, &rescuer, |maybe_recovery| -> DispatchResult {
+                let recovery = maybe_recovery.as_mut().ok_or(Error::<T>::RecoveryNotStarted)?;
+                match recovery.friends.binary_search(&friend) {
+                    Ok(_) => Err(Error::<T>::AlreadyVouched.into()),
+                    Err(position) => {
+                        recovery.friends.insert(position, friend.clone());
+                        Ok(())
+                    }
+                }
+            })?;
+            Self::deposit_event(Event::RecoveryVouched(lost, rescuer, friend));
+            Ok(())
+        }
+
+        /// Completes a recovery: once enough friends have vouched and the delay has passed, the identity of `lost`
+        /// (with its judgements, sub-accounts, recovery configuration and reputation) moves to the caller. Every
+        /// recovery attempt on `lost` ends, and the deposits of the caller and of competing rescuers are returned.
+        /// Pending judgement requests and sub-account invitations of `lost` are cancelled; deposits of linked
+        /// sub-accounts stay with the account that reserved them.
+        pub fn claim_recovery(origin: T::Origin, lost: T::AccountId) -> DispatchResult {
+            let rescuer = ensure_signed(origin)?;
+            let config = Recoverable::<T>::get(&lost).ok_or(Error::<T>::NotRecoverable)?;
+            let recovery = ActiveRecoveries::<T>::get(&lost, &rescuer).ok_or(Error::<T>::RecoveryNotStarted)?;
+            ensure!(recovery.friends.len() >= config.threshold as usize, Error::<T>::ThresholdNotReached);
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(now >= recovery.created.saturating_add(config.delay), Error::<T>::RecoveryDelayPending);
+            ensure!(
+                !Identities::<T>::contains_key(&rescuer) && !SuperOf::<T>::contains_key(&rescuer),
+                Error::<T>::AlreadyLinked
+            );
+            let mut identity = Identities::<T>::take(&lost).ok_or(Error::<T>::IdentityNotFound)?;
+            identity.judgements.retain(|(_, judgement, _)| match judgement {
+                Judgement::FeePaid(fee) => {
+                    T::Currency::unreserve(&lost, *fee);
+                    false
+                }
+                _ => true,
+            });
+            let verified = Self::holds_valid_judgement(&identity);
+            let commitment = identity.kyc_commitment;
+            Identities::<T>::insert(&rescuer, identity);
+            let subs = SubsOf::<T>::take(&lost);
+            for sub in subs.iter() {
+                SuperOf::<T>::mutate(sub, |link| {
+                    if let Some((primary, _)) = link {
+                        *primary = rescuer.clone();
+                    }
+                });
+            }
+            SubsOf::<T>::insert(&rescuer, subs);
+            Self::cancel_invitations(&lost);
+            Recoverable::<T>::remove(&lost);
+            Recoverable::<T>::insert(&rescuer, config);
+            for (attempt_rescuer, attempt) in ActiveRecoveries::<T>::drain_prefix(&lost) {
+                T::Currency::unreserve(&attempt_rescuer, attempt.deposit);
+            }
+            let timestamp = Self::current_timestamp();
+            <IdentityHistory<T>>::mutate(|history| history.push((timestamp, rescuer.clone(), verified, verified, commitment)));
+            T::OnIdentityMigrated::on_identity_migrated(&lost, &rescuer);
+            Self::deposit_event(Event::IdentityRecovered(lost, rescuer));
+            Ok(())
+        }
+
+        /// Cancels a recovery attempt on the caller's identity; the rescuer's deposit goes to the caller.
+        pub fn cancel_recovery(origin: T::Origin, rescuer: T::AccountId) -> DispatchResult {
+            let lost = ensure_signed(origin)?;
+            let recovery = ActiveRecoveries::<T>::take(&lost, &rescuer).ok_or(Error::<T>::RecoveryNotStarted)?;
+            T::Currency::repatriate_reserved(&rescuer, &lost, recovery.deposit, BalanceStatus::Free)?;
+            Self::deposit_event(Event::RecoveryCancelled(lost, rescuer));
+            Ok(())
+        }
+
+        /// Clears the caller's identity.
+        ///
+        /// Pending judgement fees and sub-account deposits are returned, sub-accounts are unlinked, pending
+        /// invitations are withdrawn, the recovery configuration is removed and
+        /// the deposits of rescuers with recovery attempts in progress are returned. To honor erasure requests, the
+        /// account's entries are also removed from `IdentityHistory`.
+        pub fn clear_identity(origin: T::Origin) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            let identity = Identities::<T>::take(&account).ok_or(Error::<T>::IdentityNotFound)?;
+            for (_, judgement, _) in identity.judgements.iter() {
+                if let Judgement::FeePaid(fee) = judgement {
+                    T::Currency::unreserve(&account, *fee);
+                }
+            }
+            for sub in SubsOf::<T>::take(&account) {
+                SuperOf::<T>::remove(&sub);
+                Self::release_sub_deposit(&sub);
+            }
+            Self::cancel_invitations(&account);
+            Recoverable::<T>::remove(&account);
+            for (rescuer, recovery) in ActiveRecoveries::<T>::drain_prefix(&account) {
+                T::Currency::unreserve(&rescuer, recovery.deposit);
+            }
+            <IdentityHistory<T>>::mutate(|history| history.retain(|entry| entry.1 != account));
+            Self::deposit_event(Event::IdentityCleared(account.clone()));
+            // Invariant check: No trace of the identity may remain.
+            assert_invariant!(!Identities::<T>::contains_key(&account), "Identity not cleared");
+            Ok(())
+        }
+
+        /// Returns whether `account`'s own identity holds an unexpired reasonable or known-good judgement.
+        pub fn is_identity_verified(account: &T::AccountId) -> bool {
+            Identities::<T>::get(account).map_or(false, |identity| Self::holds_valid_judgement(&identity))
+        }
+
+        fn holds_valid_judgement(identity: &IdentityData<T::Hash, BalanceOf<T>, T::BlockNumber>) -> bool {
+            identity
+                .judgements
+                .iter()
+                .any(|(_, judgement, given_at)| judgement.is_verifying() && !Self::is_expired(*given_at))
+        }
+
+        /// Whether a judgement given at `given_at` has outlived `JudgementValidity`.
+        fn is_expired(given_at: T::BlockNumber) -> bool {
+            let validity = T::JudgementValidity::get();
+            !validity.is_zero() && frame_system::Pallet::<T>::block_number() >= given_at.saturating_add(validity)
+        }
+
+        fn unlink_sub(primary: &T::AccountId, sub: &T::AccountId) {
+            SuperOf::<T>::remove(sub);
+            SubsOf::<T>::mutate(primary, |subs| subs.retain(|linked| linked != sub));
+            Self::release_sub_deposit(sub);
+            Self::deposit_event(Event::SubRemoved(primary.clone(), sub.clone()));
+        }
+
+        /// Returns the deposit held for a linked sub-account to whoever reserved it.
+        fn release_sub_deposit(sub: &T::AccountId) {
+            if let Some((depositor, deposit)) = SubDeposits::<T>::take(sub) {
+                T::Currency::unreserve(&depositor, deposit);
+            }
+        }
+
+        /// Withdraws every pending sub-account invitation sent by `primary`, returning the deposits.
+        fn cancel_invitations(primary: &T::AccountId) {
+            for (sub, (_, deposit)) in PendingSubs::<T>::drain_prefix(primary) {
+                T::Currency::unreserve(primary, deposit);
+                Self::deposit_event(Event::SubInvitationCancelled(primary.clone(), sub));
+            }
+        }
+
+        /// Applies `change` to an active DID controlled by `who`.
+        fn mutate_did(
+            who: &T::AccountId,
+            did: T::Hash,
+            change: impl FnOnce(&mut DidDocument<T::AccountId, T::BlockNumber>),
+        ) -> DispatchResult {
+            Dids::<T>::try_mutate(did, |maybe_document| -> DispatchResult {
+                let document = maybe_document.as_mut().ok_or(Error::<T>::DidNotFound)?;
+                ensure!(&document.controller == who, Error::<T>::NotDidController);
+                ensure!(!document.deactivated, Error::<T>::DidDeactivated);
+                change(document);
+                document.updated_at = frame_system::Pallet::<T>::block_number();
+                Ok(())
+            })?;
+            Self::deposit_event(Event::DidUpdated(did));
+            Ok(())
+        }
+
+        fn ensure_valid_keys(keys: &[DidKey]) -> DispatchResult {
+            ensure!(!keys.is_empty() && keys.len() as u32 <= T::MaxDidEntries::get(), Error::<T>::InvalidDidEntries);
+            ensure!(
+                keys.iter().all(|key| key.public_key.len() as u32 <= T::MaxDidFieldLength::get()),
+                Error::<T>::DidFieldTooLong
+            );
+            Ok(())
+        }
+
+        fn ensure_valid_services(services: &[ServiceEndpoint]) -> DispatchResult {
+            ensure!(services.len() as u32 <= T::MaxDidEntries::get(), Error::<T>::InvalidDidEntries);
+            let max = T::MaxDidFieldLength::get() as usize;
+            ensure!(
+                services.iter().all(|service| {
+                    service.id.len() <= max && service.service_type.len() <= max && service.endpoint.len() <= max
+                }),
+                Error::<T>::DidFieldTooLong
+            );
+            Ok(())
+        }
+
+        /// Returns the commitment leaf of a single salted KYC field.
+        pub fn kyc_leaf(field: &[u8], value: &[u8], salt: &[u8]) -> T::Hash {
+            T::Hashing::hash_of(&(field, value, salt))
+        }
+
+        /// Checks a selectively disclosed KYC field against `account`'s on-chain commitment.
+        ///
+        /// The user reveals `field`, `value` and `salt` off-chain together with the Merkle `proof` (sorted-pair
+        /// hashing) from the field's leaf to the commitment; no other field is revealed.
+        pub fn verify_disclosure(
+            account: &T::AccountId,
+            field: &[u8],
+            value: &[u8],
+            salt: &[u8],
+            proof: &[T::Hash],
+        ) -> bool {
+            let Some(identity) = Identities::<T>::get(account) else {
+                return false;
+            };
+            let computed = proof.iter().fold(Self::kyc_leaf(field, value, salt), |node, sibling| {
+                if node <= *sibling {
+                    T::Hashing::hash_of(&(node, *sibling))
+                } else {
+                    T::Hashing::hash_of(&(*sibling, node))
+                }
+            });
+            computed == identity.kyc_commitment
+        }
+
         /// Returns the current Unix timestamp.
         /// In production, replace this with a precise time provider.
         fn current_timestamp() -> u64 {
             1_640_000_000 // Placeholder timestamp.
         }
     }
+
+    impl<T: Config> IdentityProvider<T::AccountId> for Pallet<T> {
+        /// Sub-accounts share the verification status of their primary identity.
+        fn is_verified(who: &T::AccountId) -> bool {
+            Self::identity_of(who).map_or(false, |primary| Self::is_identity_verified(&primary))
+        }
+
+        /// Returns `who` for a primary identity, or its primary for a sub-account.
+        fn identity_of(who: &T::AccountId) -> Option<T::AccountId> {
+            if Identities::<T>::contains_key(who) {
+                return Some(who.clone());
+            }
+            SuperOf::<T>::get(who).map(|(primary, _)| primary)
+        }
+    }
 }
 
 // --- Benchmarking Section ---
@@ -188,28 +1282,61 @@ mod benchmarking {
     benchmarks! {
         register_identity {
             let account: T::AccountId = account("user", 0, 0);
-            let kyc: Vec<u8> = b"Benchmark KYC Data".to_vec();
+            let commitment = T::Hashing::hash(b"Benchmark KYC Data");
+            let reference: Vec<u8> = sp_std::vec![0u8; T::MaxKycLength::get() as usize];
         }: {
-            Pallet::<T>::register_identity(RawOrigin::Signed(account.clone()).into(), kyc.clone())?;
+            Pallet::<T>::register_identity(RawOrigin::Signed(account.clone()).into(), commitment, reference)?;
         }
         verify {
             let identity = Pallet::<T>::identities(&account).unwrap();
-            assert_eq!(identity.kyc_details, kyc);
-            assert_eq!(identity.verified, T::DefaultVerification::get());
+            assert_eq!(identity.kyc_commitment, commitment);
+            assert!(!Pallet::<T>::is_identity_verified(&account));
         }
 
         update_identity {
             let account: T::AccountId = account("user", 0, 0);
-            Pallet::<T>::register_identity(RawOrigin::Signed(account.clone()).into(), b"Initial KYC".to_vec())?;
-            let new_kyc: Vec<u8> = b"Updated KYC Data".to_vec();
-            let new_status: bool = true;
+            Pallet::<T>::register_identity(RawOrigin::Signed(account.clone()).into(), T::Hashing::hash(b"Initial KYC"), Vec::new())?;
+            let new_commitment = T::Hashing::hash(b"Updated KYC Data");
         }: {
-            Pallet::<T>::update_identity(RawOrigin::Signed(account.clone()).into(), new_kyc.clone(), new_status)?;
+            Pallet::<T>::update_identity(RawOrigin::Signed(account.clone()).into(), new_commitment, Vec::new())?;
         }
         verify {
             let identity = Pallet::<T>::identities(&account).unwrap();
-            assert_eq!(identity.kyc_details, new_kyc);
-            assert_eq!(identity.verified, new_status);
+            assert_eq!(identity.kyc_commitment, new_commitment);
+            assert!(!Pallet::<T>::is_identity_verified(&account));
+        }
+
+        provide_judgement {
+            let account: T::AccountId = account("user", 0, 0);
+            let registrar: T::AccountId = account("registrar", 0, 0);
+            let fee = T::Currency::minimum_balance();
+            T::Currency::make_free_balance_be(&account, fee + fee + fee);
+            Pallet::<T>::add_registrar(RawOrigin::Root.into(), registrar.clone(), fee)?;
+            let commitment = T::Hashing::hash(b"Benchmark KYC Data");
+            Pallet::<T>::register_identity(RawOrigin::Signed(account.clone()).into(), commitment, Vec::new())?;
+            Pallet::<T>::request_judgement(RawOrigin::Signed(account.clone()).into(), 0, fee)?;
+        }: {
+            Pallet::<T>::provide_judgement(RawOrigin::Signed(registrar.clone()).into(), 0, account.clone(), commitment, Judgement::KnownGood)?;
+        }
+        verify {
+            assert!(Pallet::<T>::is_identity_verified(&account));
+        }
+
+        issue_credential {
+            let holder: T::AccountId = account("user", 0, 0);
+            let issuer: T::AccountId = account("issuer", 0, 0);
+            let claim_type: Vec<u8> = b"KycAml".to_vec();
+            let key = DidKey { key_type: KeyType::Sr25519, public_key: sp_std::vec![0u8; 32] };
+            Pallet::<T>::create_did(RawOrigin::Signed(holder.clone()).into(), sp_std::vec![key], Vec::new())?;
+            let did = T::Hashing::hash_of(&(b"did:nodara", &holder, 1u64));
+            Pallet::<T>::add_issuer(RawOrigin::Root.into(), issuer.clone(), sp_std::vec![claim_type.clone()])?;
+            let claims_hash = T::Hashing::hash(b"Benchmark Claims");
+        }: {
+            Pallet::<T>::issue_credential(RawOrigin::Signed(issuer.clone()).into(), did, claim_type.clone(), claims_hash, None)?;
+        }
+        verify {
+            let credential_id = T::Hashing::hash_of(&(&issuer, did, &claim_type, claims_hash));
+            assert_eq!(Pallet::<T>::credential_status(credential_id), CredentialStatus::Valid);
         }
     }
 
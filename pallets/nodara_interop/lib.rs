@@ -5,27 +5,50 @@
 //!
 //! This module implements secure cross-chain interoperability for Nodara BIOSPHÈRE QUANTIC. It enables the sending,
 //! receiving, and verification of messages between the Nodara network and external blockchains. Designed to a mythical standard,
-//! the module incorporates advanced cryptographic techniques, simulated formal verification, and comprehensive audit logging.
+//! the module incorporates advanced cryptographic techniques, simulated formal verification, and an event for every interop operation.
 //! DAO governance integration allows for dynamic updates to interop parameters.
 //!
 //! ## Key Advanced Features:
-//! - **Cross-Chain Messaging:** Securely sends and receives messages with rigorous cryptographic verification.
-//! - **Data Relay & Aggregation:** Aggregates and filters data from multiple sources, ensuring reliable information flow.
+//! - **Ordered Channels:** Messages travel over per-counterpart-chain channels opened by governance. Each channel
+//!   assigns consecutive sequence numbers to outbound messages and accepts inbound messages strictly in order.
+//! - **Outbound Queue and Acknowledgements:** Sent messages stay queued until a relayer proves, against a tracked
+//!   header of the destination, that the destination's channel has received them, in sequence order; the sending
+//!   pallet is then notified through `OnMessageOutcome`.
+//! - **Timeouts:** Each message expires `BaseTimeout` blocks of its destination past the destination's latest tracked
+//!   header. A message times out only with proof that the destination had not received it at a tracked header at or
+//!   past that height; it is then dropped from the queue, the channel is closed since it can no longer deliver in
+//!   order for good, and the sending pallet is notified so it can refund or roll back the message's effects. Inbound messages
+//!   are only accepted before their own expiry height, checked against the local block number.
+//! - **Light-Client Verification:** Finalized headers of each counterpart chain are tracked by a light client
+//!   (GRANDPA for Substrate chains, sync committee for Ethereum; see `light_client`), and inbound messages are only
+//!   accepted with a storage inclusion proof against a tracked header.
 //! - **Simulated Formal Verification:** Internal invariant checks simulate the effect of formal proofs, ensuring mathematical correctness.
-//! - **Immutable Audit Logging:** Logs every interop operation with full metadata for complete traceability.
-//! - **DAO Governance Integration:** Supports on-chain proposals to update interop configurations.
+//! - **Event Audit Trail:** Every interop operation emits an event, so the trail lives in the event log rather
+//!   than in ever-growing state.
+//! - **DAO Governance Integration:** Channels and relayers are managed by `GovernanceOrigin`.
 //! - **Performance Optimizations:** Highly optimized for low latency with integrated benchmarking.
 //!
 //! ## Module Structure:
 //! - **Storage:**
-//!   - `OutgoingMessages`: Stores messages sent to external chains.
-//!   - `IncomingMessages`: Stores messages received from external chains.
-//!   - `InteropHistory`: Logs every interop event (timestamp, message ID, operation type, details).
+//!   - `Channels`: Per-counterpart-chain channel state (status and sequence numbers).
+//!   - `TimedOutChannels`: Channels closed by a timeout, which can never be reopened.
+//!   - `OutboundQueue`: Sent messages awaiting acknowledgement, keyed by destination and nonce.
+//!   - `IncomingMessages`: Messages received from external chains, keyed by source and nonce.
+//!   - `LightClients`: Light client state of each counterpart chain.
+//!   - `FinalizedHeaders` / `LatestFinalized`: Tracked finalized headers of each counterpart chain.
+//!   - `Relayers`: Accounts allowed to relay inbound messages and acknowledgements.
 //! - **Core Functions:**
-//!   - `send_message`: Sends a message to an external chain after verifying payload length and signature.
-//!   - `receive_message`: Receives and verifies a message, storing it in on-chain storage.
+//!   - `open_channel` / `close_channel`: Open a channel to a counterpart chain, or stop sending on it (governance).
+//!   - `add_relayer` / `remove_relayer`: Manage authorized relayers (governance).
+//!   - `send_message`: Queues a message on a channel under the next outbound nonce.
+//!   - `acknowledge`: Confirms delivery of the oldest pending message of a channel with a proof (relayers only).
+//!   - `timeout_message`: Times out the oldest pending message of a channel with a non-receipt proof (relayers only).
+//!   - `register_light_client`: Starts tracking a counterpart chain from a trusted checkpoint (governance).
+//!   - `submit_finality_update`: Verifies a finality proof and tracks the newly finalized header (relayers only).
+//!   - `receive_message`: Accepts the next inbound message of a channel with an inclusion proof (relayers only).
 //!   - `update_config`: Updates interop settings via DAO proposals.
-//!   - `verify_signature`: (Internal) Simulates cryptographic verification of message signatures.
+//!   - `verify_inclusion`: (Internal) Checks a message's inclusion proof with the source chain's light client.
+//!   - `proven_inbound_nonce`: (Internal) Proves how far a destination has received a channel's messages.
 //!   - `current_timestamp`: Returns the current Unix timestamp (placeholder).
 //!
 //! ## Version:
@@ -36,66 +59,216 @@
 //! - Enhanced offchain processing for large-scale data aggregation.
 //! - Extended support for emerging cross-chain interoperability standards.
 
-use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::Get};
+use frame_support::{
+    dispatch::DispatchResult,
+    pallet_prelude::*,
+    traits::{EnsureOrigin, Get},
+};
 use frame_system::pallet_prelude::*;
+use light_client::{
+    FinalizedHeader, GrandpaClient, GrandpaState, GrandpaUpdate, LightClient, LightClientError, SyncCommitteeClient,
+    SyncCommitteeState, SyncCommitteeUpdate,
+};
+use sp_core::H256;
+use sp_runtime::traits::UniqueSaturatedInto;
 use sp_std::vec::Vec;
 
+pub mod light_client;
+
+/// Identifier of a counterpart chain.
+pub type ChainId = u32;
+
+/// Interface through which other pallets send messages to counterpart chains.
+pub trait MessageSender<AccountId> {
+    /// Queues `payload` for `destination` on behalf of `sender` and returns its nonce on the channel.
+    fn send(sender: &AccountId, destination: ChainId, payload: Vec<u8>) -> Result<u64, DispatchError>;
+}
+
+/// Null sender: every message is refused.
+impl<AccountId> MessageSender<AccountId> for () {
+    fn send(_sender: &AccountId, _destination: ChainId, _payload: Vec<u8>) -> Result<u64, DispatchError> {
+        Err(DispatchError::Other("interop disabled"))
+    }
+}
+
+/// Notified of the outcome of sent messages, so the sending pallet can finalize or undo their effects.
+pub trait OnMessageOutcome<AccountId> {
+    /// The message `nonce` to `destination` was delivered, as acknowledged by a relayer.
+    fn on_acknowledged(destination: ChainId, nonce: u64, sender: &AccountId, payload: &[u8]);
+    /// The message `nonce` to `destination` was not acknowledged in time; its effects should be refunded or rolled
+    /// back.
+    fn on_timeout(destination: ChainId, nonce: u64, sender: &AccountId, payload: &[u8]);
+}
+
+/// Null handler: outcomes are ignored.
+impl<AccountId> OnMessageOutcome<AccountId> for () {
+    fn on_acknowledged(_destination: ChainId, _nonce: u64, _sender: &AccountId, _payload: &[u8]) {}
+    fn on_timeout(_destination: ChainId, _nonce: u64, _sender: &AccountId, _payload: &[u8]) {}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
 
-    /// Structure representing an interop message.
+    /// State of a channel to a counterpart chain.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Channel {
+        /// Whether new outbound messages are accepted; pending messages are still acknowledged or timed out.
+        pub open: bool,
+        /// Nonce assigned to the next outbound message.
+        pub next_outbound_nonce: u64,
+        /// Oldest outbound nonce neither acknowledged nor timed out.
+        pub next_pending_nonce: u64,
+        /// Nonce expected of the next inbound message.
+        pub next_inbound_nonce: u64,
+    }
+
+    /// A message queued for delivery to a counterpart chain.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct OutboundMessage<AccountId, BlockNumber> {
+        /// Account (or pallet account) that sent the message.
+        pub sender: AccountId,
+        /// Payload of the message.
+        pub payload: Vec<u8>,
+        /// Block in which the message was sent.
+        pub sent_at: BlockNumber,
+        /// Block of the destination (the slot, for the beacon chain) from which the message expires. The destination
+        /// must not execute it at or past this height, since its effects are then rolled back here.
+        pub timeout_at: u64,
+    }
+
+    /// Structure representing a message received from a counterpart chain.
     #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     pub struct InteropMessage {
-        /// Unique message identifier.
-        pub id: u64,
+        /// Sequence number of the message on its channel.
+        pub nonce: u64,
         /// Payload of the message.
         pub payload: Vec<u8>,
-        /// Timestamp when the message was sent.
+        /// Timestamp when the message was received.
         pub timestamp: u64,
-        /// Cryptographic signature.
-        pub signature: Vec<u8>,
+        /// Number of the tracked header the message's inclusion was proven against.
+        pub proven_at: u64,
+    }
+
+    /// Light client of a counterpart chain.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum ClientState {
+        /// Substrate chain finalized by GRANDPA.
+        Grandpa(GrandpaState),
+        /// Ethereum beacon chain followed through its sync committees.
+        SyncCommittee(SyncCommitteeState),
+    }
+
+    /// Finality update for a counterpart chain's light client.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum ClientUpdate {
+        /// Update of a GRANDPA client.
+        Grandpa(GrandpaUpdate),
+        /// Update of a sync committee client.
+        SyncCommittee(SyncCommitteeUpdate),
     }
 
     /// Main pallet structure for interoperability.
     #[frame_support::pallet]
     pub struct Pallet<T>(_);
 
-    /// Storage for outgoing messages.
+    /// Channels, keyed by counterpart chain.
     #[pallet::storage]
-    #[pallet::getter(fn outgoing_messages)]
-    pub type OutgoingMessages<T: Config> = StorageMap<_, Blake2_128Concat, u64, InteropMessage, OptionQuery>;
+    #[pallet::getter(fn channels)]
+    pub type Channels<T: Config> = StorageMap<_, Twox64Concat, ChainId, Channel, OptionQuery>;
 
-    /// Storage for incoming messages.
+    /// Channels closed by a timeout. The destination still expects the timed-out nonce, which will never be
+    /// delivered, so these channels can never be reopened. Kept apart from `Channel`, whose encoding counterpart
+    /// light clients read.
+    #[pallet::storage]
+    #[pallet::getter(fn timed_out_channels)]
+    pub type TimedOutChannels<T: Config> = StorageMap<_, Twox64Concat, ChainId, (), OptionQuery>;
+
+    /// Outbound messages awaiting acknowledgement, keyed by destination and nonce.
+    #[pallet::storage]
+    #[pallet::getter(fn outbound_queue)]
+    pub type OutboundQueue<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        ChainId,
+        Twox64Concat,
+        u64,
+        OutboundMessage<T::AccountId, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Storage for incoming messages, keyed by source and nonce.
     #[pallet::storage]
     #[pallet::getter(fn incoming_messages)]
-    pub type IncomingMessages<T: Config> = StorageMap<_, Blake2_128Concat, u64, InteropMessage, OptionQuery>;
+    pub type IncomingMessages<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, ChainId, Twox64Concat, u64, InteropMessage, OptionQuery>;
+
+    /// Light client state of each counterpart chain.
+    #[pallet::storage]
+    #[pallet::getter(fn light_clients)]
+    pub type LightClients<T: Config> = StorageMap<_, Twox64Concat, ChainId, ClientState, OptionQuery>;
+
+    /// Finalized headers of counterpart chains, keyed by chain and header number.
+    #[pallet::storage]
+    #[pallet::getter(fn finalized_headers)]
+    pub type FinalizedHeaders<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, ChainId, Twox64Concat, u64, FinalizedHeader, OptionQuery>;
+
+    /// Number of the latest finalized header of each counterpart chain.
+    #[pallet::storage]
+    #[pallet::getter(fn latest_finalized)]
+    pub type LatestFinalized<T: Config> = StorageMap<_, Twox64Concat, ChainId, u64, OptionQuery>;
 
-    /// Storage for logging interop events.
-    /// Each record: (timestamp, message ID, operation type, details)
+    /// Accounts allowed to relay inbound messages and delivery acknowledgements.
     #[pallet::storage]
-    #[pallet::getter(fn interop_history)]
-    pub type InteropHistory<T: Config> = StorageValue<_, Vec<(u64, u64, Vec<u8>, Vec<u8>)>, ValueQuery>;
+    #[pallet::getter(fn relayers)]
+    pub type Relayers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The runtime event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-        /// Base timeout for message validity (in seconds).
+        /// Number of destination blocks, past the destination's latest tracked finalized header, within which an
+        /// outbound message must be received before it expires.
         #[pallet::constant]
         type BaseTimeout: Get<u64>;
         /// Maximum allowed payload length for interop messages.
         #[pallet::constant]
         type MaxPayloadLength: Get<u32>;
+        /// Origin allowed to manage channels and relayers.
+        type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+        /// Notified when sent messages are acknowledged or time out.
+        type OnOutcome: OnMessageOutcome<Self::AccountId>;
+        /// ID of this chain, under which counterparts queue messages to Nodara.
+        #[pallet::constant]
+        type SelfChainId: Get<ChainId>;
+        /// BLS12-381 signature verification used by sync committee light clients.
+        type BlsVerifier: light_client::BlsVerifier;
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// Emitted when a message is sent: (message ID, payload details).
-        MessageSent(u64, Vec<u8>),
-        /// Emitted when a message is received: (message ID, payload details).
-        MessageReceived(u64, Vec<u8>),
+        /// Emitted when a message is queued: (destination, nonce, sender).
+        MessageSent(ChainId, u64, T::AccountId),
+        /// Emitted when a message's delivery is acknowledged: (destination, nonce).
+        MessageAcknowledged(ChainId, u64),
+        /// Emitted when a message is proven to have timed out undelivered: (destination, nonce).
+        MessageTimedOut(ChainId, u64),
+        /// Emitted when a message is received: (source, nonce, payload).
+        MessageReceived(ChainId, u64, Vec<u8>),
+        /// Emitted when a channel is opened: (counterpart chain).
+        ChannelOpened(ChainId),
+        /// Emitted when a channel stops accepting outbound messages: (counterpart chain).
+        ChannelClosed(ChainId),
+        /// Emitted when a relayer is authorized: (relayer).
+        RelayerAdded(T::AccountId),
+        /// Emitted when a relayer is removed: (relayer).
+        RelayerRemoved(T::AccountId),
+        /// Emitted when a counterpart chain's light client is registered: (chain, checkpoint number).
+        LightClientRegistered(ChainId, u64),
+        /// Emitted when a counterpart chain's header is proven final: (chain, number, hash).
+        HeaderFinalized(ChainId, u64, H256),
         /// Emitted when interop configuration is updated.
         ConfigUpdated(Vec<u8>, Vec<u8>),
     }
@@ -104,52 +277,267 @@ pub mod pallet {
     pub enum Error<T> {
         /// Payload length exceeds the maximum allowed limit.
         PayloadTooLong,
-        /// Message signature verification failed.
+        /// The inclusion or channel proof does not verify against the header.
         VerificationFailed,
         /// Invalid configuration parameters.
         InvalidConfig,
+        /// No channel exists to this chain.
+        ChannelNotFound,
+        /// The channel is already open.
+        ChannelAlreadyOpen,
+        /// The channel does not accept outbound messages.
+        ChannelClosed,
+        /// The caller is not an authorized relayer.
+        NotRelayer,
+        /// The nonce is not the next one in sequence on the channel.
+        UnexpectedNonce,
+        /// No more nonces can be allocated on the channel.
+        NonceOverflow,
+        /// No light client is registered for the chain.
+        LightClientNotFound,
+        /// The update is for a different kind of light client.
+        LightClientMismatch,
+        /// The finality update does not verify.
+        InvalidFinalityUpdate,
+        /// The header is not newer than the latest finalized header.
+        StaleHeader,
+        /// The referenced header is not tracked.
+        HeaderNotFound,
+        /// The message's expiry height has been reached; it can no longer be received.
+        MessageExpired,
+        /// The destination had not received the message at the proven header.
+        NotDelivered,
+        /// The destination had received the message at the proven header.
+        AlreadyDelivered,
+        /// The proven header is below the message's expiry height.
+        TimeoutNotReached,
+        /// The channel was closed by a timeout and can no longer deliver messages in order.
+        ChannelTimedOut,
     }
 
     impl<T: Config> Pallet<T> {
+        /// Opens a channel to a counterpart chain, or reopens a closed one with its sequence numbers preserved.
+        /// Channels closed by a timeout cannot be reopened.
+        pub fn open_channel(origin: T::Origin, chain: ChainId) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(!TimedOutChannels::<T>::contains_key(chain), Error::<T>::ChannelTimedOut);
+            Channels::<T>::try_mutate(chain, |maybe_channel| -> DispatchResult {
+                let channel = maybe_channel.get_or_insert_with(Channel::default);
+                ensure!(!channel.open, Error::<T>::ChannelAlreadyOpen);
+                channel.open = true;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::ChannelOpened(chain));
+            Ok(())
+        }
+
+        /// Stops a channel from accepting outbound messages. Pending messages are still acknowledged or timed out,
+        /// and inbound messages are still accepted.
+        pub fn close_channel(origin: T::Origin, chain: ChainId) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            Channels::<T>::try_mutate(chain, |maybe_channel| -> DispatchResult {
+                let channel = maybe_channel.as_mut().ok_or(Error::<T>::ChannelNotFound)?;
+                ensure!(channel.open, Error::<T>::ChannelClosed);
+                channel.open = false;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::ChannelClosed(chain));
+            Ok(())
+        }
+
+        /// Authorizes `relayer` to relay inbound messages and acknowledgements.
+        pub fn add_relayer(origin: T::Origin, relayer: T::AccountId) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            Relayers::<T>::insert(&relayer, ());
+            Self::deposit_event(Event::RelayerAdded(relayer));
+            Ok(())
+        }
+
+        /// Removes `relayer` from the authorized relayers.
+        pub fn remove_relayer(origin: T::Origin, relayer: T::AccountId) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(Relayers::<T>::take(&relayer).is_some(), Error::<T>::NotRelayer);
+            Self::deposit_event(Event::RelayerRemoved(relayer));
+            Ok(())
+        }
+
         /// Sends an interop message to an external chain.
         ///
         /// # Parameters:
-        /// - `id`: Unique message identifier.
+        /// - `destination`: The counterpart chain.
         /// - `payload`: Data payload for the message.
-        /// - `signature`: Cryptographic signature for verifying the message.
         ///
         /// # Requirements:
         /// - The payload length must not exceed `MaxPayloadLength`.
-        pub fn send_message(id: u64, payload: Vec<u8>, signature: Vec<u8>) -> DispatchResult {
-            ensure!(payload.len() as u32 <= T::MaxPayloadLength::get(), Error::<T>::PayloadTooLong);
-            let timestamp = Self::current_timestamp();
-            let message = InteropMessage { id, payload: payload.clone(), timestamp, signature };
-            <OutgoingMessages<T>>::insert(id, message);
-            <InteropHistory<T>>::mutate(|history| {
-                history.push((timestamp, id, b"Send".to_vec(), payload.clone()))
-            });
-            Self::deposit_event(Event::MessageSent(id, payload));
+        /// - The channel to `destination` must be open.
+        pub fn send_message(origin: T::Origin, destination: ChainId, payload: Vec<u8>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::do_send(&sender, destination, payload)?;
+            Ok(())
+        }
+
+        /// Acknowledges delivery of the oldest pending message to `destination`. Channels are ordered, so
+        /// acknowledgements must arrive in nonce order.
+        ///
+        /// # Parameters:
+        /// - `header`: Number of a tracked finalized header of `destination`.
+        /// - `proof`: Proof of the destination's channel to this chain in the state of `header`, in the format of the
+        ///   destination's light client.
+        ///
+        /// # Requirements:
+        /// - The caller must be an authorized relayer.
+        /// - `nonce` must be the channel's oldest pending nonce.
+        /// - The destination's channel must have received the message, i.e., expect a later inbound nonce.
+        pub fn acknowledge(
+            origin: T::Origin,
+            destination: ChainId,
+            nonce: u64,
+            header: u64,
+            proof: Vec<u8>,
+        ) -> DispatchResult {
+            let relayer = ensure_signed(origin)?;
+            ensure!(Relayers::<T>::contains_key(&relayer), Error::<T>::NotRelayer);
+            let mut channel = Channels::<T>::get(destination).ok_or(Error::<T>::ChannelNotFound)?;
+            ensure!(nonce == channel.next_pending_nonce, Error::<T>::UnexpectedNonce);
+            ensure!(OutboundQueue::<T>::contains_key(destination, nonce), Error::<T>::UnexpectedNonce);
+            let received = Self::proven_inbound_nonce(destination, header, &proof)?;
+            ensure!(received > nonce, Error::<T>::NotDelivered);
+            let message = OutboundQueue::<T>::take(destination, nonce).ok_or(Error::<T>::UnexpectedNonce)?;
+            channel.next_pending_nonce = nonce.saturating_add(1);
+            Channels::<T>::insert(destination, channel);
+            T::OnOutcome::on_acknowledged(destination, nonce, &message.sender, &message.payload);
+            Self::deposit_event(Event::MessageAcknowledged(destination, nonce));
+            Ok(())
+        }
+
+        /// Times out the oldest pending message to `destination`, which the destination can no longer execute. The
+        /// channel is closed for good, as later messages can no longer be delivered in order; they time out in turn.
+        ///
+        /// # Parameters:
+        /// - `header`: Number of a tracked finalized header of `destination`, at or past the message's `timeout_at`.
+        /// - `proof`: Proof of the destination's channel to this chain in the state of `header`, in the format of the
+        ///   destination's light client.
+        ///
+        /// # Requirements:
+        /// - The caller must be an authorized relayer.
+        /// - `nonce` must be the channel's oldest pending nonce.
+        /// - The destination's channel must not have received the message by `header`.
+        pub fn timeout_message(
+            origin: T::Origin,
+            destination: ChainId,
+            nonce: u64,
+            header: u64,
+            proof: Vec<u8>,
+        ) -> DispatchResult {
+            let relayer = ensure_signed(origin)?;
+            ensure!(Relayers::<T>::contains_key(&relayer), Error::<T>::NotRelayer);
+            let mut channel = Channels::<T>::get(destination).ok_or(Error::<T>::ChannelNotFound)?;
+            ensure!(nonce == channel.next_pending_nonce, Error::<T>::UnexpectedNonce);
+            let message = OutboundQueue::<T>::get(destination, nonce).ok_or(Error::<T>::UnexpectedNonce)?;
+            ensure!(header >= message.timeout_at, Error::<T>::TimeoutNotReached);
+            let received = Self::proven_inbound_nonce(destination, header, &proof)?;
+            ensure!(received <= nonce, Error::<T>::AlreadyDelivered);
+            OutboundQueue::<T>::remove(destination, nonce);
+            channel.next_pending_nonce = nonce.saturating_add(1);
+            let was_open = core::mem::replace(&mut channel.open, false);
+            Channels::<T>::insert(destination, channel);
+            TimedOutChannels::<T>::insert(destination, ());
+            if was_open {
+                Self::deposit_event(Event::ChannelClosed(destination));
+            }
+            T::OnOutcome::on_timeout(destination, nonce, &message.sender, &message.payload);
+            Self::deposit_event(Event::MessageTimedOut(destination, nonce));
+            Ok(())
+        }
+
+        /// Starts tracking a counterpart chain from a trusted checkpoint, replacing any previous light client (e.g.,
+        /// to follow a hard fork).
+        ///
+        /// # Parameters:
+        /// - `chain`: The counterpart chain.
+        /// - `state`: Initial light client state (authority set or sync committee at the checkpoint).
+        /// - `checkpoint`: The trusted finalized header the client starts from.
+        pub fn register_light_client(
+            origin: T::Origin,
+            chain: ChainId,
+            state: ClientState,
+            checkpoint: FinalizedHeader,
+        ) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            let number = checkpoint.number;
+            LightClients::<T>::insert(chain, state);
+            FinalizedHeaders::<T>::insert(chain, number, checkpoint);
+            LatestFinalized::<T>::insert(chain, number);
+            Self::deposit_event(Event::LightClientRegistered(chain, number));
+            Ok(())
+        }
+
+        /// Verifies a finality update of a counterpart chain and tracks the newly finalized header.
+        ///
+        /// # Requirements:
+        /// - The caller must be an authorized relayer.
+        /// - The update must match the chain's light client and prove a header newer than the latest one.
+        pub fn submit_finality_update(origin: T::Origin, chain: ChainId, update: ClientUpdate) -> DispatchResult {
+            let relayer = ensure_signed(origin)?;
+            ensure!(Relayers::<T>::contains_key(&relayer), Error::<T>::NotRelayer);
+            let mut state = LightClients::<T>::get(chain).ok_or(Error::<T>::LightClientNotFound)?;
+            let header = match (&mut state, update) {
+                (ClientState::Grandpa(state), ClientUpdate::Grandpa(update)) => GrandpaClient::verify_update(state, update),
+                (ClientState::SyncCommittee(state), ClientUpdate::SyncCommittee(update)) => {
+                    SyncCommitteeClient::<T::BlsVerifier>::verify_update(state, update)
+                }
+                _ => return Err(Error::<T>::LightClientMismatch.into()),
+            }
+            .map_err(|error| match error {
+                LightClientError::StaleHeader => Error::<T>::StaleHeader,
+                _ => Error::<T>::InvalidFinalityUpdate,
+            })?;
+            LightClients::<T>::insert(chain, state);
+            LatestFinalized::<T>::insert(chain, header.number);
+            Self::deposit_event(Event::HeaderFinalized(chain, header.number, header.hash));
+            FinalizedHeaders::<T>::insert(chain, header.number, header);
             Ok(())
         }
 
         /// Receives and verifies an interop message from an external chain.
         ///
         /// # Parameters:
-        /// - `id`: Unique message identifier.
+        /// - `source`: The counterpart chain the message comes from.
+        /// - `nonce`: Sequence number of the message on the channel.
         /// - `payload`: Data payload for the message.
-        /// - `signature`: Cryptographic signature for verifying the message.
+        /// - `timeout_at`: The message's expiry height, in this chain's blocks, as committed by `source`.
+        /// - `header`: Number of a tracked finalized header of `source`.
+        /// - `proof`: Proof that `source` queued the message to this chain in the state of `header`, in the format
+        ///   of the source's light client.
         ///
         /// # Requirements:
-        /// - The message must pass the simulated signature verification.
-        pub fn receive_message(id: u64, payload: Vec<u8>, signature: Vec<u8>) -> DispatchResult {
-            ensure!(Self::verify_signature(&payload, &signature), Error::<T>::VerificationFailed);
+        /// - The caller must be an authorized relayer.
+        /// - A channel to `source` must exist and `nonce` must be its next inbound nonce.
+        /// - The proof must verify against the tracked header.
+        /// - The current block must be below `timeout_at`; past it, `source` may already have rolled the message back.
+        pub fn receive_message(
+            origin: T::Origin,
+            source: ChainId,
+            nonce: u64,
+            payload: Vec<u8>,
+            timeout_at: u64,
+            header: u64,
+            proof: Vec<u8>,
+        ) -> DispatchResult {
+            let relayer = ensure_signed(origin)?;
+            ensure!(Relayers::<T>::contains_key(&relayer), Error::<T>::NotRelayer);
+            ensure!(payload.len() as u32 <= T::MaxPayloadLength::get(), Error::<T>::PayloadTooLong);
+            let mut channel = Channels::<T>::get(source).ok_or(Error::<T>::ChannelNotFound)?;
+            ensure!(nonce == channel.next_inbound_nonce, Error::<T>::UnexpectedNonce);
+            Self::verify_inclusion(source, nonce, &payload, timeout_at, header, &proof)?;
+            let now: u64 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+            ensure!(now < timeout_at, Error::<T>::MessageExpired);
+            channel.next_inbound_nonce = nonce.checked_add(1).ok_or(Error::<T>::NonceOverflow)?;
+            Channels::<T>::insert(source, channel);
             let timestamp = Self::current_timestamp();
-            let message = InteropMessage { id, payload: payload.clone(), timestamp, signature };
-            <IncomingMessages<T>>::insert(id, message);
-            <InteropHistory<T>>::mutate(|history| {
-                history.push((timestamp, id, b"Receive".to_vec(), payload.clone()))
-            });
-            Self::deposit_event(Event::MessageReceived(id, payload));
+            let message = InteropMessage { nonce, payload: payload.clone(), timestamp, proven_at: header };
+            <IncomingMessages<T>>::insert(source, nonce, message);
+            Self::deposit_event(Event::MessageReceived(source, nonce, payload));
             Ok(())
         }
 
@@ -163,18 +551,75 @@ pub mod pallet {
         /// - The new configuration must be non-empty.
         pub fn update_config(new_config: Vec<u8>, details: Vec<u8>) -> DispatchResult {
             ensure!(!new_config.is_empty(), Error::<T>::InvalidConfig);
-            let timestamp = Self::current_timestamp();
-            <InteropHistory<T>>::mutate(|history| {
-                history.push((timestamp, 0, b"ConfigUpdate".to_vec(), details.clone()))
-            });
             Self::deposit_event(Event::ConfigUpdated(new_config, details));
             Ok(())
         }
 
-        /// Simulated signature verification for interop messages.
-        fn verify_signature(payload: &Vec<u8>, signature: &Vec<u8>) -> bool {
-            // Simulation: Both payload and signature must be non-empty.
-            !payload.is_empty() && !signature.is_empty()
+        /// Queues a message for `destination` under the channel's next outbound nonce and returns that nonce.
+        ///
+        /// The message expires `BaseTimeout` blocks of `destination` past its latest tracked finalized header, so
+        /// `destination` needs a light client.
+        pub fn do_send(sender: &T::AccountId, destination: ChainId, payload: Vec<u8>) -> Result<u64, DispatchError> {
+            ensure!(payload.len() as u32 <= T::MaxPayloadLength::get(), Error::<T>::PayloadTooLong);
+            let mut channel = Channels::<T>::get(destination).ok_or(Error::<T>::ChannelNotFound)?;
+            ensure!(channel.open, Error::<T>::ChannelClosed);
+            let latest = LatestFinalized::<T>::get(destination).ok_or(Error::<T>::LightClientNotFound)?;
+            let nonce = channel.next_outbound_nonce;
+            channel.next_outbound_nonce = nonce.checked_add(1).ok_or(Error::<T>::NonceOverflow)?;
+            Channels::<T>::insert(destination, channel);
+            let sent_at = frame_system::Pallet::<T>::block_number();
+            let timeout_at = latest.saturating_add(T::BaseTimeout::get());
+            let message = OutboundMessage { sender: sender.clone(), payload, sent_at, timeout_at };
+            OutboundQueue::<T>::insert(destination, nonce, message);
+            Self::deposit_event(Event::MessageSent(destination, nonce, sender.clone()));
+            Ok(nonce)
+        }
+
+        /// Checks, with the source chain's light client, that `source` queued `payload` as message `nonce` to this
+        /// chain, expiring at `timeout_at`, in the state of its tracked header `header`.
+        fn verify_inclusion(
+            source: ChainId,
+            nonce: u64,
+            payload: &[u8],
+            timeout_at: u64,
+            header: u64,
+            proof: &[u8],
+        ) -> DispatchResult {
+            let state = LightClients::<T>::get(source).ok_or(Error::<T>::LightClientNotFound)?;
+            let header = FinalizedHeaders::<T>::get(source, header).ok_or(Error::<T>::HeaderNotFound)?;
+            let destination = T::SelfChainId::get();
+            match &state {
+                ClientState::Grandpa(state) => {
+                    GrandpaClient::verify_message(state, &header, destination, nonce, payload, timeout_at, proof)
+                }
+                ClientState::SyncCommittee(state) => SyncCommitteeClient::<T::BlsVerifier>::verify_message(
+                    state,
+                    &header,
+                    destination,
+                    nonce,
+                    payload,
+                    timeout_at,
+                    proof,
+                ),
+            }
+            .map_err(|_| Error::<T>::VerificationFailed)?;
+            Ok(())
+        }
+
+        /// Returns the next inbound nonce of `destination`'s channel to this chain, i.e., how many of this chain's
+        /// messages it has received, as proven with its light client in the state of its tracked header `header`.
+        fn proven_inbound_nonce(destination: ChainId, header: u64, proof: &[u8]) -> Result<u64, DispatchError> {
+            let state = LightClients::<T>::get(destination).ok_or(Error::<T>::LightClientNotFound)?;
+            let header = FinalizedHeaders::<T>::get(destination, header).ok_or(Error::<T>::HeaderNotFound)?;
+            let source = T::SelfChainId::get();
+            let received = match &state {
+                ClientState::Grandpa(state) => GrandpaClient::verify_inbound_nonce(state, &header, source, proof),
+                ClientState::SyncCommittee(state) => {
+                    SyncCommitteeClient::<T::BlsVerifier>::verify_inbound_nonce(state, &header, source, proof)
+                }
+            }
+            .map_err(|_| Error::<T>::VerificationFailed)?;
+            Ok(received)
         }
 
         /// Returns the current Unix timestamp.
@@ -183,6 +628,12 @@ pub mod pallet {
             1_640_000_000 // Placeholder timestamp.
         }
     }
+
+    impl<T: Config> MessageSender<T::AccountId> for Pallet<T> {
+        fn send(sender: &T::AccountId, destination: ChainId, payload: Vec<u8>) -> Result<u64, DispatchError> {
+            Self::do_send(sender, destination, payload)
+        }
+    }
 }
 
 // --- Benchmarking Section ---
@@ -196,28 +647,103 @@ mod benchmarking {
     use frame_system::RawOrigin;
     use sp_std::vec::Vec;
     use core::ops::Div;
+    use light_client::{GrandpaJustification, SubstrateHeader};
+    use sp_core::crypto::KeyTypeId;
+    use sp_runtime::traits::Header as HeaderT;
+
+    /// Key type of the keystore keys generated for benchmarking GRANDPA authorities.
+    const KEY_TYPE: KeyTypeId = KeyTypeId(*b"intr");
+
+    /// Registers a GRANDPA light client of `chain` from a genesis checkpoint, so messages can be sent to it.
+    fn track_chain<T: Config>(chain: ChainId) -> DispatchResult {
+        let state = GrandpaState { set_id: 0, authorities: Vec::new(), finalized_number: 0, interop_pallet: b"Interop".to_vec() };
+        let checkpoint = FinalizedHeader { number: 0, hash: H256::zero(), state_root: H256::zero() };
+        Pallet::<T>::register_light_client(RawOrigin::Root.into(), chain, ClientState::Grandpa(state), checkpoint)
+    }
+
+    /// Tracks header `number` of `chain` with a state holding its channel to this chain at `next_inbound_nonce`, and
+    /// returns the proof of that channel.
+    fn track_channel<T: Config>(chain: ChainId, number: u64, next_inbound_nonce: u64) -> Vec<u8> {
+        use sp_trie::{trie_types::TrieDBMutBuilderV1, MemoryDB, TrieMut};
+        let key = GrandpaClient::channel_storage_key(b"Interop", T::SelfChainId::get());
+        let channel = Channel { open: true, next_outbound_nonce: 0, next_pending_nonce: 0, next_inbound_nonce };
+        let mut db = MemoryDB::<sp_runtime::traits::BlakeTwo256>::default();
+        let mut root = H256::zero();
+        {
+            let mut trie = TrieDBMutBuilderV1::new(&mut db, &mut root).build();
+            trie.insert(&key, &channel.encode()).expect("in-memory trie");
+        }
+        let nodes: Vec<Vec<u8>> = db.drain().into_values().filter(|(_, rc)| *rc > 0).map(|(node, _)| node).collect();
+        FinalizedHeaders::<T>::insert(chain, number, FinalizedHeader { number, hash: H256::zero(), state_root: root });
+        nodes.encode()
+    }
 
     benchmarks! {
         send_message {
-            let id: u64 = 1;
-            let payload: Vec<u8> = b"BenchmarkPayload".to_vec();
-            let signature: Vec<u8> = b"BenchmarkSignature".to_vec();
+            let sender: T::AccountId = account("sender", 0, 0);
+            let payload: Vec<u8> = sp_std::vec![1u8; T::MaxPayloadLength::get() as usize];
+            Pallet::<T>::open_channel(RawOrigin::Root.into(), 1)?;
+            track_chain::<T>(1)?;
         }: {
-            Pallet::<T>::send_message(id, payload.clone(), signature.clone())?;
+            Pallet::<T>::send_message(RawOrigin::Signed(sender.clone()).into(), 1, payload.clone())?;
         }
         verify {
-            // Verification based on event emission.
+            assert!(OutboundQueue::<T>::contains_key(1, 0));
         }
 
-        receive_message {
-            let id: u64 = 2;
-            let payload: Vec<u8> = b"BenchmarkReceive".to_vec();
-            let signature: Vec<u8> = b"BenchmarkSignature".to_vec();
+        acknowledge {
+            let sender: T::AccountId = account("sender", 0, 0);
+            let relayer: T::AccountId = account("relayer", 0, 0);
+            Pallet::<T>::open_channel(RawOrigin::Root.into(), 1)?;
+            Pallet::<T>::add_relayer(RawOrigin::Root.into(), relayer.clone())?;
+            track_chain::<T>(1)?;
+            Pallet::<T>::send_message(RawOrigin::Signed(sender).into(), 1, b"BenchmarkPayload".to_vec())?;
+            let proof = track_channel::<T>(1, 1, 1);
         }: {
-            Pallet::<T>::receive_message(id, payload.clone(), signature.clone())?;
+            Pallet::<T>::acknowledge(RawOrigin::Signed(relayer.clone()).into(), 1, 0, 1, proof.clone())?;
         }
         verify {
-            // Verification based on event emission.
+            assert!(!OutboundQueue::<T>::contains_key(1, 0));
+        }
+
+        submit_finality_update {
+            let relayer: T::AccountId = account("relayer", 0, 0);
+            Pallet::<T>::add_relayer(RawOrigin::Root.into(), relayer.clone())?;
+            let authorities: Vec<_> = (0..32).map(|_| (sp_io::crypto::ed25519_generate(KEY_TYPE, None), 1u64)).collect();
+            let state = GrandpaState { set_id: 0, authorities: authorities.clone(), finalized_number: 0, interop_pallet: b"Interop".to_vec() };
+            let checkpoint = FinalizedHeader { number: 0, hash: H256::zero(), state_root: H256::zero() };
+            Pallet::<T>::register_light_client(RawOrigin::Root.into(), 1, ClientState::Grandpa(state), checkpoint)?;
+            let header = SubstrateHeader::new(1, H256::zero(), H256::repeat_byte(1), H256::zero(), Default::default());
+            let hash = header.hash();
+            let message = GrandpaClient::precommit_payload(&hash, 1, 0, 0);
+            let precommits = authorities
+                .iter()
+                .map(|(authority, _)| (*authority, sp_io::crypto::ed25519_sign(KEY_TYPE, authority, &message).unwrap()))
+                .collect();
+            let justification = GrandpaJustification { round: 0, target_hash: hash, target_number: 1, precommits };
+            let update = ClientUpdate::Grandpa(GrandpaUpdate { header, justification });
+        }: {
+            Pallet::<T>::submit_finality_update(RawOrigin::Signed(relayer.clone()).into(), 1, update)?;
+        }
+        verify {
+            assert_eq!(LatestFinalized::<T>::get(1), Some(1));
+        }
+
+        timeout_message {
+            let sender: T::AccountId = account("sender", 0, 0);
+            let relayer: T::AccountId = account("relayer", 0, 0);
+            Pallet::<T>::open_channel(RawOrigin::Root.into(), 1)?;
+            Pallet::<T>::add_relayer(RawOrigin::Root.into(), relayer.clone())?;
+            track_chain::<T>(1)?;
+            Pallet::<T>::send_message(RawOrigin::Signed(sender).into(), 1, b"BenchmarkPayload".to_vec())?;
+            // The destination has received nothing at the message's expiry height.
+            let expiry = T::BaseTimeout::get();
+            let proof = track_channel::<T>(1, expiry, 0);
+        }: {
+            Pallet::<T>::timeout_message(RawOrigin::Signed(relayer.clone()).into(), 1, 0, expiry, proof.clone())?;
+        }
+        verify {
+            assert!(!OutboundQueue::<T>::contains_key(1, 0));
         }
 
         update_config {
@@ -0,0 +1,808 @@
+//! Light clients of counterpart chains.
+//!
+//! A light client follows the finality of a counterpart chain from a trusted checkpoint, accepting a newer finalized
+//! header only with a valid finality proof, and checks that the counterpart committed an outbound message to Nodara
+//! in the state of a finalized header. It also proves how far the counterpart has received Nodara's messages, which
+//! is how their delivery is acknowledged and their timeouts are justified.
+//!
+//! - [`GrandpaClient`]: Substrate chains finalized by GRANDPA. Finality is proven by precommits of more than two thirds
+//!   of the authority set's weight; messages and received nonces by storage proofs of the counterpart's
+//!   `nodara_interop` outbound queue and channels.
+//! - [`SyncCommitteeClient`]: The Ethereum beacon chain, following the Altair light-client protocol. Finality is
+//!   proven by a sync committee signature and SSZ Merkle branches; messages and received nonces by Merkle-Patricia
+//!   proofs of the bridge contract's storage in the execution state.
+
+use crate::ChainId;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::{ed25519, H160, H256};
+use sp_io::hashing::{keccak_256, sha2_256, twox_128, twox_64};
+use sp_runtime::{
+    generic,
+    traits::{BlakeTwo256, Header as HeaderT},
+    ConsensusEngineId, DigestItem, RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// A finalized header of a counterpart chain, reduced to what message verification needs.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct FinalizedHeader {
+    /// Block number (slot, for the beacon chain).
+    pub number: u64,
+    /// Block hash (beacon block root, for the beacon chain).
+    pub hash: H256,
+    /// State root message proofs are checked against (the execution state root, for the beacon chain).
+    pub state_root: H256,
+}
+
+/// Reasons a finality update or a message proof is rejected.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum LightClientError {
+    /// The update or proof is not well-formed.
+    Malformed,
+    /// The header is not newer than the latest finalized header.
+    StaleHeader,
+    /// The finality proof does not target the given header.
+    TargetMismatch,
+    /// A vote comes from outside the authority set.
+    UnknownAuthority,
+    /// An authority voted more than once.
+    DuplicateVote,
+    /// A signature does not verify.
+    BadSignature,
+    /// Signers hold too little weight (or too few sync committee members signed).
+    InsufficientVotes,
+    /// The header changes the authority set in a way the client cannot follow (forced or delayed change).
+    UnsupportedAuthorityChange,
+    /// The update is signed by a sync committee the client does not know yet.
+    UnknownSyncCommittee,
+    /// A Merkle branch does not verify.
+    InvalidBranch,
+    /// The inclusion proof does not verify against the header's state root.
+    InvalidProof,
+    /// The proven state holds no such message.
+    MessageNotFound,
+    /// The proven message differs from the relayed one.
+    PayloadMismatch,
+}
+
+/// Verification of a counterpart chain's finality and state.
+pub trait LightClient {
+    /// State kept between updates (authority set, sync committee, ...).
+    type State;
+    /// Finality update relayed from the counterpart.
+    type Update;
+
+    /// Verifies `update` against `state`. On success `state` follows the update (e.g., an authority set change) and
+    /// the newly finalized header is returned; on failure `state` is left unchanged.
+    fn verify_update(state: &mut Self::State, update: Self::Update) -> Result<FinalizedHeader, LightClientError>;
+
+    /// Verifies that the counterpart committed `payload` as message `nonce` to `destination`, expiring at
+    /// `timeout_at` (a block number of `destination`), in the state of `header`, a header finalized by this client.
+    fn verify_message(
+        state: &Self::State,
+        header: &FinalizedHeader,
+        destination: ChainId,
+        nonce: u64,
+        payload: &[u8],
+        timeout_at: u64,
+        proof: &[u8],
+    ) -> Result<(), LightClientError>;
+
+    /// Returns the nonce the counterpart expects next from `source` (i.e., how many of `source`'s messages it has
+    /// received), as proven in the state of `header`. A counterpart without a channel to `source` has received none.
+    fn verify_inbound_nonce(
+        state: &Self::State,
+        header: &FinalizedHeader,
+        source: ChainId,
+        proof: &[u8],
+    ) -> Result<u64, LightClientError>;
+}
+
+// --- GRANDPA ---
+
+/// Consensus engine ID of GRANDPA digests.
+pub const GRANDPA_ENGINE_ID: ConsensusEngineId = *b"FRNK";
+
+/// Index of `ScheduledChange` in GRANDPA's `ConsensusLog`.
+const SCHEDULED_CHANGE_LOG: u8 = 1;
+/// Index of `ForcedChange` in GRANDPA's `ConsensusLog`.
+const FORCED_CHANGE_LOG: u8 = 2;
+/// Index of `Precommit` in GRANDPA's vote `Message`.
+const PRECOMMIT_MESSAGE: u8 = 1;
+
+/// GRANDPA authorities and their voting weights.
+pub type AuthorityList = Vec<(ed25519::Public, u64)>;
+
+/// Header of a Substrate counterpart.
+pub type SubstrateHeader = generic::Header<u32, BlakeTwo256>;
+
+/// State of a GRANDPA light client.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct GrandpaState {
+    /// ID of the current authority set.
+    pub set_id: u64,
+    /// Current authority set.
+    pub authorities: AuthorityList,
+    /// Number of the latest finalized header.
+    pub finalized_number: u32,
+    /// Name of the `nodara_interop` instance in the counterpart's runtime (e.g., `b"Interop"`).
+    pub interop_pallet: Vec<u8>,
+}
+
+/// A GRANDPA commit justifying the finality of a header.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct GrandpaJustification {
+    /// Voting round.
+    pub round: u64,
+    /// Hash of the finalized header.
+    pub target_hash: H256,
+    /// Number of the finalized header.
+    pub target_number: u32,
+    /// Precommits for the target and the authorities that signed them.
+    pub precommits: Vec<(ed25519::Public, ed25519::Signature)>,
+}
+
+/// A GRANDPA finality update: a header and the justification finalizing it.
+///
+/// Relayers must submit every header that schedules an authority set change, since the change is read from the
+/// header's digest.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct GrandpaUpdate {
+    /// The finalized header.
+    pub header: SubstrateHeader,
+    /// Justification of its finality by the current authority set.
+    pub justification: GrandpaJustification,
+}
+
+/// GRANDPA light client.
+pub struct GrandpaClient;
+
+impl GrandpaClient {
+    /// Returns the message GRANDPA authorities sign when precommitting to a target.
+    pub fn precommit_payload(target_hash: &H256, target_number: u32, round: u64, set_id: u64) -> Vec<u8> {
+        (PRECOMMIT_MESSAGE, target_hash, target_number, round, set_id).encode()
+    }
+
+    /// Returns the storage key of message `nonce` to `destination` in a counterpart's `nodara_interop` outbound
+    /// queue.
+    pub fn message_storage_key(interop_pallet: &[u8], destination: ChainId, nonce: u64) -> Vec<u8> {
+        let destination = destination.encode();
+        let nonce = nonce.encode();
+        let mut key = twox_128(interop_pallet).to_vec();
+        key.extend_from_slice(&twox_128(b"OutboundQueue"));
+        key.extend_from_slice(&twox_64(&destination));
+        key.extend_from_slice(&destination);
+        key.extend_from_slice(&twox_64(&nonce));
+        key.extend_from_slice(&nonce);
+        key
+    }
+
+    /// Returns the storage key of the channel to `counterpart` in a counterpart's `nodara_interop` pallet.
+    pub fn channel_storage_key(interop_pallet: &[u8], counterpart: ChainId) -> Vec<u8> {
+        let counterpart = counterpart.encode();
+        let mut key = twox_128(interop_pallet).to_vec();
+        key.extend_from_slice(&twox_128(b"Channels"));
+        key.extend_from_slice(&twox_64(&counterpart));
+        key.extend_from_slice(&counterpart);
+        key
+    }
+
+    /// Reads `key` from the state with root `state_root`, using the SCALE-encoded trie nodes in `proof`.
+    fn read_storage(state_root: &H256, key: &[u8], proof: &[u8]) -> Result<Option<Vec<u8>>, LightClientError> {
+        let nodes = Vec::<Vec<u8>>::decode(&mut &proof[..]).map_err(|_| LightClientError::Malformed)?;
+        let db = sp_trie::StorageProof::new(nodes).into_memory_db::<BlakeTwo256>();
+        sp_trie::read_trie_value::<sp_trie::LayoutV1<BlakeTwo256>, _>(&db, state_root, key, None, None)
+            .map_err(|_| LightClientError::InvalidProof)
+    }
+
+    /// Returns the authority set scheduled by a header, if any. Only immediate scheduled changes are supported.
+    fn scheduled_change(header: &SubstrateHeader) -> Result<Option<AuthorityList>, LightClientError> {
+        let mut change = None;
+        for log in header.digest().logs() {
+            let DigestItem::Consensus(engine_id, data) = log else { continue };
+            if *engine_id != GRANDPA_ENGINE_ID {
+                continue;
+            }
+            match data.first() {
+                Some(&SCHEDULED_CHANGE_LOG) => {
+                    let (authorities, delay) = <(AuthorityList, u32)>::decode(&mut &data[1..])
+                        .map_err(|_| LightClientError::Malformed)?;
+                    if delay != 0 || change.is_some() {
+                        return Err(LightClientError::UnsupportedAuthorityChange);
+                    }
+                    change = Some(authorities);
+                }
+                Some(&FORCED_CHANGE_LOG) => return Err(LightClientError::UnsupportedAuthorityChange),
+                _ => {}
+            }
+        }
+        Ok(change)
+    }
+}
+
+impl LightClient for GrandpaClient {
+    type State = GrandpaState;
+    type Update = GrandpaUpdate;
+
+    fn verify_update(state: &mut GrandpaState, update: GrandpaUpdate) -> Result<FinalizedHeader, LightClientError> {
+        let GrandpaUpdate { header, justification } = update;
+        if header.number <= state.finalized_number {
+            return Err(LightClientError::StaleHeader);
+        }
+        let hash = header.hash();
+        if justification.target_hash != hash || justification.target_number != header.number {
+            return Err(LightClientError::TargetMismatch);
+        }
+
+        let message = Self::precommit_payload(&hash, header.number, justification.round, state.set_id);
+        let mut signers: Vec<&ed25519::Public> = Vec::with_capacity(justification.precommits.len());
+        let mut signed_weight: u128 = 0;
+        for (authority, signature) in &justification.precommits {
+            let (_, weight) = state
+                .authorities
+                .iter()
+                .find(|(id, _)| id == authority)
+                .ok_or(LightClientError::UnknownAuthority)?;
+            if signers.contains(&authority) {
+                return Err(LightClientError::DuplicateVote);
+            }
+            if !sp_io::crypto::ed25519_verify(signature, &message, authority) {
+                return Err(LightClientError::BadSignature);
+            }
+            signers.push(authority);
+            signed_weight += *weight as u128;
+        }
+        // GRANDPA's supermajority threshold: more than two thirds of the total weight.
+        let total_weight: u128 = state.authorities.iter().map(|(_, weight)| *weight as u128).sum();
+        let threshold = total_weight - total_weight.saturating_sub(1) / 3;
+        if total_weight == 0 || signed_weight < threshold {
+            return Err(LightClientError::InsufficientVotes);
+        }
+
+        if let Some(authorities) = Self::scheduled_change(&header)? {
+            state.authorities = authorities;
+            state.set_id = state.set_id.saturating_add(1);
+        }
+        state.finalized_number = header.number;
+        Ok(FinalizedHeader { number: header.number as u64, hash, state_root: header.state_root })
+    }
+
+    /// `proof` is the SCALE encoding of the trie nodes (`Vec<Vec<u8>>`) proving the outbound queue entry. The
+    /// counterpart is expected to use 32-byte account IDs and `u32` block numbers, as Nodara does.
+    fn verify_message(
+        state: &GrandpaState,
+        header: &FinalizedHeader,
+        destination: ChainId,
+        nonce: u64,
+        payload: &[u8],
+        timeout_at: u64,
+        proof: &[u8],
+    ) -> Result<(), LightClientError> {
+        let key = Self::message_storage_key(&state.interop_pallet, destination, nonce);
+        let value = Self::read_storage(&header.state_root, &key, proof)?.ok_or(LightClientError::MessageNotFound)?;
+        // The counterpart's `OutboundMessage`: sender, payload, sent at, timeout at (in Nodara's blocks).
+        let (_sender, committed, _sent_at, committed_timeout) =
+            <([u8; 32], Vec<u8>, u32, u64)>::decode(&mut &value[..]).map_err(|_| LightClientError::Malformed)?;
+        if committed != payload || committed_timeout != timeout_at {
+            return Err(LightClientError::PayloadMismatch);
+        }
+        Ok(())
+    }
+
+    /// `proof` is the SCALE encoding of the trie nodes (`Vec<Vec<u8>>`) proving the counterpart's `Channels` entry
+    /// for `source`, or its absence.
+    fn verify_inbound_nonce(
+        state: &GrandpaState,
+        header: &FinalizedHeader,
+        source: ChainId,
+        proof: &[u8],
+    ) -> Result<u64, LightClientError> {
+        let key = Self::channel_storage_key(&state.interop_pallet, source);
+        let Some(value) = Self::read_storage(&header.state_root, &key, proof)? else {
+            return Ok(0);
+        };
+        // The counterpart's `Channel`: open, next outbound, next pending, next inbound nonce.
+        let (_open, _next_outbound, _next_pending, next_inbound) =
+            <(bool, u64, u64, u64)>::decode(&mut &value[..]).map_err(|_| LightClientError::Malformed)?;
+        Ok(next_inbound)
+    }
+}
+
+// --- Ethereum sync committee ---
+
+/// Number of members of a sync committee.
+pub const SYNC_COMMITTEE_SIZE: usize = 512;
+/// Slots per sync committee period (256 epochs of 32 slots).
+pub const SLOTS_PER_SYNC_COMMITTEE_PERIOD: u64 = 8_192;
+/// Signature domain type of sync committee messages.
+const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [7, 0, 0, 0];
+/// Depth and index of `finalized_checkpoint.root` in the beacon state (generalized index 105).
+const FINALIZED_ROOT_DEPTH: usize = 6;
+const FINALIZED_ROOT_INDEX: u64 = 41;
+/// Depth and index of `next_sync_committee` in the beacon state (generalized index 55).
+const NEXT_SYNC_COMMITTEE_DEPTH: usize = 5;
+const NEXT_SYNC_COMMITTEE_INDEX: u64 = 23;
+/// Depth and index of `execution_payload.state_root` in a Deneb beacon block body (generalized index 802).
+const EXECUTION_STATE_ROOT_DEPTH: usize = 9;
+const EXECUTION_STATE_ROOT_INDEX: u64 = 290;
+
+/// BLS12-381 public key.
+pub type BlsPublicKey = [u8; 48];
+/// BLS12-381 signature.
+pub type BlsSignature = [u8; 96];
+
+/// Verification of BLS12-381 signatures, provided by the runtime (e.g., through a host function).
+pub trait BlsVerifier {
+    /// Checks `signature` as the aggregate signature of `public_keys` over `message`.
+    fn fast_aggregate_verify(public_keys: &[BlsPublicKey], message: &H256, signature: &BlsSignature) -> bool;
+}
+
+/// No BLS support: every signature is rejected.
+impl BlsVerifier for () {
+    fn fast_aggregate_verify(_public_keys: &[BlsPublicKey], _message: &H256, _signature: &BlsSignature) -> bool {
+        false
+    }
+}
+
+/// Header of a beacon chain block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct BeaconBlockHeader {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub parent_root: H256,
+    pub state_root: H256,
+    pub body_root: H256,
+}
+
+impl BeaconBlockHeader {
+    /// SSZ hash tree root of the header, i.e., the beacon block root.
+    pub fn hash_tree_root(&self) -> H256 {
+        let mut chunks = [[0u8; 32]; 8];
+        chunks[0][..8].copy_from_slice(&self.slot.to_le_bytes());
+        chunks[1][..8].copy_from_slice(&self.proposer_index.to_le_bytes());
+        chunks[2] = self.parent_root.0;
+        chunks[3] = self.state_root.0;
+        chunks[4] = self.body_root.0;
+        H256(merkleize(&chunks))
+    }
+}
+
+/// A sync committee.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SyncCommittee {
+    /// Public keys of the `SYNC_COMMITTEE_SIZE` members.
+    pub pubkeys: Vec<BlsPublicKey>,
+    /// Aggregate of all members' public keys.
+    pub aggregate_pubkey: BlsPublicKey,
+}
+
+impl SyncCommittee {
+    /// SSZ hash tree root of the committee.
+    pub fn hash_tree_root(&self) -> Result<H256, LightClientError> {
+        if self.pubkeys.len() != SYNC_COMMITTEE_SIZE {
+            return Err(LightClientError::Malformed);
+        }
+        let leaves: Vec<[u8; 32]> = self.pubkeys.iter().map(pubkey_root).collect();
+        Ok(H256(hash_pair(&merkleize(&leaves), &pubkey_root(&self.aggregate_pubkey))))
+    }
+}
+
+/// State of a sync committee light client.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SyncCommitteeState {
+    /// Genesis validators root of the beacon chain.
+    pub genesis_validators_root: H256,
+    /// Current fork version, part of the signature domain; updated by governance at each hard fork.
+    pub fork_version: [u8; 4],
+    /// Sync committee of the period of the latest finalized header.
+    pub current_sync_committee: SyncCommittee,
+    /// Sync committee of the following period, once known.
+    pub next_sync_committee: Option<SyncCommittee>,
+    /// Slot of the latest finalized header.
+    pub finalized_slot: u64,
+    /// Bridge contract holding outbound message hashes (see `SyncCommitteeClient::message_hash`).
+    pub bridge_contract: H160,
+    /// Storage slot of the contract's `mapping(uint32 => mapping(uint64 => bytes32))` of message hashes, keyed by
+    /// destination chain and nonce.
+    pub messages_slot: H256,
+    /// Storage slot of the contract's `mapping(uint32 => uint64)` of the next nonce expected from each source chain.
+    pub inbound_nonces_slot: H256,
+}
+
+/// A finality update of the beacon chain, as served by the light-client API.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SyncCommitteeUpdate {
+    /// Header signed by the sync committee.
+    pub attested_header: BeaconBlockHeader,
+    /// Header finalized in the attested state.
+    pub finalized_header: BeaconBlockHeader,
+    /// Branch of `finalized_header` in the attested state.
+    pub finality_branch: Vec<H256>,
+    /// Execution state root of the finalized block.
+    pub execution_state_root: H256,
+    /// Branch of `execution_state_root` in the finalized block body.
+    pub execution_branch: Vec<H256>,
+    /// Next sync committee and its branch in the attested state, when the update carries it.
+    pub next_sync_committee: Option<(SyncCommittee, Vec<H256>)>,
+    /// Participation bits of the signing sync committee (SSZ bitvector).
+    pub sync_committee_bits: Vec<u8>,
+    /// Aggregate signature of the participants over the attested header.
+    pub sync_committee_signature: BlsSignature,
+    /// Slot at which the signature was made.
+    pub signature_slot: u64,
+}
+
+/// Sync committee light client, generic over the BLS implementation.
+pub struct SyncCommitteeClient<B>(PhantomData<B>);
+
+impl<B: BlsVerifier> SyncCommitteeClient<B> {
+    /// Returns the sync committee period of a slot.
+    pub fn period(slot: u64) -> u64 {
+        slot / SLOTS_PER_SYNC_COMMITTEE_PERIOD
+    }
+
+    /// Returns the root sync committee members sign for `header`.
+    pub fn signing_root(state: &SyncCommitteeState, header: &BeaconBlockHeader) -> H256 {
+        let mut version = [0u8; 32];
+        version[..4].copy_from_slice(&state.fork_version);
+        let fork_data_root = hash_pair(&version, &state.genesis_validators_root.0);
+        let mut domain = [0u8; 32];
+        domain[..4].copy_from_slice(&DOMAIN_SYNC_COMMITTEE);
+        domain[4..].copy_from_slice(&fork_data_root[..28]);
+        H256(hash_pair(&header.hash_tree_root().0, &domain))
+    }
+
+    /// Returns the storage slot holding the hash of message `nonce` to `destination` in the bridge contract.
+    pub fn message_slot(state: &SyncCommitteeState, destination: ChainId, nonce: u64) -> H256 {
+        let mut inner = [0u8; 64];
+        inner[28..32].copy_from_slice(&destination.to_be_bytes());
+        inner[32..].copy_from_slice(&state.messages_slot.0);
+        let mut outer = [0u8; 64];
+        outer[24..32].copy_from_slice(&nonce.to_be_bytes());
+        outer[32..].copy_from_slice(&keccak_256(&inner));
+        H256(keccak_256(&outer))
+    }
+
+    /// Returns the storage slot holding the next nonce the bridge contract expects from `source`.
+    pub fn inbound_nonce_slot(state: &SyncCommitteeState, source: ChainId) -> H256 {
+        let mut key = [0u8; 64];
+        key[28..32].copy_from_slice(&source.to_be_bytes());
+        key[32..].copy_from_slice(&state.inbound_nonces_slot.0);
+        H256(keccak_256(&key))
+    }
+
+    /// Reads storage `slot` of the bridge contract in the execution state of `header`. `proof` is the SCALE encoding
+    /// of `(account_proof, storage_proof)`, the `eth_getProof` node lists of the contract account and of the slot.
+    /// Returns the slot's value as a 32-byte big-endian word, or `None` if the slot is empty.
+    fn read_contract_slot(
+        state: &SyncCommitteeState,
+        header: &FinalizedHeader,
+        slot: &H256,
+        proof: &[u8],
+    ) -> Result<Option<[u8; 32]>, LightClientError> {
+        let (account_proof, storage_proof) =
+            <(Vec<Vec<u8>>, Vec<Vec<u8>>)>::decode(&mut &proof[..]).map_err(|_| LightClientError::Malformed)?;
+        let account = mpt_get(&header.state_root, &keccak_256(state.bridge_contract.as_bytes()), &account_proof)?
+            .ok_or(LightClientError::InvalidProof)?;
+        // Account: [nonce, balance, storage root, code hash].
+        let (is_list, fields, _) = rlp_split(&account)?;
+        let fields = rlp_list_items(fields).filter(|_| is_list).ok_or(LightClientError::Malformed)?;
+        let (_, storage_root, _) = rlp_split(fields.get(2).ok_or(LightClientError::Malformed)?)?;
+        if storage_root.len() != 32 {
+            return Err(LightClientError::Malformed);
+        }
+        let Some(value) = mpt_get(&H256::from_slice(storage_root), &keccak_256(slot.as_bytes()), &storage_proof)? else {
+            return Ok(None);
+        };
+        // Storage values are RLP strings of the big-endian value without leading zeros.
+        let (_, value, _) = rlp_split(&value)?;
+        if value.len() > 32 {
+            return Err(LightClientError::Malformed);
+        }
+        let mut word = [0u8; 32];
+        word[32 - value.len()..].copy_from_slice(value);
+        Ok(Some(word))
+    }
+
+    /// Returns the hash the bridge contract stores for a message: `keccak256(timeout_at ++ payload)`, with
+    /// `timeout_at` as 8 big-endian bytes, so the expiry is committed along with the payload.
+    pub fn message_hash(timeout_at: u64, payload: &[u8]) -> [u8; 32] {
+        let mut data = Vec::with_capacity(8 + payload.len());
+        data.extend_from_slice(&timeout_at.to_be_bytes());
+        data.extend_from_slice(payload);
+        keccak_256(&data)
+    }
+}
+
+impl<B: BlsVerifier> LightClient for SyncCommitteeClient<B> {
+    type State = SyncCommitteeState;
+    type Update = SyncCommitteeUpdate;
+
+    fn verify_update(
+        state: &mut SyncCommitteeState,
+        update: SyncCommitteeUpdate,
+    ) -> Result<FinalizedHeader, LightClientError> {
+        let attested = &update.attested_header;
+        let finalized = &update.finalized_header;
+        if !(update.signature_slot > attested.slot && attested.slot >= finalized.slot) {
+            return Err(LightClientError::Malformed);
+        }
+        if finalized.slot <= state.finalized_slot {
+            return Err(LightClientError::StaleHeader);
+        }
+        let store_period = Self::period(state.finalized_slot);
+        let signature_period = Self::period(update.signature_slot);
+        let committee = if signature_period == store_period {
+            &state.current_sync_committee
+        } else if signature_period == store_period + 1 {
+            state.next_sync_committee.as_ref().ok_or(LightClientError::UnknownSyncCommittee)?
+        } else {
+            return Err(LightClientError::UnknownSyncCommittee);
+        };
+
+        if update.sync_committee_bits.len() != SYNC_COMMITTEE_SIZE / 8 || committee.pubkeys.len() != SYNC_COMMITTEE_SIZE {
+            return Err(LightClientError::Malformed);
+        }
+        let participants: Vec<BlsPublicKey> = committee
+            .pubkeys
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| update.sync_committee_bits[i / 8] >> (i % 8) & 1 == 1)
+            .map(|(_, pubkey)| *pubkey)
+            .collect();
+        // Require a supermajority of the committee, not just the protocol's minimum participation.
+        if participants.len() * 3 < SYNC_COMMITTEE_SIZE * 2 {
+            return Err(LightClientError::InsufficientVotes);
+        }
+
+        let finalized_root = finalized.hash_tree_root();
+        if !is_valid_merkle_branch(
+            &finalized_root,
+            &update.finality_branch,
+            FINALIZED_ROOT_DEPTH,
+            FINALIZED_ROOT_INDEX,
+            &attested.state_root,
+        ) {
+            return Err(LightClientError::InvalidBranch);
+        }
+        if !is_valid_merkle_branch(
+            &update.execution_state_root,
+            &update.execution_branch,
+            EXECUTION_STATE_ROOT_DEPTH,
+            EXECUTION_STATE_ROOT_INDEX,
+            &finalized.body_root,
+        ) {
+            return Err(LightClientError::InvalidBranch);
+        }
+        // The attested state of the store's period carries the committee of the following period.
+        let mut next_sync_committee = None;
+        if let Some((next, branch)) = &update.next_sync_committee {
+            if !is_valid_merkle_branch(
+                &next.hash_tree_root()?,
+                branch,
+                NEXT_SYNC_COMMITTEE_DEPTH,
+                NEXT_SYNC_COMMITTEE_INDEX,
+                &attested.state_root,
+            ) {
+                return Err(LightClientError::InvalidBranch);
+            }
+            if Self::period(attested.slot) == store_period {
+                next_sync_committee = Some(next.clone());
+            }
+        }
+
+        if !B::fast_aggregate_verify(&participants, &Self::signing_root(state, attested), &update.sync_committee_signature) {
+            return Err(LightClientError::BadSignature);
+        }
+
+        if state.next_sync_committee.is_none() {
+            state.next_sync_committee = next_sync_committee;
+        }
+        if Self::period(finalized.slot) > store_period {
+            state.current_sync_committee =
+                state.next_sync_committee.take().ok_or(LightClientError::UnknownSyncCommittee)?;
+        }
+        state.finalized_slot = finalized.slot;
+        Ok(FinalizedHeader { number: finalized.slot, hash: finalized_root, state_root: update.execution_state_root })
+    }
+
+    /// `proof` is the `eth_getProof` proof of the message slot (see `read_contract_slot`). The slot must hold
+    /// `message_hash(timeout_at, payload)`.
+    fn verify_message(
+        state: &SyncCommitteeState,
+        header: &FinalizedHeader,
+        destination: ChainId,
+        nonce: u64,
+        payload: &[u8],
+        timeout_at: u64,
+        proof: &[u8],
+    ) -> Result<(), LightClientError> {
+        let slot = Self::message_slot(state, destination, nonce);
+        let committed = Self::read_contract_slot(state, header, &slot, proof)?.ok_or(LightClientError::MessageNotFound)?;
+        if committed != Self::message_hash(timeout_at, payload) {
+            return Err(LightClientError::PayloadMismatch);
+        }
+        Ok(())
+    }
+
+    /// `proof` is the `eth_getProof` proof of the `source` entry of the contract's inbound nonces (see
+    /// `read_contract_slot`).
+    fn verify_inbound_nonce(
+        state: &SyncCommitteeState,
+        header: &FinalizedHeader,
+        source: ChainId,
+        proof: &[u8],
+    ) -> Result<u64, LightClientError> {
+        let slot = Self::inbound_nonce_slot(state, source);
+        let Some(word) = Self::read_contract_slot(state, header, &slot, proof)? else {
+            return Ok(0);
+        };
+        if word[..24].iter().any(|byte| *byte != 0) {
+            return Err(LightClientError::Malformed);
+        }
+        let mut nonce = [0u8; 8];
+        nonce.copy_from_slice(&word[24..]);
+        Ok(u64::from_be_bytes(nonce))
+    }
+}
+
+// --- SSZ Merkleization ---
+
+/// SHA-256 of two concatenated chunks.
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(left);
+    data[32..].copy_from_slice(right);
+    sha2_256(&data)
+}
+
+/// SSZ hash tree root of a BLS public key (two chunks).
+fn pubkey_root(pubkey: &BlsPublicKey) -> [u8; 32] {
+    let mut data = [0u8; 64];
+    data[..48].copy_from_slice(pubkey);
+    sha2_256(&data)
+}
+
+/// Merkle root of a power-of-two number of chunks.
+fn merkleize(chunks: &[[u8; 32]]) -> [u8; 32] {
+    let mut layer = chunks.to_vec();
+    while layer.len() > 1 {
+        layer = layer.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+    }
+    layer.first().copied().unwrap_or_default()
+}
+
+/// Checks that `leaf` sits at `index` of a tree of `depth` with root `root`, as in the consensus specs.
+pub fn is_valid_merkle_branch(leaf: &H256, branch: &[H256], depth: usize, index: u64, root: &H256) -> bool {
+    if branch.len() != depth {
+        return false;
+    }
+    let mut value = leaf.0;
+    for (i, node) in branch.iter().enumerate() {
+        value = if (index >> i) & 1 == 1 { hash_pair(&node.0, &value) } else { hash_pair(&value, &node.0) };
+    }
+    value == root.0
+}
+
+// --- Merkle-Patricia proofs ---
+
+/// Splits the first RLP item off `data`: (is list, payload, remaining bytes).
+pub fn rlp_split(data: &[u8]) -> Result<(bool, &[u8], &[u8]), LightClientError> {
+    let (&prefix, tail) = data.split_first().ok_or(LightClientError::Malformed)?;
+    let (is_list, offset, len) = match prefix {
+        0x00..=0x7f => return Ok((false, &data[..1], tail)),
+        0x80..=0xb7 => (false, 0, (prefix - 0x80) as usize),
+        0xb8..=0xbf => (false, (prefix - 0xb7) as usize, rlp_length(tail, (prefix - 0xb7) as usize)?),
+        0xc0..=0xf7 => (true, 0, (prefix - 0xc0) as usize),
+        _ => (true, (prefix - 0xf7) as usize, rlp_length(tail, (prefix - 0xf7) as usize)?),
+    };
+    let end = offset.checked_add(len).ok_or(LightClientError::Malformed)?;
+    if end > tail.len() {
+        return Err(LightClientError::Malformed);
+    }
+    Ok((is_list, &tail[offset..end], &tail[end..]))
+}
+
+/// Reads a big-endian RLP length of `bytes` bytes.
+fn rlp_length(data: &[u8], bytes: usize) -> Result<usize, LightClientError> {
+    if bytes > 8 || data.len() < bytes {
+        return Err(LightClientError::Malformed);
+    }
+    let len = data[..bytes].iter().fold(0u64, |len, &byte| (len << 8) | byte as u64);
+    usize::try_from(len).map_err(|_| LightClientError::Malformed)
+}
+
+/// Returns the raw encodings of the items of an RLP list payload.
+fn rlp_list_items(mut payload: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let (_, _, rest) = rlp_split(payload).ok()?;
+        items.push(&payload[..payload.len() - rest.len()]);
+        payload = rest;
+    }
+    Some(items)
+}
+
+/// Decodes a hex-prefix encoded path: (is leaf, nibbles).
+pub fn decode_hex_prefix(encoded: &[u8]) -> Result<(bool, Vec<u8>), LightClientError> {
+    let (&first, rest) = encoded.split_first().ok_or(LightClientError::Malformed)?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(LightClientError::Malformed);
+    }
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+    Ok((flag >= 2, nibbles))
+}
+
+/// Resolves a child reference of a trie node: an empty reference, a node embedded in its parent, or the hash of a
+/// node found in `proof`.
+fn resolve_node(reference: &[u8], proof: &[Vec<u8>]) -> Result<Option<Vec<u8>>, LightClientError> {
+    let (is_list, payload, _) = rlp_split(reference)?;
+    if is_list {
+        return Ok(Some(reference.to_vec()));
+    }
+    match payload.len() {
+        0 => Ok(None),
+        32 => proof
+            .iter()
+            .find(|node| keccak_256(node)[..] == *payload)
+            .cloned()
+            .map(Some)
+            .ok_or(LightClientError::InvalidProof),
+        _ => Err(LightClientError::Malformed),
+    }
+}
+
+/// Looks up `path` in the Merkle-Patricia trie with root `root`, using the nodes in `proof`. Returns `None` if the
+/// proof shows the path is absent.
+pub fn mpt_get(root: &H256, path: &[u8; 32], proof: &[Vec<u8>]) -> Result<Option<Vec<u8>>, LightClientError> {
+    let nibbles: Vec<u8> = path.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect();
+    let mut position = 0;
+    let mut node = proof
+        .iter()
+        .find(|node| keccak_256(node) == root.0)
+        .cloned()
+        .ok_or(LightClientError::InvalidProof)?;
+    loop {
+        let (is_list, payload, _) = rlp_split(&node)?;
+        let items = rlp_list_items(payload).filter(|_| is_list).ok_or(LightClientError::Malformed)?;
+        let reference = match items.len() {
+            // Branch node; values are never stored in branches for fixed-length paths.
+            17 => {
+                let nibble = *nibbles.get(position).ok_or(LightClientError::Malformed)?;
+                position += 1;
+                items[nibble as usize]
+            }
+            // Leaf or extension node.
+            2 => {
+                let (_, encoded_path, _) = rlp_split(items[0])?;
+                let (is_leaf, partial) = decode_hex_prefix(encoded_path)?;
+                if !nibbles[position..].starts_with(&partial) {
+                    return Ok(None);
+                }
+                position += partial.len();
+                if is_leaf {
+                    if position != nibbles.len() {
+                        return Ok(None);
+                    }
+                    let (_, value, _) = rlp_split(items[1])?;
+                    return Ok(Some(value.to_vec()));
+                }
+                items[1]
+            }
+            _ => return Err(LightClientError::Malformed),
+        };
+        match resolve_node(reference, proof)? {
+            Some(next) => node = next,
+            None => return Ok(None),
+        }
+    }
+}
@@ -9,10 +9,34 @@
 //! remains adaptive and secure as requirements evolve.
 //!
 //! ## Key Advanced Features:
+//! - **Device Registry:**  
+//!   Owners register devices with an sr25519, ed25519 or ECDSA public key and metadata, and can rotate or revoke keys.
 //! - **Secure Data Submission:**  
-//!   Receives IoT data with payloads and device identifiers, ensuring that each submission is verified via cryptographic signature checks.
+//!   Receives IoT data with payloads and device identifiers; each submission must carry a signature by the device's
+//!   registered key over the payload, device ID and nonce.
+//! - **Replay Protection:**  
+//!   Each device signs readings with a strictly increasing nonce, so stale or replayed readings are rejected, and
+//!   message IDs are allocated on-chain so existing records can never be overwritten.
+//! - **Spam Resistance:**  
+//!   Readings may be submitted as signed transactions or as unsigned transactions authorised by the device signature
+//!   alone (validated in `ValidateUnsigned`). Registering a device reserves a deposit from its owner, and each device
+//!   may submit at most one reading per `MinSubmissionInterval` blocks.
+//! - **Schema-Validated Payloads:**  
+//!   A device may declare a payload schema registered in `nodara_standards`; its payloads are decoded and
+//!   range-checked against that schema, and malformed payloads are rejected.
+//! - **Windowed Aggregation:**  
+//!   Typed numeric readings are folded into per-device, per-metric aggregates (count, min, max, mean, last) over
+//!   `AggregationWindow`-block windows. Aggregates are kept long-term; raw records are pruned after `RetentionPeriod`.
+//! - **Anomaly Flagging and Quarantine:**  
+//!   Owners set per-metric plausibility rules (range limits, maximum rate of change, stuck-value detection). Readings
+//!   breaking them are flagged with an event, kept out of the aggregates and reported against the owner's
+//!   `IotDataQuality` reputation; devices with repeated anomalies are quarantined until their owner releases them.
+//! - **Data Subscriptions:**  
+//!   Device owners list encrypted data streams with a per-period price. Subscribers escrow each period's price; the
+//!   escrow is released to the owner when the owner delivers the period's stream key, encrypted to the subscriber's
+//!   key, and refunded if the subscription is cancelled before delivery.
 //! - **Immutable Audit Logging:**  
-//!   Every data submission is recorded with a timestamp, device ID, payload, and signature for full transparency.
+//!   Every data submission emits an event, and configuration updates are logged on-chain for full transparency.
 //! - **DAO Governance Integration:**  
 //!   Allows for on-chain proposals to update parameters such as maximum payload length and data timeout values.
 //! - **Performance Optimizations:**  
@@ -22,12 +46,37 @@
 //!
 //! ## Module Structure:
 //! - **Storage:**
-//!   - `IotData`: Maps unique message IDs to IoT data records.
-//!   - `IotHistory`: Logs each IoT data event (timestamp, message ID, device ID, payload, signature).
+//!   - `Devices`: Maps device IDs to their owner, public key, metadata and status.
+//!   - `DeviceNonces`: The lowest nonce each device may use next.
+//!   - `NextIotId`: The ID assigned to the next accepted IoT message.
+//!   - `LastSubmission`: The block of each device's most recent accepted reading.
+//!   - `IotData`: Maps unique message IDs to raw IoT data records, pruned after `RetentionPeriod`.
+//!   - `PruneCursor`: The oldest message ID that may still have a raw record.
+//!   - `Aggregates`: Per-device, per-metric aggregates for each window.
+//!   - `PlausibilityRules` / `LastValues`: Per-device, per-metric plausibility rules and the last plausible values.
+//!   - `PendingBaselines`: Runs of consistent readings that jumped away from the last plausible value.
+//!   - `DeviceHealthOf`: Each device's anomaly score and quarantine status.
+//!   - `IotHistory`: Logs configuration updates (timestamp, 0, tag, details, new configuration).
+//!   - `Listings` / `NextListingId`: Data stream listings.
+//!   - `Subscriptions`: Each subscriber's current period and escrow per listing.
+//!   - `KeyDeliveries`: The latest stream key delivered to each subscriber, encrypted to the subscriber's key.
 //! - **Core Functions:**
+//!   - `register_device` / `rotate_device_key` / `revoke_device`: Manage registered devices (owner only); registering
+//!     reserves `DeviceDeposit`, revoking returns it.
 //!   - `submit_iot_data`: Verifies and records IoT data from off-chain sources.
-//!   - `update_config`: Updates configuration parameters via DAO governance.
-//!   - `verify_data`: (Internal) Simulates cryptographic signature verification for IoT data.
+//!   - `signing_payload`: Returns the bytes a device signs for a submission.
+//!   - `decode_readings`: Decodes a payload into typed numeric readings, using the device's schema if it has one.
+//!   - `set_plausibility_rule`: Configures anomaly screening (owner only; a quarantined device's rules can only be
+//!     tightened).
+//!   - `release_quarantine`: Lifts a device's quarantine (`GovernanceOrigin` only).
+//!   - `check_plausibility`: Checks a value against a plausibility rule.
+//!   - `on_initialize`: Prunes raw records older than `RetentionPeriod`.
+//!   - `update_config`: Updates configuration parameters (`GovernanceOrigin` only).
+//!   - `create_listing` / `close_listing`: List or unlist a device's data stream (owner only).
+//!   - `subscribe` / `renew_subscription` / `cancel_subscription`: Pay for, extend or leave a subscription.
+//!   - `deliver_key`: Delivers a period's stream key to a paying subscriber and releases the escrow.
+//!   - `check_submission`: (Internal) Runs every check a reading must pass; shared by dispatch and `ValidateUnsigned`.
+//!   - `verify_data`: (Internal) Verifies a submission's signature against the device's registered key.
 //!   - `current_timestamp`: Returns the current Unix timestamp (placeholder).
 //!
 //! ## Version:
@@ -36,42 +85,307 @@
 //! ## Future Enhancements:
 //! - Integration with hardware-based cryptographic accelerators.
 //! - Advanced parallel offchain processing for bulk data verification.
-//! - Incorporation of real-time anomaly detection using AI models.
+//! - Statistical anomaly detection across devices measuring the same environment.
 
-use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::Get};
+use frame_support::{
+    dispatch::DispatchResult,
+    pallet_prelude::*,
+    traits::{BalanceStatus, Currency, EnsureOrigin, Get, ReservableCurrency},
+};
+use codec::DecodeAll;
 use frame_system::pallet_prelude::*;
+use sp_runtime::{
+    traits::{IdentifyAccount, Saturating, UniqueSaturatedInto, Verify, Zero},
+    MultiSignature, MultiSigner,
+};
+use nodara_reputation::{ReputationCategory, ReputationReporter};
+use nodara_standards::{pallet::PayloadError, SchemaProvider};
 use sp_std::vec::Vec;
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
 
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// A registered IoT device.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct DeviceInfo<AccountId, Balance> {
+        /// Account that registered and manages the device.
+        pub owner: AccountId,
+        /// Public key (sr25519, ed25519 or ECDSA) the device signs its readings with.
+        pub key: MultiSigner,
+        /// Device metadata (e.g., model, firmware, location).
+        pub metadata: Vec<u8>,
+        /// ID of the `nodara_standards` standard whose payload schema the device's payloads follow, if any.
+        pub schema: Option<Vec<u8>>,
+        /// Whether the device may submit data; revoked devices are kept so their ID cannot be reused.
+        pub active: bool,
+        /// Deposit reserved from the owner while the device is active.
+        pub deposit: Balance,
+    }
+
+    /// Identifier of a metric reported by a device (e.g., 0 = temperature, 1 = humidity).
+    pub type MetricId = u32;
+
+    /// A typed numeric reading. Payloads of devices with a schema yield one reading per schema field (the metric
+    /// being the field index); payloads of other devices holding the SCALE encoding of `Vec<Reading>` are used as is.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Reading {
+        /// Metric the value belongs to.
+        pub metric: MetricId,
+        /// Reading value, in the metric's fixed-point unit.
+        pub value: i64,
+    }
+
+    /// Aggregate of one device's readings of one metric over one window.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Aggregate {
+        /// Number of readings.
+        pub count: u32,
+        /// Smallest value.
+        pub min: i64,
+        /// Largest value.
+        pub max: i64,
+        /// Sum of all values, used to derive the mean.
+        pub sum: i128,
+        /// Most recent value.
+        pub last: i64,
+    }
+
+    impl Aggregate {
+        /// Starts an aggregate from its first value.
+        pub fn new(value: i64) -> Self {
+            Aggregate { count: 1, min: value, max: value, sum: value as i128, last: value }
+        }
+
+        /// Folds another value into the aggregate.
+        pub fn add(&mut self, value: i64) {
+            self.count = self.count.saturating_add(1);
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+            self.sum = self.sum.saturating_add(value as i128);
+            self.last = value;
+        }
+
+        /// Mean of the aggregated values, rounded towards zero.
+        pub fn mean(&self) -> i64 {
+            if self.count == 0 {
+                return 0;
+            }
+            (self.sum / self.count as i128) as i64
+        }
+    }
+
+    /// Plausibility rule for one metric of a device. Readings breaking the rule are flagged as anomalies and kept out
+    /// of the aggregates.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PlausibilityRule {
+        /// Smallest plausible value.
+        pub min: Option<i64>,
+        /// Largest plausible value.
+        pub max: Option<i64>,
+        /// Largest plausible absolute change from the previous plausible value (or, while re-baselining, from the
+        /// previous reading).
+        pub max_delta: Option<i64>,
+        /// Number of consecutive repeats of the same value tolerated before the value is considered stuck.
+        pub max_repeats: Option<u32>,
+    }
+
+    impl PlausibilityRule {
+        /// Whether every limit of `self` is at least as strict as the same limit of `other`. A limit `other` sets
+        /// must also be set by `self`.
+        pub fn is_at_least_as_strict_as(&self, other: &PlausibilityRule) -> bool {
+            fn tighter<V: Ord + Copy>(new: Option<V>, old: Option<V>, stricter: fn(V, V) -> bool) -> bool {
+                match (new, old) {
+                    (_, None) => true,
+                    (None, Some(_)) => false,
+                    (Some(new), Some(old)) => stricter(new, old),
+                }
+            }
+            tighter(self.min, other.min, |new, old| new >= old)
+                && tighter(self.max, other.max, |new, old| new <= old)
+                && tighter(self.max_delta, other.max_delta, |new, old| new <= old)
+                && tighter(self.max_repeats, other.max_repeats, |new, old| new <= old)
+        }
+    }
+
+    /// Kind of rule an anomalous reading breaks.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum AnomalyKind {
+        /// The value lies outside `[min, max]`.
+        OutOfRange,
+        /// The value changed by more than `max_delta` since the previous plausible value.
+        RateOfChange,
+        /// The same value was repeated more than `max_repeats` times in a row.
+        Repeated,
+    }
+
+    /// Anomaly tracking of a device.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct DeviceHealth {
+        /// Anomaly score: raised by each submission with an anomalous reading, lowered by each clean one.
+        pub anomalies: u32,
+        /// Whether the device is quarantined; quarantined devices cannot submit readings.
+        pub quarantined: bool,
+    }
+
+    /// Identifier of a data stream listing.
+    pub type ListingId = u64;
+
+    /// A device's data stream offered for subscription. Payloads of listed devices are encrypted with a stream key
+    /// that only subscribers receive.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct DataListing<AccountId, Balance, BlockNumber> {
+        /// Owner of the device, paid by subscribers.
+        pub owner: AccountId,
+        /// Device whose stream is listed.
+        pub device_id: Vec<u8>,
+        /// Price of one subscription period.
+        pub price: Balance,
+        /// Length of a subscription period, in blocks.
+        pub period: BlockNumber,
+        /// Whether new subscriptions and renewals are accepted.
+        pub active: bool,
+    }
+
+    /// A subscriber's current period on a listing.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Subscription<Balance, BlockNumber> {
+        /// x25519 public key the stream keys are encrypted to.
+        pub encryption_key: [u8; 32],
+        /// First block of the current period.
+        pub period_start: BlockNumber,
+        /// First block after the current period.
+        pub period_end: BlockNumber,
+        /// Price reserved from the subscriber for the current period; zero once released to the owner.
+        pub escrow: Balance,
+    }
+
     /// Structure representing an IoT data record.
     #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-    pub struct IotRecord {
+    pub struct IotRecord<BlockNumber> {
         /// Unique identifier for the IoT message.
         pub id: u64,
         /// Data payload from the IoT device.
         pub payload: Vec<u8>,
         /// Device identifier (e.g., MAC address, serial number).
         pub device_id: Vec<u8>,
+        /// Nonce included in the signed message.
+        pub nonce: u64,
         /// Timestamp of data submission.
         pub timestamp: u64,
-        /// Cryptographic signature verifying the data.
-        pub signature: Vec<u8>,
+        /// Block in which the record was accepted; the record is pruned `RetentionPeriod` blocks later.
+        pub block: BlockNumber,
+        /// Device signature over the payload, device ID and nonce.
+        pub signature: MultiSignature,
     }
 
     /// Main pallet structure for the IoT bridge.
     #[frame_support::pallet]
     pub struct Pallet<T>(_);
 
-    /// Storage mapping for IoT data records.
+    /// Registered devices, keyed by device ID.
+    #[pallet::storage]
+    #[pallet::getter(fn devices)]
+    pub type Devices<T: Config> =
+        StorageMap<_, Blake2_128Concat, Vec<u8>, DeviceInfo<T::AccountId, BalanceOf<T>>, OptionQuery>;
+
+    /// The lowest nonce each device may sign its next reading with.
+    #[pallet::storage]
+    #[pallet::getter(fn device_nonces)]
+    pub type DeviceNonces<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, u64, ValueQuery>;
+
+    /// The ID assigned to the next accepted IoT message.
+    #[pallet::storage]
+    #[pallet::getter(fn next_iot_id)]
+    pub type NextIotId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// The block of each device's most recent accepted reading.
+    #[pallet::storage]
+    #[pallet::getter(fn last_submission)]
+    pub type LastSubmission<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, T::BlockNumber, OptionQuery>;
+
+    /// Storage mapping for raw IoT data records, pruned after `RetentionPeriod`.
     #[pallet::storage]
     #[pallet::getter(fn iot_data)]
-    pub type IotData<T: Config> = StorageMap<_, Blake2_128Concat, u64, IotRecord, OptionQuery>;
+    pub type IotData<T: Config> = StorageMap<_, Blake2_128Concat, u64, IotRecord<T::BlockNumber>, OptionQuery>;
+
+    /// The oldest message ID that may still have a raw record; IDs are allocated in block order.
+    #[pallet::storage]
+    #[pallet::getter(fn prune_cursor)]
+    pub type PruneCursor<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Aggregates keyed by (device ID, metric) and window index (block number / `AggregationWindow`).
+    #[pallet::storage]
+    #[pallet::getter(fn aggregates)]
+    pub type Aggregates<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (Vec<u8>, MetricId),
+        Twox64Concat,
+        T::BlockNumber,
+        Aggregate,
+        OptionQuery,
+    >;
 
-    /// Storage for logging IoT data events.
-    /// Each log entry: (timestamp, message ID, device ID, payload, signature)
+    /// Plausibility rules, keyed by device ID and metric.
+    #[pallet::storage]
+    #[pallet::getter(fn plausibility_rules)]
+    pub type PlausibilityRules<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, Vec<u8>, Twox64Concat, MetricId, PlausibilityRule, OptionQuery>;
+
+    /// Last plausible value of each ruled metric and how many times in a row it has been repeated since.
+    #[pallet::storage]
+    #[pallet::getter(fn last_values)]
+    pub type LastValues<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, Vec<u8>, Twox64Concat, MetricId, (i64, u32), OptionQuery>;
+
+    /// Latest reading of each ruled metric rejected for its rate of change, and how many consecutive rejected
+    /// readings, each within `max_delta` of the one before, led up to it. A long enough run becomes the new baseline.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_baselines)]
+    pub type PendingBaselines<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, Vec<u8>, Twox64Concat, MetricId, (i64, u32), OptionQuery>;
+
+    /// Anomaly score and quarantine status of each device.
+    #[pallet::storage]
+    #[pallet::getter(fn device_health)]
+    pub type DeviceHealthOf<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, DeviceHealth, ValueQuery>;
+
+    /// Data stream listings.
+    #[pallet::storage]
+    #[pallet::getter(fn listings)]
+    pub type Listings<T: Config> =
+        StorageMap<_, Blake2_128Concat, ListingId, DataListing<T::AccountId, BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+    /// The ID assigned to the next listing.
+    #[pallet::storage]
+    #[pallet::getter(fn next_listing_id)]
+    pub type NextListingId<T: Config> = StorageValue<_, ListingId, ValueQuery>;
+
+    /// Subscriptions, keyed by listing and subscriber.
+    #[pallet::storage]
+    #[pallet::getter(fn subscriptions)]
+    pub type Subscriptions<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        ListingId,
+        Blake2_128Concat,
+        T::AccountId,
+        Subscription<BalanceOf<T>, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Latest stream key delivered to each subscriber: (period start, key encrypted to the subscriber's key).
+    #[pallet::storage]
+    #[pallet::getter(fn key_deliveries)]
+    pub type KeyDeliveries<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, ListingId, Blake2_128Concat, T::AccountId, (T::BlockNumber, Vec<u8>), OptionQuery>;
+
+    /// Storage for logging configuration updates.
+    /// Each log entry: (timestamp, 0, b"ConfigUpdate", details, new configuration)
     #[pallet::storage]
     #[pallet::getter(fn iot_history)]
     pub type IotHistory<T: Config> = StorageValue<_, Vec<(u64, u64, Vec<u8>, Vec<u8>, Vec<u8>)>, ValueQuery>;
@@ -86,6 +400,51 @@ pub mod pallet {
         /// Base timeout (in seconds) for data validity.
         #[pallet::constant]
         type BaseTimeout: Get<u64>;
+        /// Maximum length of a device ID and of device metadata.
+        #[pallet::constant]
+        type MaxDeviceMetadataLength: Get<u32>;
+        /// Currency used for device deposits.
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// Origin allowed to update the bridge configuration and release quarantined devices.
+        type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+        /// Deposit reserved from the owner for each registered device.
+        #[pallet::constant]
+        type DeviceDeposit: Get<BalanceOf<Self>>;
+        /// Minimum number of blocks between two accepted readings of the same device.
+        #[pallet::constant]
+        type MinSubmissionInterval: Get<Self::BlockNumber>;
+        /// Transaction pool priority of unsigned reading submissions.
+        #[pallet::constant]
+        type UnsignedPriority: Get<TransactionPriority>;
+        /// Length, in blocks, of an aggregation window.
+        #[pallet::constant]
+        type AggregationWindow: Get<Self::BlockNumber>;
+        /// Number of blocks raw records are kept before being pruned.
+        #[pallet::constant]
+        type RetentionPeriod: Get<Self::BlockNumber>;
+        /// Maximum number of raw records pruned per block.
+        #[pallet::constant]
+        type MaxPrunePerBlock: Get<u32>;
+        /// Source of payload schemas (the standards pallet).
+        type Schemas: SchemaProvider;
+        /// Maximum length of an encrypted stream key.
+        #[pallet::constant]
+        type MaxEncryptedKeyLength: Get<u32>;
+        /// Reputation pallet anomalies are reported to, against the device owner's `IotDataQuality` score.
+        type Reputation: ReputationReporter<Self::AccountId>;
+        /// Account the bridge reports anomalies as; it must be an authorized reporter of the reputation pallet.
+        #[pallet::constant]
+        type ReporterAccount: Get<Self::AccountId>;
+        /// Reputation penalty reported for each submission with an anomalous reading.
+        #[pallet::constant]
+        type AnomalyPenalty: Get<u32>;
+        /// Anomaly score at which a device is quarantined.
+        #[pallet::constant]
+        type QuarantineThreshold: Get<u32>;
+        /// Number of consecutive readings, each within `max_delta` of the previous one, after which a value that
+        /// jumped away from the last plausible value is accepted as the new baseline; zero disables re-baselining.
+        #[pallet::constant]
+        type RebaselineAfter: Get<u32>;
     }
 
     #[pallet::event]
@@ -95,6 +454,31 @@ pub mod pallet {
         IotDataSubmitted(u64, Vec<u8>),
         /// Emitted when IoT bridge configuration is updated.
         ConfigUpdated(Vec<u8>, Vec<u8>),
+        /// Emitted when a device is registered: (device ID, owner).
+        DeviceRegistered(Vec<u8>, T::AccountId),
+        /// Emitted when a device's key is rotated: (device ID).
+        DeviceKeyRotated(Vec<u8>),
+        /// Emitted when a device is revoked: (device ID).
+        DeviceRevoked(Vec<u8>),
+        /// Emitted when a data stream is listed: (listing ID, device ID, owner).
+        ListingCreated(ListingId, Vec<u8>, T::AccountId),
+        /// Emitted when a listing stops accepting subscriptions: (listing ID).
+        ListingClosed(ListingId),
+        /// Emitted when a subscription period is paid into escrow: (listing ID, subscriber, period end).
+        Subscribed(ListingId, T::AccountId, T::BlockNumber),
+        /// Emitted when a stream key is delivered and the escrow released: (listing ID, subscriber, amount actually
+        /// transferred to the owner).
+        KeyDelivered(ListingId, T::AccountId, BalanceOf<T>),
+        /// Emitted when a subscription is cancelled: (listing ID, subscriber, amount refunded).
+        SubscriptionCancelled(ListingId, T::AccountId, BalanceOf<T>),
+        /// Emitted when a plausibility rule is set or removed: (device ID, metric, old rule, new rule).
+        PlausibilityRuleSet(Vec<u8>, MetricId, Option<PlausibilityRule>, Option<PlausibilityRule>),
+        /// Emitted when a reading breaks its plausibility rule: (device ID, message ID, metric, value, kind).
+        AnomalyDetected(Vec<u8>, u64, MetricId, i64, AnomalyKind),
+        /// Emitted when a device is quarantined after repeated anomalies: (device ID).
+        DeviceQuarantined(Vec<u8>),
+        /// Emitted when a device is released from quarantine: (device ID).
+        QuarantineReleased(Vec<u8>),
     }
 
     #[pallet::error]
@@ -107,37 +491,209 @@ pub mod pallet {
         InvalidDeviceId,
         /// Data processing error.
         DataProcessingError,
+        /// A device with this ID is already registered.
+        DeviceAlreadyRegistered,
+        /// No device is registered with this ID.
+        DeviceNotFound,
+        /// The caller does not own the device.
+        NotDeviceOwner,
+        /// The device has been revoked.
+        DeviceRevoked,
+        /// The device ID or metadata exceeds `MaxDeviceMetadataLength`.
+        MetadataTooLong,
+        /// The nonce is lower than the device's next expected nonce (stale or replayed reading).
+        InvalidNonce,
+        /// No more message IDs can be allocated.
+        IdOverflow,
+        /// The device submitted a reading less than `MinSubmissionInterval` blocks ago.
+        RateLimited,
+        /// The owner cannot reserve the device deposit.
+        InsufficientBalance,
+        /// No payload schema is registered under the given standard ID.
+        UnknownSchema,
+        /// The payload does not decode according to the device's schema.
+        MalformedPayload,
+        /// A payload value lies outside the range allowed by the device's schema.
+        ValueOutOfRange,
+        /// No listing exists with this ID.
+        ListingNotFound,
+        /// The caller does not own the listing.
+        NotListingOwner,
+        /// The listing no longer accepts subscriptions or renewals.
+        ListingClosed,
+        /// The subscription period must be at least one block.
+        InvalidPeriod,
+        /// A listing must charge a non-zero price per period.
+        ZeroPrice,
+        /// Devices with a payload schema publish plaintext readings and cannot be listed.
+        SchemaDeviceNotListable,
+        /// The caller already subscribes to the listing.
+        AlreadySubscribed,
+        /// The account does not subscribe to the listing.
+        NotSubscribed,
+        /// The current period's key has not been delivered yet, or was already delivered.
+        PeriodNotSettled,
+        /// The encrypted key exceeds `MaxEncryptedKeyLength` or is empty.
+        InvalidEncryptedKey,
+        /// The device is quarantined after repeated anomalies.
+        DeviceQuarantined,
+        /// The device is not quarantined.
+        NotQuarantined,
+        /// The plausibility rule's `min` exceeds its `max`, or `max_delta` is negative.
+        InvalidRule,
+        /// The rule of a quarantined device cannot be removed or loosened.
+        RuleLoosenedWhileQuarantined,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let pruned = Self::prune_records(n);
+            T::DbWeight::get().reads_writes(pruned as u64 + 2, pruned as u64 + 1)
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        /// Accepts unsigned readings that carry a valid device signature and would pass every on-chain check.
+        /// Only one unsigned reading per device is kept in the pool at a time.
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            let Call::submit_iot_data { payload, device_id, nonce, signature } = call else {
+                return InvalidTransaction::Call.into();
+            };
+            Self::check_submission(payload, device_id, *nonce, signature).map_err(|error| match error {
+                Error::<T>::InvalidNonce => InvalidTransaction::Stale,
+                Error::<T>::DataVerificationFailed => InvalidTransaction::BadProof,
+                Error::<T>::RateLimited => InvalidTransaction::ExhaustsResources,
+                _ => InvalidTransaction::Call,
+            })?;
+            let longevity: u64 = T::MinSubmissionInterval::get().unique_saturated_into();
+            ValidTransaction::with_tag_prefix("NodaraIoTBridge")
+                .priority(T::UnsignedPriority::get())
+                .and_provides(device_id)
+                .longevity(longevity.max(1))
+                .propagate(true)
+                .build()
+        }
     }
 
+    #[pallet::call]
     impl<T: Config> Pallet<T> {
+        /// Registers a device owned by the caller, reserving `DeviceDeposit`.
+        ///
+        /// # Parameters:
+        /// - `device_id`: Identifier of the device (e.g., MAC address, serial number).
+        /// - `key`: Public key the device signs its readings with.
+        /// - `metadata`: Device metadata.
+        /// - `schema`: Standard ID of the payload schema the device's payloads follow, if any.
+        ///
+        /// # Requirements:
+        /// - `device_id` must be non-empty and unused; `device_id` and `metadata` must fit `MaxDeviceMetadataLength`.
+        /// - `schema`, if given, must name a standard with a payload schema.
+        /// - The caller must be able to reserve `DeviceDeposit`.
+        #[pallet::call_index(0)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn register_device(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            key: MultiSigner,
+            metadata: Vec<u8>,
+            schema: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            ensure!(!device_id.is_empty(), Error::<T>::InvalidDeviceId);
+            let max = T::MaxDeviceMetadataLength::get() as usize;
+            ensure!(device_id.len() <= max && metadata.len() <= max, Error::<T>::MetadataTooLong);
+            ensure!(!Devices::<T>::contains_key(&device_id), Error::<T>::DeviceAlreadyRegistered);
+            if let Some(schema) = &schema {
+                ensure!(T::Schemas::payload_schema(schema).is_some(), Error::<T>::UnknownSchema);
+            }
+            let deposit = T::DeviceDeposit::get();
+            T::Currency::reserve(&owner, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+            Devices::<T>::insert(&device_id, DeviceInfo { owner: owner.clone(), key, metadata, schema, active: true, deposit });
+            Self::deposit_event(Event::DeviceRegistered(device_id, owner));
+            Ok(())
+        }
+
+        /// Replaces the key of one of the caller's devices, e.g., after a firmware update or a suspected leak.
+        #[pallet::call_index(1)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn rotate_device_key(origin: OriginFor<T>, device_id: Vec<u8>, new_key: MultiSigner) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Devices::<T>::try_mutate(&device_id, |maybe_device| -> DispatchResult {
+                let device = maybe_device.as_mut().ok_or(Error::<T>::DeviceNotFound)?;
+                ensure!(device.owner == who, Error::<T>::NotDeviceOwner);
+                ensure!(device.active, Error::<T>::DeviceRevoked);
+                device.key = new_key;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::DeviceKeyRotated(device_id));
+            Ok(())
+        }
+
+        /// Permanently revokes one of the caller's devices; its data is no longer accepted and the deposit is returned.
+        #[pallet::call_index(2)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn revoke_device(origin: OriginFor<T>, device_id: Vec<u8>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Devices::<T>::try_mutate(&device_id, |maybe_device| -> DispatchResult {
+                let device = maybe_device.as_mut().ok_or(Error::<T>::DeviceNotFound)?;
+                ensure!(device.owner == who, Error::<T>::NotDeviceOwner);
+                device.active = false;
+                T::Currency::unreserve(&who, device.deposit);
+                device.deposit = Zero::zero();
+                Ok(())
+            })?;
+            Self::deposit_event(Event::DeviceRevoked(device_id));
+            Ok(())
+        }
+
         /// Submits IoT data to the blockchain after verifying its integrity.
         ///
+        /// May be submitted as a signed transaction (e.g., by a gateway paying fees) or as an unsigned transaction,
+        /// in which case the device signature alone authorises it (see `ValidateUnsigned`). The message ID is
+        /// allocated from `NextIotId`. Nonces must strictly increase per device; gaps are allowed so readings lost in
+        /// transit do not block the device. Payloads holding typed readings are screened against the device's
+        /// plausibility rules, and the plausible ones are folded into the current window's aggregates.
+        ///
         /// # Parameters:
-        /// - `id`: Unique message identifier.
+        /// - `origin`: Signed or none.
         /// - `payload`: Data payload from the IoT device.
-        /// - `device_id`: Identifier of the IoT device.
-        /// - `signature`: Cryptographic signature for data verification.
+        /// - `device_id`: Identifier of the registered IoT device.
+        /// - `nonce`: Nonce included in the signed message.
+        /// - `signature`: Device signature over `signing_payload(device_id, nonce, payload)`.
         ///
         /// # Requirements:
         /// - The payload length must not exceed `MaxPayloadLength`.
-        /// - The device identifier must be non-empty.
-        /// - The data must pass the simulated cryptographic verification.
+        /// - The device must be registered, active and not quarantined.
+        /// - The signature must verify against the device's registered key.
+        /// - If the device has a schema, the payload must decode according to it.
+        /// - `nonce` must be at least `DeviceNonces(device_id)`.
+        /// - At least `MinSubmissionInterval` blocks must have passed since the device's previous reading.
+        #[pallet::call_index(3)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
         pub fn submit_iot_data(
-            id: u64,
+            origin: OriginFor<T>,
             payload: Vec<u8>,
             device_id: Vec<u8>,
-            signature: Vec<u8>,
+            nonce: u64,
+            signature: MultiSignature,
         ) -> DispatchResult {
-            ensure!(payload.len() as u32 <= T::MaxPayloadLength::get(), Error::<T>::PayloadTooLong);
-            ensure!(!device_id.is_empty(), Error::<T>::InvalidDeviceId);
-            // Simulate cryptographic verification.
-            ensure!(Self::verify_data(&payload, &signature), Error::<T>::DataVerificationFailed);
+            frame_system::ensure_signed_or_none(origin)?;
+            let readings = Self::check_submission(&payload, &device_id, nonce, &signature)?;
+            LastSubmission::<T>::insert(&device_id, frame_system::Pallet::<T>::block_number());
+            let next_nonce = nonce.checked_add(1).ok_or(Error::<T>::InvalidNonce)?;
+            let id = NextIotId::<T>::get();
+            NextIotId::<T>::put(id.checked_add(1).ok_or(Error::<T>::IdOverflow)?);
+            DeviceNonces::<T>::insert(&device_id, next_nonce);
             let timestamp = Self::current_timestamp();
-            let record = IotRecord { id, payload: payload.clone(), device_id: device_id.clone(), timestamp, signature: signature.clone() };
+            let block = frame_system::Pallet::<T>::block_number();
+            let readings = Self::screen_readings(&device_id, id, readings);
+            Self::aggregate_readings(&device_id, block, &readings);
+            let record = IotRecord { id, payload: payload.clone(), device_id, nonce, timestamp, block, signature };
             <IotData<T>>::insert(id, record);
-            <IotHistory<T>>::mutate(|history| {
-                history.push((timestamp, id, device_id, payload.clone(), signature))
-            });
             Self::deposit_event(Event::IotDataSubmitted(id, payload));
             Ok(())
         }
@@ -145,12 +701,16 @@ pub mod pallet {
         /// Updates the IoT bridge configuration via DAO governance.
         ///
         /// # Parameters:
+        /// - `origin`: Must satisfy `GovernanceOrigin`.
         /// - `new_config`: The new configuration parameters as a byte vector.
         /// - `details`: Additional details or rationale for the update.
         ///
         /// # Requirements:
         /// - The new configuration must be non-empty.
-        pub fn update_config(new_config: Vec<u8>, details: Vec<u8>) -> DispatchResult {
+        #[pallet::call_index(4)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn update_config(origin: OriginFor<T>, new_config: Vec<u8>, details: Vec<u8>) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
             ensure!(!new_config.is_empty(), Error::<T>::DataProcessingError);
             let timestamp = Self::current_timestamp();
             <IotHistory<T>>::mutate(|history| {
@@ -161,10 +721,458 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Simulates cryptographic verification for IoT data.
-        fn verify_data(payload: &Vec<u8>, signature: &Vec<u8>) -> bool {
-            // Simulation: Verify that both payload and signature are non-empty.
-            !payload.is_empty() && !signature.is_empty()
+        /// Lists the data stream of one of the caller's devices.
+        ///
+        /// # Parameters:
+        /// - `device_id`: The device whose stream is listed.
+        /// - `price`: Price of one subscription period.
+        /// - `period`: Length of a subscription period, in blocks.
+        ///
+        /// # Requirements:
+        /// - The caller must own the device, which must be active and have no payload schema.
+        /// - `price` and `period` must be non-zero.
+        #[pallet::call_index(5)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn create_listing(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            price: BalanceOf<T>,
+            period: T::BlockNumber,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            let device = Devices::<T>::get(&device_id).ok_or(Error::<T>::DeviceNotFound)?;
+            ensure!(device.owner == owner, Error::<T>::NotDeviceOwner);
+            ensure!(device.active, Error::<T>::DeviceRevoked);
+            ensure!(device.schema.is_none(), Error::<T>::SchemaDeviceNotListable);
+            ensure!(!price.is_zero(), Error::<T>::ZeroPrice);
+            ensure!(!period.is_zero(), Error::<T>::InvalidPeriod);
+            let id = NextListingId::<T>::get();
+            NextListingId::<T>::put(id.checked_add(1).ok_or(Error::<T>::IdOverflow)?);
+            Listings::<T>::insert(id, DataListing { owner: owner.clone(), device_id: device_id.clone(), price, period, active: true });
+            Self::deposit_event(Event::ListingCreated(id, device_id, owner));
+            Ok(())
+        }
+
+        /// Stops a listing from accepting subscriptions and renewals; current periods still run to completion.
+        #[pallet::call_index(6)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn close_listing(origin: OriginFor<T>, listing_id: ListingId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Listings::<T>::try_mutate(listing_id, |maybe_listing| -> DispatchResult {
+                let listing = maybe_listing.as_mut().ok_or(Error::<T>::ListingNotFound)?;
+                ensure!(listing.owner == who, Error::<T>::NotListingOwner);
+                listing.active = false;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::ListingClosed(listing_id));
+            Ok(())
+        }
+
+        /// Subscribes to a listing, reserving the first period's price in escrow.
+        ///
+        /// # Parameters:
+        /// - `listing_id`: The listing to subscribe to.
+        /// - `encryption_key`: x25519 public key the owner encrypts stream keys to.
+        ///
+        /// # Requirements:
+        /// - The listing must be active and the caller must not already subscribe to it.
+        /// - The caller must be able to reserve the listing price.
+        #[pallet::call_index(7)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn subscribe(origin: OriginFor<T>, listing_id: ListingId, encryption_key: [u8; 32]) -> DispatchResult {
+            let subscriber = ensure_signed(origin)?;
+            let listing = Self::active_listing(listing_id)?;
+            ensure!(!Subscriptions::<T>::contains_key(listing_id, &subscriber), Error::<T>::AlreadySubscribed);
+            T::Currency::reserve(&subscriber, listing.price).map_err(|_| Error::<T>::InsufficientBalance)?;
+            let period_start = frame_system::Pallet::<T>::block_number();
+            let period_end = period_start.saturating_add(listing.period);
+            Subscriptions::<T>::insert(
+                listing_id,
+                &subscriber,
+                Subscription { encryption_key, period_start, period_end, escrow: listing.price },
+            );
+            Self::deposit_event(Event::Subscribed(listing_id, subscriber, period_end));
+            Ok(())
+        }
+
+        /// Pays the next period into escrow. The new period starts when the current one ends, or now if it has
+        /// already ended.
+        ///
+        /// # Requirements:
+        /// - The listing must be active and the current period's key must have been delivered.
+        /// - The caller must be able to reserve the listing price.
+        #[pallet::call_index(8)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn renew_subscription(origin: OriginFor<T>, listing_id: ListingId) -> DispatchResult {
+            let subscriber = ensure_signed(origin)?;
+            let listing = Self::active_listing(listing_id)?;
+            let period_end = Subscriptions::<T>::try_mutate(listing_id, &subscriber, |maybe_subscription| {
+                let subscription = maybe_subscription.as_mut().ok_or(Error::<T>::NotSubscribed)?;
+                ensure!(subscription.escrow.is_zero(), Error::<T>::PeriodNotSettled);
+                T::Currency::reserve(&subscriber, listing.price).map_err(|_| Error::<T>::InsufficientBalance)?;
+                let now = frame_system::Pallet::<T>::block_number();
+                subscription.period_start = subscription.period_end.max(now);
+                subscription.period_end = subscription.period_start.saturating_add(listing.period);
+                subscription.escrow = listing.price;
+                Ok::<_, Error<T>>(subscription.period_end)
+            })?;
+            Self::deposit_event(Event::Subscribed(listing_id, subscriber, period_end));
+            Ok(())
+        }
+
+        /// Delivers the current period's stream key to a subscriber and releases the period's escrow to the owner.
+        ///
+        /// # Parameters:
+        /// - `listing_id`: The listing.
+        /// - `subscriber`: A subscriber with an unsettled period.
+        /// - `encrypted_key`: The stream key, encrypted to the subscriber's `encryption_key`.
+        ///
+        /// # Requirements:
+        /// - The caller must own the listing.
+        /// - The subscriber's current period must be paid and not yet settled.
+        #[pallet::call_index(9)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn deliver_key(
+            origin: OriginFor<T>,
+            listing_id: ListingId,
+            subscriber: T::AccountId,
+            encrypted_key: Vec<u8>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            let listing = Listings::<T>::get(listing_id).ok_or(Error::<T>::ListingNotFound)?;
+            ensure!(listing.owner == owner, Error::<T>::NotListingOwner);
+            ensure!(
+                !encrypted_key.is_empty() && encrypted_key.len() as u32 <= T::MaxEncryptedKeyLength::get(),
+                Error::<T>::InvalidEncryptedKey
+            );
+            let mut subscription = Subscriptions::<T>::get(listing_id, &subscriber).ok_or(Error::<T>::NotSubscribed)?;
+            ensure!(!subscription.escrow.is_zero(), Error::<T>::PeriodNotSettled);
+            // Part of the escrow may have been slashed from the subscriber's reserve since it was taken.
+            let shortfall =
+                T::Currency::repatriate_reserved(&subscriber, &owner, subscription.escrow, BalanceStatus::Free)?;
+            let paid = subscription.escrow.saturating_sub(shortfall);
+            subscription.escrow = Zero::zero();
+            KeyDeliveries::<T>::insert(listing_id, &subscriber, (subscription.period_start, encrypted_key));
+            Subscriptions::<T>::insert(listing_id, &subscriber, subscription);
+            Self::deposit_event(Event::KeyDelivered(listing_id, subscriber, paid));
+            Ok(())
+        }
+
+        /// Ends the caller's subscription. An escrow whose key has not been delivered is refunded.
+        #[pallet::call_index(10)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn cancel_subscription(origin: OriginFor<T>, listing_id: ListingId) -> DispatchResult {
+            let subscriber = ensure_signed(origin)?;
+            let subscription = Subscriptions::<T>::take(listing_id, &subscriber).ok_or(Error::<T>::NotSubscribed)?;
+            T::Currency::unreserve(&subscriber, subscription.escrow);
+            KeyDeliveries::<T>::remove(listing_id, &subscriber);
+            Self::deposit_event(Event::SubscriptionCancelled(listing_id, subscriber, subscription.escrow));
+            Ok(())
+        }
+
+        /// Sets, replaces or removes the plausibility rule of one metric of the caller's device. The metric's last
+        /// plausible value and any pending re-baseline are reset, so readings are screened afresh under the new rule.
+        ///
+        /// # Parameters:
+        /// - `device_id`: The device.
+        /// - `metric`: The metric the rule applies to.
+        /// - `rule`: The new rule, or `None` to stop screening the metric.
+        ///
+        /// # Requirements:
+        /// - The caller must own the device.
+        /// - `min` must not exceed `max`, and `max_delta` must not be negative.
+        /// - While the device is quarantined, the rule can only be tightened (see
+        ///   `PlausibilityRule::is_at_least_as_strict_as`), not removed or loosened.
+        #[pallet::call_index(11)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_plausibility_rule(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            metric: MetricId,
+            rule: Option<PlausibilityRule>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let device = Devices::<T>::get(&device_id).ok_or(Error::<T>::DeviceNotFound)?;
+            ensure!(device.owner == who, Error::<T>::NotDeviceOwner);
+            let old_rule = PlausibilityRules::<T>::get(&device_id, metric);
+            if DeviceHealthOf::<T>::get(&device_id).quarantined {
+                let tightened = match (&rule, &old_rule) {
+                    (Some(new), Some(old)) => new.is_at_least_as_strict_as(old),
+                    (Some(_), None) => true,
+                    (None, old) => old.is_none(),
+                };
+                ensure!(tightened, Error::<T>::RuleLoosenedWhileQuarantined);
+            }
+            match rule {
+                Some(rule) => {
+                    if let (Some(min), Some(max)) = (rule.min, rule.max) {
+                        ensure!(min <= max, Error::<T>::InvalidRule);
+                    }
+                    ensure!(rule.max_delta.map_or(true, |delta| delta >= 0), Error::<T>::InvalidRule);
+                    PlausibilityRules::<T>::insert(&device_id, metric, rule);
+                }
+                None => PlausibilityRules::<T>::remove(&device_id, metric),
+            }
+            LastValues::<T>::remove(&device_id, metric);
+            PendingBaselines::<T>::remove(&device_id, metric);
+            Self::deposit_event(Event::PlausibilityRuleSet(device_id, metric, old_rule, rule));
+            Ok(())
+        }
+
+        /// Releases a device from quarantine, e.g., after the owner showed the sensor was repaired or replaced. The
+        /// anomaly score and the last plausible values are reset.
+        ///
+        /// # Requirements:
+        /// - The origin must satisfy `GovernanceOrigin`, so owners cannot clear their own quarantine and resume
+        ///   submitting bad data.
+        #[pallet::call_index(12)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn release_quarantine(origin: OriginFor<T>, device_id: Vec<u8>) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(Devices::<T>::contains_key(&device_id), Error::<T>::DeviceNotFound);
+            ensure!(DeviceHealthOf::<T>::get(&device_id).quarantined, Error::<T>::NotQuarantined);
+            DeviceHealthOf::<T>::remove(&device_id);
+            let _ = LastValues::<T>::clear_prefix(&device_id, u32::MAX, None);
+            let _ = PendingBaselines::<T>::clear_prefix(&device_id, u32::MAX, None);
+            Self::deposit_event(Event::QuarantineReleased(device_id));
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Checks that a reading would be accepted: payload length, device status, nonce, signature, schema and rate
+        /// limit. Returns the typed readings held by the payload.
+        pub(crate) fn check_submission(
+            payload: &[u8],
+            device_id: &[u8],
+            nonce: u64,
+            signature: &MultiSignature,
+        ) -> Result<Vec<Reading>, Error<T>> {
+            ensure!(payload.len() as u32 <= T::MaxPayloadLength::get(), Error::<T>::PayloadTooLong);
+            ensure!(!device_id.is_empty(), Error::<T>::InvalidDeviceId);
+            let device = Devices::<T>::get(device_id).ok_or(Error::<T>::DeviceNotFound)?;
+            ensure!(device.active, Error::<T>::DeviceRevoked);
+            ensure!(!DeviceHealthOf::<T>::get(device_id).quarantined, Error::<T>::DeviceQuarantined);
+            ensure!(nonce >= DeviceNonces::<T>::get(device_id), Error::<T>::InvalidNonce);
+            ensure!(
+                Self::verify_data(&device.key, device_id, nonce, payload, signature),
+                Error::<T>::DataVerificationFailed
+            );
+            let readings = Self::decode_readings(&device, payload)?;
+            if let Some(last) = LastSubmission::<T>::get(device_id) {
+                let now = frame_system::Pallet::<T>::block_number();
+                ensure!(now.saturating_sub(last) >= T::MinSubmissionInterval::get(), Error::<T>::RateLimited);
+            }
+            Ok(readings)
+        }
+
+        /// Decodes a device's payload into typed readings.
+        ///
+        /// With a schema, the payload must decode according to it and yields one reading per field. Without one, a
+        /// payload holding the SCALE encoding of `Vec<Reading>` is used as is and any other payload is kept opaque.
+        pub fn decode_readings(
+            device: &DeviceInfo<T::AccountId, BalanceOf<T>>,
+            payload: &[u8],
+        ) -> Result<Vec<Reading>, Error<T>> {
+            let Some(schema_id) = &device.schema else {
+                return Ok(Vec::<Reading>::decode_all(&mut &payload[..]).unwrap_or_default());
+            };
+            let schema = T::Schemas::payload_schema(schema_id).ok_or(Error::<T>::UnknownSchema)?;
+            let values = schema.decode_payload(payload).map_err(|error| match error {
+                PayloadError::Malformed => Error::<T>::MalformedPayload,
+                PayloadError::OutOfRange(_) => Error::<T>::ValueOutOfRange,
+            })?;
+            Ok(values
+                .into_iter()
+                .enumerate()
+                .map(|(metric, value)| Reading { metric: metric as MetricId, value })
+                .collect())
+        }
+
+        /// Checks a value against a plausibility rule, given the metric's last plausible value and its repeat count.
+        pub fn check_plausibility(rule: &PlausibilityRule, last: Option<(i64, u32)>, value: i64) -> Option<AnomalyKind> {
+            if rule.min.map_or(false, |min| value < min) || rule.max.map_or(false, |max| value > max) {
+                return Some(AnomalyKind::OutOfRange);
+            }
+            let (last_value, repeats) = last?;
+            if Self::exceeds_delta(rule, last_value, value) {
+                return Some(AnomalyKind::RateOfChange);
+            }
+            if value == last_value && rule.max_repeats.map_or(false, |max_repeats| repeats >= max_repeats) {
+                return Some(AnomalyKind::Repeated);
+            }
+            None
+        }
+
+        /// Whether the change from `from` to `to` is larger than the rule's `max_delta`.
+        fn exceeds_delta(rule: &PlausibilityRule, from: i64, to: i64) -> bool {
+            rule.max_delta.map_or(false, |max_delta| (to as i128 - from as i128).abs() > max_delta as i128)
+        }
+
+        /// Records a reading rejected for its rate of change against the run of rejected readings before it, and
+        /// returns whether the run has reached `RebaselineAfter` readings, so the value is the metric's new baseline.
+        ///
+        /// Without this a legitimate step change (a relocated or recalibrated sensor) would be compared with the
+        /// old baseline forever and every later reading flagged.
+        fn rebaseline(device_id: &[u8], rule: &PlausibilityRule, metric: MetricId, value: i64) -> bool {
+            let required = T::RebaselineAfter::get();
+            PendingBaselines::<T>::mutate(device_id, metric, |pending| {
+                let run = match *pending {
+                    Some((previous, run)) if !Self::exceeds_delta(rule, previous, value) => run.saturating_add(1),
+                    _ => 1,
+                };
+                if required != 0 && run >= required {
+                    *pending = None;
+                    true
+                } else {
+                    *pending = Some((value, run));
+                    false
+                }
+            })
+        }
+
+        /// Screens a submission's readings against the device's plausibility rules and returns the plausible ones.
+        ///
+        /// A reading breaking `max_delta` that completes a run of `RebaselineAfter` consistent readings is accepted as
+        /// the metric's new baseline instead of being flagged. Each anomalous reading is flagged with an event. A
+        /// submission with any anomaly raises the device's anomaly score and is reported against the owner's
+        /// reputation; a clean one lowers the score. Devices reaching `QuarantineThreshold` are quarantined.
+        fn screen_readings(device_id: &[u8], id: u64, readings: Vec<Reading>) -> Vec<Reading> {
+            let mut plausible = Vec::with_capacity(readings.len());
+            let mut anomalous = false;
+            for reading in readings {
+                let Some(rule) = PlausibilityRules::<T>::get(device_id, reading.metric) else {
+                    plausible.push(reading);
+                    continue;
+                };
+                let last = LastValues::<T>::get(device_id, reading.metric);
+                let mut anomaly = Self::check_plausibility(&rule, last, reading.value);
+                if anomaly == Some(AnomalyKind::RateOfChange)
+                    && Self::rebaseline(device_id, &rule, reading.metric, reading.value)
+                {
+                    anomaly = None;
+                }
+                match anomaly {
+                    Some(kind) => {
+                        anomalous = true;
+                        // A stuck value keeps counting its repeats; other anomalies leave the last value unchanged.
+                        if kind == AnomalyKind::Repeated {
+                            LastValues::<T>::mutate(device_id, reading.metric, |last| {
+                                if let Some((_, repeats)) = last {
+                                    *repeats = repeats.saturating_add(1);
+                                }
+                            });
+                        }
+                        Self::deposit_event(Event::AnomalyDetected(
+                            device_id.to_vec(),
+                            id,
+                            reading.metric,
+                            reading.value,
+                            kind,
+                        ));
+                    }
+                    None => {
+                        let repeats = match last {
+                            Some((last_value, repeats)) if last_value == reading.value => repeats.saturating_add(1),
+                            _ => 0,
+                        };
+                        LastValues::<T>::insert(device_id, reading.metric, (reading.value, repeats));
+                        PendingBaselines::<T>::remove(device_id, reading.metric);
+                        plausible.push(reading);
+                    }
+                }
+            }
+            if anomalous {
+                Self::record_anomaly(device_id);
+            } else {
+                DeviceHealthOf::<T>::mutate(device_id, |health| health.anomalies = health.anomalies.saturating_sub(1));
+            }
+            plausible
+        }
+
+        /// Raises a device's anomaly score, reports the anomaly against the owner's reputation and quarantines the
+        /// device once the score reaches `QuarantineThreshold`.
+        fn record_anomaly(device_id: &[u8]) {
+            let health = DeviceHealthOf::<T>::mutate(device_id, |health| {
+                health.anomalies = health.anomalies.saturating_add(1);
+                *health
+            });
+            if let Some(device) = Devices::<T>::get(device_id) {
+                let penalty = T::AnomalyPenalty::get().min(i32::MAX as u32) as i32;
+                // A rejected report (e.g., the reporter's cap is exhausted) must not reject the reading itself.
+                let _ = T::Reputation::report(
+                    &T::ReporterAccount::get(),
+                    &device.owner,
+                    ReputationCategory::IotDataQuality,
+                    -penalty,
+                    device_id.to_vec(),
+                );
+            }
+            if !health.quarantined && health.anomalies >= T::QuarantineThreshold::get() {
+                DeviceHealthOf::<T>::mutate(device_id, |health| health.quarantined = true);
+                Self::deposit_event(Event::DeviceQuarantined(device_id.to_vec()));
+            }
+        }
+
+        /// Returns the window index a block falls into.
+        pub fn window_of(block: T::BlockNumber) -> T::BlockNumber {
+            let window = T::AggregationWindow::get();
+            if window.is_zero() {
+                return block;
+            }
+            block / window
+        }
+
+        /// Folds readings into the device's aggregates for the window containing `block`.
+        fn aggregate_readings(device_id: &[u8], block: T::BlockNumber, readings: &[Reading]) {
+            let window = Self::window_of(block);
+            for reading in readings {
+                Aggregates::<T>::mutate((device_id.to_vec(), reading.metric), window, |aggregate| match aggregate {
+                    Some(aggregate) => aggregate.add(reading.value),
+                    None => *aggregate = Some(Aggregate::new(reading.value)),
+                });
+            }
+        }
+
+        /// Removes raw records older than `RetentionPeriod`, at most `MaxPrunePerBlock` per call.
+        /// Returns the number of records removed.
+        pub(crate) fn prune_records(now: T::BlockNumber) -> u32 {
+            let next_id = NextIotId::<T>::get();
+            let mut cursor = PruneCursor::<T>::get();
+            let mut pruned = 0;
+            while cursor < next_id && pruned < T::MaxPrunePerBlock::get() {
+                if let Some(record) = IotData::<T>::get(cursor) {
+                    if now.saturating_sub(record.block) < T::RetentionPeriod::get() {
+                        break;
+                    }
+                    IotData::<T>::remove(cursor);
+                    pruned += 1;
+                }
+                cursor += 1;
+            }
+            PruneCursor::<T>::put(cursor);
+            pruned
+        }
+
+        /// Returns a listing that accepts subscriptions.
+        fn active_listing(
+            listing_id: ListingId,
+        ) -> Result<DataListing<T::AccountId, BalanceOf<T>, T::BlockNumber>, Error<T>> {
+            let listing = Listings::<T>::get(listing_id).ok_or(Error::<T>::ListingNotFound)?;
+            ensure!(listing.active, Error::<T>::ListingClosed);
+            ensure!(Devices::<T>::get(&listing.device_id).map_or(false, |device| device.active), Error::<T>::DeviceRevoked);
+            Ok(listing)
+        }
+
+        /// Returns the message a device signs for a submission: the SCALE encoding of (device ID, nonce, payload).
+        pub fn signing_payload(device_id: &[u8], nonce: u64, payload: &[u8]) -> Vec<u8> {
+            (device_id, nonce, payload).encode()
+        }
+
+        /// Verifies a submission's signature against the device's registered key.
+        fn verify_data(key: &MultiSigner, device_id: &[u8], nonce: u64, payload: &[u8], signature: &MultiSignature) -> bool {
+            let message = Self::signing_payload(device_id, nonce, payload);
+            signature.verify(&message[..], &key.clone().into_account())
         }
 
         /// Returns the current Unix timestamp.
@@ -183,26 +1191,60 @@ mod benchmarking {
     use super::*;
     use frame_benchmarking::{benchmarks, account};
     use frame_system::RawOrigin;
+    use sp_core::crypto::KeyTypeId;
     use sp_std::vec::Vec;
 
+    /// Key type of the keystore keys generated for benchmarking device signatures.
+    const KEY_TYPE: KeyTypeId = KeyTypeId(*b"iotb");
+
     benchmarks! {
         submit_iot_data {
-            let id: u64 = 1;
-            let payload: Vec<u8> = b"Benchmark IoT Payload".to_vec();
+            let owner: T::AccountId = account("owner", 0, 0);
+            let readings: Vec<Reading> = (0..8).map(|metric| Reading { metric, value: 21 }).collect();
+            let payload: Vec<u8> = readings.encode();
             let device_id: Vec<u8> = b"Device01".to_vec();
-            let signature: Vec<u8> = b"ValidSignature".to_vec();
+            let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+            let deposit = T::DeviceDeposit::get();
+            T::Currency::make_free_balance_be(&owner, deposit.saturating_mul(10u32.into()));
+            Pallet::<T>::register_device(RawOrigin::Signed(owner.clone()).into(), device_id.clone(), public.into(), Vec::new(), None)?;
+            // Screen every metric so the benchmark covers plausibility checks.
+            let rule = PlausibilityRule { min: Some(0), max: Some(100), max_delta: Some(10), max_repeats: Some(3) };
+            for metric in 0..8 {
+                Pallet::<T>::set_plausibility_rule(RawOrigin::Signed(owner.clone()).into(), device_id.clone(), metric, Some(rule))?;
+            }
+            let message = Pallet::<T>::signing_payload(&device_id, 0, &payload);
+            let signature: MultiSignature = sp_io::crypto::sr25519_sign(KEY_TYPE, &public, &message).unwrap().into();
         }: {
-            Pallet::<T>::submit_iot_data(id, payload.clone(), device_id.clone(), signature.clone())?;
+            Pallet::<T>::submit_iot_data(RawOrigin::None.into(), payload.clone(), device_id.clone(), 0, signature.clone())?;
         }
         verify {
             // Verification is based on event emission.
         }
 
+        deliver_key {
+            let owner: T::AccountId = account("owner", 0, 0);
+            let subscriber: T::AccountId = account("subscriber", 0, 0);
+            let device_id: Vec<u8> = b"Device01".to_vec();
+            let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+            let deposit = T::DeviceDeposit::get();
+            T::Currency::make_free_balance_be(&owner, deposit.saturating_mul(10u32.into()));
+            T::Currency::make_free_balance_be(&subscriber, deposit.saturating_mul(10u32.into()));
+            Pallet::<T>::register_device(RawOrigin::Signed(owner.clone()).into(), device_id.clone(), public.into(), Vec::new(), None)?;
+            Pallet::<T>::create_listing(RawOrigin::Signed(owner.clone()).into(), device_id, deposit, 10u32.into())?;
+            Pallet::<T>::subscribe(RawOrigin::Signed(subscriber.clone()).into(), 0, [1u8; 32])?;
+            let encrypted_key = sp_std::vec![7u8; T::MaxEncryptedKeyLength::get() as usize];
+        }: {
+            Pallet::<T>::deliver_key(RawOrigin::Signed(owner.clone()).into(), 0, subscriber.clone(), encrypted_key)?;
+        }
+        verify {
+            assert!(KeyDeliveries::<T>::contains_key(0, &subscriber));
+        }
+
         update_config {
             let new_config: Vec<u8> = b"NewIoTConfig".to_vec();
             let details: Vec<u8> = b"BenchmarkConfigUpdate".to_vec();
         }: {
-            Pallet::<T>::update_config(new_config.clone(), details.clone())?;
+            Pallet::<T>::update_config(RawOrigin::Root.into(), new_config.clone(), details.clone())?;
         }
         verify {
             // Verification is based on event emission.
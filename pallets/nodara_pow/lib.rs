@@ -18,6 +18,8 @@
 //!   Logs every PoW submission and validation event with complete metadata.
 //! - **DAO Governance Integration:**  
 //!   Enables community-driven updates to difficulty and reward parameters.
+//! - **Stake Slashing:**  
+//!   Miners whose work fails validation lose `InvalidWorkSlash` of their stake in `nodara_staking`.
 //! - **Performance Optimizations:**  
 //!   Highly optimized code ensures minimal computational overhead, with integrated benchmarking.
 //!
@@ -25,6 +27,7 @@
 //! - **Storage:**
 //!   - `WorkSubmissions`: Stores PoW submissions indexed by unique IDs.
 //!   - `PowHistory`: Maintains an immutable log of all PoW events.
+//!   - `Miners`: Account that submitted each work still awaiting validation.
 //! - **Core Functions:**
 //!   - `submit_work`: Accepts and verifies work submissions from miners.
 //!   - `validate_work`: Validates submitted work against current difficulty.
@@ -42,6 +45,8 @@
 
 use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::Get};
 use frame_system::pallet_prelude::*;
+use nodara_staking::StakeSlasher;
+use sp_runtime::Perbill;
 use sp_std::vec::Vec;
 
 /// Macro to simulate formal invariant checks.
@@ -83,6 +88,11 @@ pub mod pallet {
     #[pallet::getter(fn pow_history)]
     pub type PowHistory<T: Config> = StorageValue<_, Vec<(u64, u64, u64, u32, bool)>, ValueQuery>;
 
+    /// Account that submitted each work awaiting validation; taken when the work is validated.
+    #[pallet::storage]
+    #[pallet::getter(fn miners)]
+    pub type Miners<T: Config> = StorageMap<_, Blake2_128Concat, u64, T::AccountId, OptionQuery>;
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The runtime event type.
@@ -96,6 +106,11 @@ pub mod pallet {
         /// Minimum allowed difficulty.
         #[pallet::constant]
         type MinDifficulty: Get<u32>;
+        /// Stake slashed from miners whose work fails validation (typically `nodara_staking`).
+        type Slasher: StakeSlasher<Self::AccountId>;
+        /// Fraction of a miner's stake slashed for invalid work.
+        #[pallet::constant]
+        type InvalidWorkSlash: Get<Perbill>;
     }
 
     #[pallet::event]
@@ -105,6 +120,8 @@ pub mod pallet {
         WorkSubmitted(u64, u64, Vec<u8>),
         /// Emitted when a work submission is validated.
         WorkValidated(u64, bool),
+        /// Emitted when a miner is slashed for invalid work (submission ID, miner, slashed amount).
+        MinerSlashed(u64, T::AccountId, u32),
     }
 
     #[pallet::error]
@@ -132,6 +149,7 @@ pub mod pallet {
             let timestamp = Self::current_timestamp();
             let submission = WorkSubmission { id, work_hash: work_hash.clone(), miner: miner.into(), timestamp };
             <WorkSubmissions<T>>::insert(id, submission);
+            <Miners<T>>::insert(id, &miner);
             <PowHistory<T>>::mutate(|history| {
                 // Initially mark the submission as unvalidated (false).
                 history.push((timestamp, id, miner.into(), T::BaselineDifficulty::get(), false))
@@ -145,6 +163,9 @@ pub mod pallet {
         /// # Parameters:
         /// - `id`: Unique identifier of the work submission.
         ///
+        /// Invalid work is reported to `T::Slasher`, which slashes `InvalidWorkSlash` of the miner's stake. Each
+        /// submission is slashed at most once.
+        ///
         /// # Requirements:
         /// - The work must pass the simulated cryptographic and difficulty checks.
         pub fn validate_work(id: u64) -> DispatchResult {
@@ -157,6 +178,12 @@ pub mod pallet {
                 history.push((timestamp, id, submission.miner, T::BaselineDifficulty::get(), valid))
             });
             Self::deposit_event(Event::WorkValidated(id, valid));
+            if let Some(miner) = <Miners<T>>::take(id) {
+                if !valid {
+                    let slashed = T::Slasher::slash(&miner, T::InvalidWorkSlash::get(), b"Invalid Work".to_vec());
+                    Self::deposit_event(Event::MinerSlashed(id, miner, slashed));
+                }
+            }
             Ok(())
         }
 
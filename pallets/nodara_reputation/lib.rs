@@ -24,12 +24,37 @@
 //!
 //! ## Module Structure:
 //! - **Storage:**
-//!   - `ReputationScores`: Maps account IDs to their current reputation scores.
+//!   - `ReputationScores`: Maps (account, category) to a reputation score and the block it was last written at.
+//!   - `Reports`: Disputable reports and bonded peer attestations, with the score change they applied.
+//!   - `CategoryConfig` / `CategoryWeights`: Governance-set bounds and decay per category, and the weights used to
+//!     compute the aggregate score.
 //!   - `ReputationHistory`: Records all updates to reputation scores.
+//!   - `Reporters` / `ReporterUsage`: Governance-authorized reporters with their weight, per-period cap and cap usage.
+//!   - `AttestationUsage`: Per-period attestation gains and losses applied to each subject record.
 //! - **Core Functions:**
 //!   - `calculate_reputation`: Aggregates reputation data and computes a new score.
-//!   - `update_reputation`: Updates the reputation score for an account with smoothing and weighting.
+//!   - `add_reporter` / `remove_reporter`: Manage the authorized reporter registry (governance origin only).
+//!   - `update_reputation`: Applies a report from an authorized reporter to a subject account. Accounts can no longer
+//!     report on themselves; pallets report through the `ReputationReporter` trait.
+//!   - `attest`: Records a bonded peer attestation about another account, within a per-period cap on the total
+//!     attested gain (and loss) of the subject's record, however many accounts attest.
+//!   - `dispute` / `resolve_dispute` / `release_bond`: Any bonded account challenges a report within `DisputeWindow`; the
+//!     arbiter origin decides, and overturned reports are reverted while the reporter's bond and reputation are slashed.
+//!   - `record_holder`: Resolves the account whose record holds an account's reputation. With
+//!     `ShareIdentityReputation`, accounts linked to one `nodara_id` identity share a single record, so throwaway
+//!     accounts cannot farm separate scores.
+//!   - `set_category_params` / `set_category_weights`: Governance configuration of categories (trading reliability,
+//!     IoT data quality, mining honesty, governance participation) and of the aggregate.
+//!   - `aggregate_score`: Weighted aggregate across categories; exposed to other pallets via `ReputationProvider`.
+//!   - `current_score`: Returns a category score decayed toward the category default with a configurable half-life. Decay is
+//!     applied lazily on read and update, so scores fall back over time without any storage sweep.
 //!   - `verify_invariants`: (Internal) Checks that reputation calculations satisfy defined invariants.
+//! - **Integration:**
+//!   - `IdentityProvider` (from `nodara_id`): Positive metrics for accounts without a verified identity are scaled by
+//!     `UnverifiedGainWeight`, gating or weighting reputation gains by verification.
+//!   - `OnIdentityMigrated` (from `nodara_id`): Reputation follows an identity recovered to a new account.
+//!   - `ReputationProvider`: Implemented by the pallet so that other modules read scores directly from
+//!     `ReputationScores` instead of trusting caller-supplied values.
 //!
 //! ## Version:
 //! March 2025 – Nodara BIOSPHÈRE QUANTIC Legendary Edition
@@ -39,9 +64,80 @@
 //! - Transition to full formal verification frameworks.
 //! - Expanded governance mechanisms for granular reputation adjustments.
 
-use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::Get};
+use frame_support::{
+    dispatch::DispatchResult,
+    pallet_prelude::*,
+    traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+};
 use frame_system::pallet_prelude::*;
+use sp_runtime::{traits::{SaturatedConversion, Zero}, Percent};
 use sp_std::vec::Vec;
+use nodara_id::{IdentityProvider, OnIdentityMigrated};
+
+/// Read-only access to earned reputation, consumed by pallets that weight their logic by reputation
+/// (e.g., the reward engine).
+pub trait ReputationProvider<AccountId> {
+    /// Returns the recorded reputation score of `who`, or `None` if the account has no record.
+    fn reputation_of(who: &AccountId) -> Option<u32>;
+}
+
+/// Null provider: no account has a recorded reputation.
+impl<AccountId> ReputationProvider<AccountId> for () {
+    fn reputation_of(_who: &AccountId) -> Option<u32> {
+        None
+    }
+}
+
+/// Entry point for pallets (marketplace, IoT bridge, PoW, ...) to report on an account's behavior.
+///
+/// `reporter` is the account registered for the calling pallet (e.g., derived from its `PalletId`); the report is
+/// subject to the same weight and per-period cap as a report submitted by an authorized account.
+pub trait ReputationReporter<AccountId> {
+    /// Reports `input_metric` about `subject` in `category` on behalf of `reporter`.
+    fn report(
+        reporter: &AccountId,
+        subject: &AccountId,
+        category: ReputationCategory,
+        input_metric: i32,
+        details: Vec<u8>,
+    ) -> DispatchResult;
+}
+
+/// Null reporter: reports are accepted and discarded.
+impl<AccountId> ReputationReporter<AccountId> for () {
+    fn report(
+        _reporter: &AccountId,
+        _subject: &AccountId,
+        _category: ReputationCategory,
+        _input_metric: i32,
+        _details: Vec<u8>,
+    ) -> DispatchResult {
+        Ok(())
+    }
+}
+
+/// Dimension along which reputation is tracked. Each category has its own bounds and decay.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ReputationCategory {
+    /// Reliability as a marketplace counterparty.
+    TradingReliability,
+    /// Quality of the data published through the IoT bridge.
+    IotDataQuality,
+    /// Honesty of PoW submissions.
+    MiningHonesty,
+    /// Participation in DAO governance.
+    GovernanceParticipation,
+}
+
+impl ReputationCategory {
+    /// All categories, in aggregation order.
+    pub const ALL: [ReputationCategory; 4] = [
+        ReputationCategory::TradingReliability,
+        ReputationCategory::IotDataQuality,
+        ReputationCategory::MiningHonesty,
+        ReputationCategory::GovernanceParticipation,
+    ];
+}
 
 /// Macro to simulate formal invariant checks. In production, replace with rigorous formal proofs.
 macro_rules! assert_invariant {
@@ -56,33 +152,149 @@ pub mod pallet {
 
     /// Structure representing a reputation record.
     #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-    pub struct ReputationRecord {
-        /// The current reputation score.
+    pub struct ReputationRecord<BlockNumber> {
+        /// The reputation score as of `last_updated` (before decay).
         pub score: u32,
         /// Additional details from the calculation.
         pub details: Vec<u8>,
+        /// Block at which `score` was last written.
+        pub last_updated: BlockNumber,
+    }
+
+    /// Bounds and decay parameters of a reputation category.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct CategoryParams<BlockNumber> {
+        /// Minimum score in the category.
+        pub min: u32,
+        /// Maximum score in the category.
+        pub max: u32,
+        /// Score of accounts without a record, and the value scores decay toward.
+        pub default: u32,
+        /// Blocks after which the distance to `default` is halved; zero disables decay.
+        pub half_life: BlockNumber,
+    }
+
+    /// Registration of an authorized reporter.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ReporterInfo {
+        /// Fraction of each reported metric that is applied to the subject's score.
+        pub weight: Percent,
+        /// Maximum total absolute (weighted) metric the reporter may apply per reporting period.
+        pub period_cap: u32,
+    }
+
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// Identifier of a recorded report or attestation.
+    pub type ReportId = u64;
+
+    /// Lifecycle of a report.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum ReportStatus {
+        /// Applied and still within (or past) its dispute window without being challenged.
+        Active,
+        /// Challenged by a bonded account and awaiting the arbiter.
+        Disputed,
+        /// Confirmed by the arbiter.
+        Upheld,
+        /// Overturned by the arbiter; its effect was reverted and the reporter penalized.
+        Overturned,
+        /// The reporter's bond has been released.
+        Settled,
+    }
+
+    /// A report or peer attestation that changed a subject's score and can be disputed.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Report<AccountId, Balance, BlockNumber> {
+        /// Authorized reporter or attesting peer.
+        pub reporter: AccountId,
+        /// Account whose score was changed.
+        pub subject: AccountId,
+        /// Category the report concerns.
+        pub category: ReputationCategory,
+        /// Score change actually applied to the subject (after weighting and clamping).
+        pub applied_delta: i64,
+        /// Bond reserved from the reporter (zero for authorized reporters).
+        pub bond: Balance,
+        /// Account that disputed the report, if any.
+        pub disputer: Option<AccountId>,
+        /// Bond reserved from the disputer.
+        pub dispute_bond: Balance,
+        /// Block at which the report was applied.
+        pub submitted_at: BlockNumber,
+        /// Current status.
+        pub status: ReportStatus,
     }
 
     /// Main pallet structure for decentralized reputation management.
     #[frame_support::pallet]
     pub struct Pallet<T>(_);
 
-    /// Storage for reputation scores.
+    /// Storage for reputation scores, per account and category.
     #[pallet::storage]
     #[pallet::getter(fn reputation_scores)]
-    pub type ReputationScores<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ReputationRecord, OptionQuery>;
+    pub type ReputationScores<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        ReputationCategory,
+        ReputationRecord<T::BlockNumber>,
+        OptionQuery,
+    >;
 
     /// Storage for logging reputation updates.
-    /// Each record: (timestamp, account, previous score, new score, calculation details)
+    /// Each record: (timestamp, account, category, previous score, new score, calculation details)
     #[pallet::storage]
     #[pallet::getter(fn reputation_history)]
-    pub type ReputationHistory<T: Config> = StorageValue<_, Vec<(u64, T::AccountId, u32, u32, Vec<u8>)>, ValueQuery>;
+    pub type ReputationHistory<T: Config> =
+        StorageValue<_, Vec<(u64, T::AccountId, ReputationCategory, u32, u32, Vec<u8>)>, ValueQuery>;
+
+    /// Governance-set parameters per category. Categories without an entry use the pallet-wide constants.
+    #[pallet::storage]
+    #[pallet::getter(fn category_config)]
+    pub type CategoryConfig<T: Config> =
+        StorageMap<_, Blake2_128Concat, ReputationCategory, CategoryParams<T::BlockNumber>, OptionQuery>;
+
+    /// Governance-set weight of each category in the aggregate score. If all weights are zero, categories are
+    /// weighted equally.
+    #[pallet::storage]
+    #[pallet::getter(fn category_weights)]
+    pub type CategoryWeights<T: Config> = StorageMap<_, Blake2_128Concat, ReputationCategory, u32, ValueQuery>;
+
+    /// Authorized reporter accounts (including accounts representing pallets).
+    #[pallet::storage]
+    #[pallet::getter(fn reporters)]
+    pub type Reporters<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ReporterInfo, OptionQuery>;
+
+    /// Usage of each reporter's cap: (reporting period index, weighted metric applied in that period).
+    #[pallet::storage]
+    #[pallet::getter(fn reporter_usage)]
+    pub type ReporterUsage<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (u32, u32), ValueQuery>;
+
+    /// Attestation cap usage per subject record: (reporting period index, weighted gain applied in that period,
+    /// weighted loss applied in that period), summed over all attesters.
+    #[pallet::storage]
+    #[pallet::getter(fn attestation_usage)]
+    pub type AttestationUsage<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (u32, u32, u32), ValueQuery>;
+
+    /// Reports and attestations that can be disputed.
+    #[pallet::storage]
+    #[pallet::getter(fn reports)]
+    pub type Reports<T: Config> =
+        StorageMap<_, Blake2_128Concat, ReportId, Report<T::AccountId, BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+    /// Identifier assigned to the next report.
+    #[pallet::storage]
+    #[pallet::getter(fn next_report_id)]
+    pub type NextReportId<T: Config> = StorageValue<_, ReportId, ValueQuery>;
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The runtime event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-        /// Default reputation score for new accounts.
+        /// Default reputation score for new accounts (used for categories without governance-set parameters).
         #[pallet::constant]
         type DefaultReputation: Get<u32>;
         /// Maximum allowed reputation score.
@@ -91,13 +303,69 @@ pub mod pallet {
         /// Minimum allowed reputation score.
         #[pallet::constant]
         type MinReputation: Get<u32>;
+        /// Number of blocks after which the distance between a score and `DefaultReputation` is halved.
+        /// Zero disables decay.
+        #[pallet::constant]
+        type ReputationHalfLife: Get<Self::BlockNumber>;
+        /// Length, in blocks, of the period over which reporter caps apply.
+        #[pallet::constant]
+        type ReportingPeriod: Get<Self::BlockNumber>;
+        /// Origin allowed to manage reporters and category parameters (e.g., DAO governance).
+        type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+        /// Currency used for attestation and dispute bonds.
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// Origin that resolves disputes (e.g., an arbitration council).
+        type ArbiterOrigin: EnsureOrigin<Self::Origin>;
+        /// Bond reserved from a peer for each attestation.
+        #[pallet::constant]
+        type AttestationBond: Get<BalanceOf<Self>>;
+        /// Bond reserved from an account disputing a report.
+        #[pallet::constant]
+        type DisputeBond: Get<BalanceOf<Self>>;
+        /// Fraction of a peer attestation's metric applied to the subject's score.
+        #[pallet::constant]
+        type AttestationWeight: Get<Percent>;
+        /// Maximum total weighted gain, and separately loss, that attestations from all attesters together may apply
+        /// to one subject record per `ReportingPeriod`.
+        #[pallet::constant]
+        type AttestationCap: Get<u32>;
+        /// Number of blocks after a report during which it may be disputed.
+        #[pallet::constant]
+        type DisputeWindow: Get<Self::BlockNumber>;
+        /// Score deducted from a reporter, in the report's category, when its report is overturned.
+        #[pallet::constant]
+        type OverturnPenalty: Get<u32>;
+        /// Source of identity status (typically `nodara_id`).
+        type Identity: IdentityProvider<Self::AccountId>;
+        /// Fraction of positive metrics applied to accounts without a verified identity (zero gates gains entirely).
+        #[pallet::constant]
+        type UnverifiedGainWeight: Get<Percent>;
+        /// If true, all accounts linked to the same identity share the reputation record of the identity holder.
+        #[pallet::constant]
+        type ShareIdentityReputation: Get<bool>;
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// Emitted when a reputation score is updated: (account, previous score, new score, details).
-        ReputationUpdated(T::AccountId, u32, u32, Vec<u8>),
+        /// Emitted when a reputation score is updated: (account, category, previous score, new score, details).
+        ReputationUpdated(T::AccountId, ReputationCategory, u32, u32, Vec<u8>),
+        /// Emitted when governance changes a category's bounds or decay: (category).
+        CategoryParamsUpdated(ReputationCategory),
+        /// Emitted when governance changes the aggregate weights.
+        CategoryWeightsUpdated(Vec<(ReputationCategory, u32)>),
+        /// Emitted when a report or attestation is recorded: (report ID, reporter, subject, category).
+        ReportRecorded(ReportId, T::AccountId, T::AccountId, ReputationCategory),
+        /// Emitted when a report is disputed: (report ID, disputer).
+        ReportDisputed(ReportId, T::AccountId),
+        /// Emitted when the arbiter resolves a dispute: (report ID, overturned).
+        DisputeResolved(ReportId, bool),
+        /// Emitted when a reporter's bond is slashed: (reporter, amount).
+        ReporterSlashed(T::AccountId, BalanceOf<T>),
+        /// Emitted when a reporter is authorized or its terms change: (reporter, weight, period cap).
+        ReporterAuthorized(T::AccountId, Percent, u32),
+        /// Emitted when a reporter is removed: (reporter).
+        ReporterRemoved(T::AccountId),
     }
 
     #[pallet::error]
@@ -106,6 +374,28 @@ pub mod pallet {
         ReputationOutOfBounds,
         /// Invalid data provided for reputation calculation.
         InvalidReputationData,
+        /// The reporter is not in the authorized reporter registry.
+        NotAuthorizedReporter,
+        /// Accounts cannot report on themselves.
+        SelfReportNotAllowed,
+        /// The report would exceed the reporter's cap for the current period.
+        ReporterCapExceeded,
+        /// Category parameters must satisfy min <= default <= max.
+        InvalidCategoryParams,
+        /// The report does not exist.
+        ReportNotFound,
+        /// A reporter cannot dispute its own report.
+        CannotDisputeOwnReport,
+        /// The attestation would exceed the subject's attestation cap for the current period.
+        AttestationCapExceeded,
+        /// The dispute window of the report has closed.
+        DisputeWindowClosed,
+        /// The dispute window is still open, so the bond cannot be released yet.
+        DisputeWindowOpen,
+        /// The report is not in a state that allows this operation.
+        InvalidReportStatus,
+        /// The free balance does not cover the required bond.
+        InsufficientBond,
     }
 
     impl<T: Config> Pallet<T> {
@@ -115,52 +405,412 @@ pub mod pallet {
         /// and computes a reputation score that is then clamped within the allowed range.
         ///
         /// # Parameters:
-        /// - `current_score`: The current reputation score.
+        /// - `category`: The category whose bounds apply.
+        /// - `current_score`: The current (decayed) reputation score.
         /// - `input_metric`: A metric representing recent activity (e.g., transaction count, voting participation).
+        ///   Negative metrics (e.g., failed deliveries, invalid work) lower the score.
         ///
         /// # Returns:
         /// The new reputation score.
-        pub fn calculate_reputation(current_score: u32, input_metric: u32) -> Result<u32, Error<T>> {
+        pub fn calculate_reputation(category: ReputationCategory, current_score: u32, input_metric: i32) -> Result<u32, Error<T>> {
+            let params = Self::params(category);
             // For demonstration, use a simple formula: new_score = current_score + (input_metric / 2)
-            let mut new_score = current_score.saturating_add(input_metric / 2);
-            if new_score > T::MaxReputation::get() {
-                new_score = T::MaxReputation::get();
+            let delta = input_metric.unsigned_abs() / 2;
+            let mut new_score = if input_metric >= 0 {
+                current_score.saturating_add(delta)
+            } else {
+                current_score.saturating_sub(delta)
+            };
+            if new_score > params.max {
+                new_score = params.max;
             }
-            if new_score < T::MinReputation::get() {
-                new_score = T::MinReputation::get();
+            if new_score < params.min {
+                new_score = params.min;
             }
             Ok(new_score)
         }
 
-        /// Updates the reputation score for a given account.
+        /// Sets the bounds and decay of a category.
+        ///
+        /// # Requirements:
+        /// - `origin` must satisfy `GovernanceOrigin`.
+        /// - `params` must satisfy min <= default <= max.
+        pub fn set_category_params(
+            origin: T::Origin,
+            category: ReputationCategory,
+            params: CategoryParams<T::BlockNumber>,
+        ) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(params.min <= params.default && params.default <= params.max, Error::<T>::InvalidCategoryParams);
+            CategoryConfig::<T>::insert(category, params);
+            Self::deposit_event(Event::CategoryParamsUpdated(category));
+            Ok(())
+        }
+
+        /// Sets the weight of each category in the aggregate score. Categories not listed get weight zero.
+        pub fn set_category_weights(origin: T::Origin, weights: Vec<(ReputationCategory, u32)>) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            for category in ReputationCategory::ALL.iter() {
+                CategoryWeights::<T>::remove(category);
+            }
+            for (category, weight) in weights.iter() {
+                CategoryWeights::<T>::insert(category, weight);
+            }
+            Self::deposit_event(Event::CategoryWeightsUpdated(weights));
+            Ok(())
+        }
+
+        /// Authorizes `reporter` (an account or a pallet's account) to submit reputation reports.
         ///
         /// # Parameters:
-        /// - `origin`: The account initiating the update.
-        /// - `input_metric`: The metric used to update the reputation score.
+        /// - `origin`: Must satisfy `GovernanceOrigin`.
+        /// - `reporter`: The account to authorize.
+        /// - `weight`: Fraction of each reported metric applied to the subject.
+        /// - `period_cap`: Maximum total absolute weighted metric per `ReportingPeriod`.
+        pub fn add_reporter(origin: T::Origin, reporter: T::AccountId, weight: Percent, period_cap: u32) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            Reporters::<T>::insert(&reporter, ReporterInfo { weight, period_cap });
+            Self::deposit_event(Event::ReporterAuthorized(reporter, weight, period_cap));
+            Ok(())
+        }
+
+        /// Removes `reporter` from the authorized reporter registry.
+        pub fn remove_reporter(origin: T::Origin, reporter: T::AccountId) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(Reporters::<T>::take(&reporter).is_some(), Error::<T>::NotAuthorizedReporter);
+            ReporterUsage::<T>::remove(&reporter);
+            Self::deposit_event(Event::ReporterRemoved(reporter));
+            Ok(())
+        }
+
+        /// Updates the reputation score of `subject` based on a report by the signed origin.
+        ///
+        /// # Parameters:
+        /// - `origin`: The reporter; must be an authorized reporter.
+        /// - `subject`: The account whose reputation is updated.
+        /// - `category`: The reputation category the report concerns.
+        /// - `input_metric`: The metric used to update the reputation score (may be negative).
         /// - `details`: Additional details about the update (e.g., data sources, calculation factors).
         ///
+        /// The stored score is first decayed to the current block, then the reporter-weighted metric is applied.
+        ///
         /// # Requirements:
-        /// - The calculated reputation must be within [MinReputation, MaxReputation].
-        pub fn update_reputation(origin: T::Origin, input_metric: u32, details: Vec<u8>) -> DispatchResult {
-            let account = ensure_signed(origin)?;
-            let current_record = ReputationScores::<T>::get(&account).unwrap_or(ReputationRecord {
-                score: T::DefaultReputation::get(),
-                details: b"Initial Score".to_vec(),
-            });
-            let previous_score = current_record.score;
-            let new_score = Self::calculate_reputation(previous_score, input_metric)
+        /// - The reporter must be authorized and must not report on itself.
+        /// - The weighted metric must fit in the reporter's remaining cap for the current period.
+        /// - The calculated reputation must be within the category's bounds.
+        pub fn update_reputation(
+            origin: T::Origin,
+            subject: T::AccountId,
+            category: ReputationCategory,
+            input_metric: i32,
+            details: Vec<u8>,
+        ) -> DispatchResult {
+            let reporter = ensure_signed(origin)?;
+            Self::do_report(&reporter, &subject, category, input_metric, details)
+        }
+
+        /// Records a bonded peer attestation about `subject`.
+        ///
+        /// Any account may attest; `AttestationWeight` of the metric is applied and `AttestationBond` is reserved
+        /// until the dispute window has passed (see `release_bond`). The bond is slashed if the attestation is
+        /// overturned.
+        ///
+        /// # Requirements:
+        /// - The attester must not attest about its own record (itself or, with `ShareIdentityReputation`, an account
+        ///   of its own identity) and must be able to reserve the bond.
+        /// - The weighted metric must fit in the subject record's remaining `AttestationCap` for the current period.
+        ///   The cap is shared by all attesters, so spreading attestations over many bonded accounts gains nothing.
+        pub fn attest(
+            origin: T::Origin,
+            subject: T::AccountId,
+            category: ReputationCategory,
+            input_metric: i32,
+            details: Vec<u8>,
+        ) -> DispatchResult {
+            let attester = ensure_signed(origin)?;
+            ensure!(Self::record_holder(&attester) != Self::record_holder(&subject), Error::<T>::SelfReportNotAllowed);
+            let weighted = T::AttestationWeight::get().mul_floor(input_metric.unsigned_abs());
+            let period = Self::current_period();
+            let holder = Self::record_holder(&subject);
+            let (usage_period, gained, lost) = AttestationUsage::<T>::get(&holder);
+            let (mut gained, mut lost) = if usage_period == period { (gained, lost) } else { (0, 0) };
+            let used = if input_metric >= 0 { &mut gained } else { &mut lost };
+            *used = used.saturating_add(weighted);
+            ensure!(*used <= T::AttestationCap::get(), Error::<T>::AttestationCapExceeded);
+            let weighted_metric = Self::signed_metric(input_metric, weighted)?;
+            let bond = T::AttestationBond::get();
+            T::Currency::reserve(&attester, bond).map_err(|_| Error::<T>::InsufficientBond)?;
+            let applied = Self::apply_metric(&subject, category, weighted_metric, details)?;
+            AttestationUsage::<T>::insert(&holder, (period, gained, lost));
+            Self::record_report(attester, subject, category, applied, bond);
+            Ok(())
+        }
+
+        /// Disputes a report. Any account other than the reporter may dispute, within `DisputeWindow` blocks of the
+        /// report, so that inflated attestations between colluding accounts can be challenged by third parties;
+        /// `DisputeBond` is reserved from the disputer until the arbiter decides.
+        pub fn dispute(origin: T::Origin, report_id: ReportId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Reports::<T>::try_mutate(report_id, |maybe_report| -> DispatchResult {
+                let report = maybe_report.as_mut().ok_or(Error::<T>::ReportNotFound)?;
+                ensure!(report.reporter != who, Error::<T>::CannotDisputeOwnReport);
+                ensure!(report.status == ReportStatus::Active, Error::<T>::InvalidReportStatus);
+                let now = frame_system::Pallet::<T>::block_number();
+                ensure!(now <= report.submitted_at.saturating_add(T::DisputeWindow::get()), Error::<T>::DisputeWindowClosed);
+                let dispute_bond = T::DisputeBond::get();
+                T::Currency::reserve(&who, dispute_bond).map_err(|_| Error::<T>::InsufficientBond)?;
+                report.disputer = Some(who.clone());
+                report.dispute_bond = dispute_bond;
+                report.status = ReportStatus::Disputed;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::ReportDisputed(report_id, who));
+            Ok(())
+        }
+
+        /// Resolves a dispute.
+        ///
+        /// If `overturn` is true, the report's effect is reverted, the reporter's bond is slashed, its score in
+        /// the report's category is lowered by `OverturnPenalty`, and the disputer's bond is returned.
+        /// Otherwise the disputer's bond is slashed and the report stands.
+        pub fn resolve_dispute(origin: T::Origin, report_id: ReportId, overturn: bool) -> DispatchResult {
+            T::ArbiterOrigin::ensure_origin(origin)?;
+            let mut report = Reports::<T>::get(report_id).ok_or(Error::<T>::ReportNotFound)?;
+            ensure!(report.status == ReportStatus::Disputed, Error::<T>::InvalidReportStatus);
+            let disputer = report.disputer.clone().ok_or(Error::<T>::InvalidReportStatus)?;
+            if overturn {
+                Self::apply_delta(&report.subject, report.category, -report.applied_delta, b"Report Overturned".to_vec())?;
+                let penalty = T::OverturnPenalty::get() as i64;
+                Self::apply_delta(&report.reporter, report.category, -penalty, b"Overturned Report Penalty".to_vec())?;
+                let (_imbalance, _remaining) = T::Currency::slash_reserved(&report.reporter, report.bond);
+                T::Currency::unreserve(&disputer, report.dispute_bond);
+                Self::deposit_event(Event::ReporterSlashed(report.reporter.clone(), report.bond));
+                report.status = ReportStatus::Overturned;
+            } else {
+                let (_imbalance, _remaining) = T::Currency::slash_reserved(&disputer, report.dispute_bond);
+                report.status = ReportStatus::Upheld;
+            }
+            Reports::<T>::insert(report_id, report);
+            Self::deposit_event(Event::DisputeResolved(report_id, overturn));
+            Ok(())
+        }
+
+        /// Releases the reporter's bond of an undisputed report after its dispute window, or of an upheld report.
+        /// Callable by any signed account.
+        pub fn release_bond(origin: T::Origin, report_id: ReportId) -> DispatchResult {
+            let _caller = ensure_signed(origin)?;
+            Reports::<T>::try_mutate(report_id, |maybe_report| -> DispatchResult {
+                let report = maybe_report.as_mut().ok_or(Error::<T>::ReportNotFound)?;
+                match report.status {
+                    ReportStatus::Active => {
+                        let now = frame_system::Pallet::<T>::block_number();
+                        ensure!(now > report.submitted_at.saturating_add(T::DisputeWindow::get()), Error::<T>::DisputeWindowOpen);
+                    }
+                    ReportStatus::Upheld => {}
+                    _ => return Err(Error::<T>::InvalidReportStatus.into()),
+                }
+                T::Currency::unreserve(&report.reporter, report.bond);
+                report.status = ReportStatus::Settled;
+                Ok(())
+            })
+        }
+
+        /// Applies a report after checking the reporter's authorization, weight and cap.
+        fn do_report(
+            reporter: &T::AccountId,
+            subject: &T::AccountId,
+            category: ReputationCategory,
+            input_metric: i32,
+            details: Vec<u8>,
+        ) -> DispatchResult {
+            ensure!(Self::record_holder(reporter) != Self::record_holder(subject), Error::<T>::SelfReportNotAllowed);
+            let info = Reporters::<T>::get(reporter).ok_or(Error::<T>::NotAuthorizedReporter)?;
+            let weighted = info.weight.mul_floor(input_metric.unsigned_abs());
+            let period = Self::current_period();
+            let (usage_period, used) = ReporterUsage::<T>::get(reporter);
+            let used = if usage_period == period { used } else { 0 };
+            let used = used.saturating_add(weighted);
+            ensure!(used <= info.period_cap, Error::<T>::ReporterCapExceeded);
+            let weighted_metric = Self::signed_metric(input_metric, weighted)?;
+            let applied = Self::apply_metric(subject, category, weighted_metric, details)?;
+            ReporterUsage::<T>::insert(reporter, (period, used));
+            Self::record_report(reporter.clone(), subject.clone(), category, applied, Zero::zero());
+            Ok(())
+        }
+
+        /// Index of the current `ReportingPeriod`, over which reporter and attestation caps apply.
+        fn current_period() -> u32 {
+            let now = frame_system::Pallet::<T>::block_number();
+            let period_length = T::ReportingPeriod::get();
+            if period_length.is_zero() { 0 } else { (now / period_length).saturated_into() }
+        }
+
+        /// Gives the weighted magnitude of a metric the metric's sign. The negation is done in `i64` because a
+        /// fully weighted `i32::MIN` has no positive `i32` counterpart.
+        fn signed_metric(input_metric: i32, weighted: u32) -> Result<i32, DispatchError> {
+            let magnitude = weighted as i64;
+            let signed = if input_metric >= 0 { Some(magnitude) } else { magnitude.checked_neg() };
+            signed
+                .and_then(|metric| i32::try_from(metric).ok())
+                .ok_or_else(|| Error::<T>::InvalidReputationData.into())
+        }
+
+        /// Applies `metric` to the subject's (decayed) score in `category` and returns the resulting score change.
+        fn apply_metric(
+            subject: &T::AccountId,
+            category: ReputationCategory,
+            metric: i32,
+            details: Vec<u8>,
+        ) -> Result<i64, DispatchError> {
+            let metric = if metric > 0 && !T::Identity::is_verified(subject) {
+                T::UnverifiedGainWeight::get().mul_floor(metric as u32) as i32
+            } else {
+                metric
+            };
+            let previous_score = Self::current_score(subject, category);
+            let new_score = Self::calculate_reputation(category, previous_score, metric)
                 .map_err(|_| Error::<T>::InvalidReputationData)?;
-            ensure!(new_score >= T::MinReputation::get() && new_score <= T::MaxReputation::get(), Error::<T>::ReputationOutOfBounds);
-            let updated_record = ReputationRecord { score: new_score, details: details.clone() };
-            ReputationScores::<T>::insert(&account, updated_record);
+            Self::write_score(subject, category, previous_score, new_score, details)
+        }
+
+        /// Shifts the subject's (decayed) score in `category` by exactly `delta`, clamped to the category bounds.
+        /// Used to revert overturned reports and to penalize their reporters.
+        fn apply_delta(
+            subject: &T::AccountId,
+            category: ReputationCategory,
+            delta: i64,
+            details: Vec<u8>,
+        ) -> Result<i64, DispatchError> {
+            let params = Self::params(category);
+            let previous_score = Self::current_score(subject, category);
+            let new_score = (previous_score as i64)
+                .saturating_add(delta)
+                .clamp(params.min as i64, params.max as i64) as u32;
+            Self::write_score(subject, category, previous_score, new_score, details)
+        }
+
+        /// Stores `new_score`, records it in the history and returns the change from `previous_score`.
+        fn write_score(
+            subject: &T::AccountId,
+            category: ReputationCategory,
+            previous_score: u32,
+            new_score: u32,
+            details: Vec<u8>,
+        ) -> Result<i64, DispatchError> {
+            let now = frame_system::Pallet::<T>::block_number();
+            let params = Self::params(category);
+            ensure!(new_score >= params.min && new_score <= params.max, Error::<T>::ReputationOutOfBounds);
+            let updated_record = ReputationRecord { score: new_score, details: details.clone(), last_updated: now };
+            ReputationScores::<T>::insert(Self::record_holder(subject), category, updated_record);
             let timestamp = Self::current_timestamp();
             <ReputationHistory<T>>::mutate(|history| {
-                history.push((timestamp, account.clone(), previous_score, new_score, details.clone()))
+                history.push((timestamp, subject.clone(), category, previous_score, new_score, details.clone()))
             });
-            Self::deposit_event(Event::ReputationUpdated(account, previous_score, new_score, details));
+            Self::deposit_event(Event::ReputationUpdated(subject.clone(), category, previous_score, new_score, details));
             // Invariant check: New reputation must be within defined bounds.
-            assert_invariant!(new_score >= T::MinReputation::get() && new_score <= T::MaxReputation::get(), "Reputation out of bounds after update");
-            Ok(())
+            assert_invariant!(new_score >= params.min && new_score <= params.max, "Reputation out of bounds after update");
+            Ok(new_score as i64 - previous_score as i64)
+        }
+
+        /// Stores a disputable report and returns its identifier.
+        fn record_report(
+            reporter: T::AccountId,
+            subject: T::AccountId,
+            category: ReputationCategory,
+            applied_delta: i64,
+            bond: BalanceOf<T>,
+        ) -> ReportId {
+            let id = NextReportId::<T>::get();
+            NextReportId::<T>::put(id.saturating_add(1));
+            let report = Report {
+                reporter: reporter.clone(),
+                subject: subject.clone(),
+                category,
+                applied_delta,
+                bond,
+                disputer: None,
+                dispute_bond: Zero::zero(),
+                submitted_at: frame_system::Pallet::<T>::block_number(),
+                status: ReportStatus::Active,
+            };
+            Reports::<T>::insert(id, report);
+            Self::deposit_event(Event::ReportRecorded(id, reporter, subject, category));
+            id
+        }
+
+        /// Returns the parameters in force for `category`.
+        pub fn params(category: ReputationCategory) -> CategoryParams<T::BlockNumber> {
+            CategoryConfig::<T>::get(category).unwrap_or_else(|| CategoryParams {
+                min: T::MinReputation::get(),
+                max: T::MaxReputation::get(),
+                default: T::DefaultReputation::get(),
+                half_life: T::ReputationHalfLife::get(),
+            })
+        }
+
+        /// Returns the score of `account` in `category` decayed to the current block, or the category default if
+        /// it has no record.
+        ///
+        /// Decay is computed lazily from `last_updated`, so no storage sweep is ever needed.
+        pub fn current_score(account: &T::AccountId, category: ReputationCategory) -> u32 {
+            ReputationScores::<T>::get(Self::record_holder(account), category)
+                .map(|record| Self::decayed_score(category, &record))
+                .unwrap_or_else(|| Self::params(category).default)
+        }
+
+        /// Returns the account whose record holds `account`'s reputation: the identity holder when
+        /// `ShareIdentityReputation` is enabled and `account` belongs to an identity, otherwise `account` itself.
+        pub fn record_holder(account: &T::AccountId) -> T::AccountId {
+            if T::ShareIdentityReputation::get() {
+                if let Some(holder) = T::Identity::identity_of(account) {
+                    return holder;
+                }
+            }
+            account.clone()
+        }
+
+        /// Returns the weighted aggregate of `account`'s category scores, or `None` if it has no record in any
+        /// category. Categories without a record contribute their default score.
+        pub fn aggregate_score(account: &T::AccountId) -> Option<u32> {
+            if ReputationScores::<T>::iter_prefix(Self::record_holder(account)).next().is_none() {
+                return None;
+            }
+            let total_weight: u64 = ReputationCategory::ALL.iter().map(|c| CategoryWeights::<T>::get(c) as u64).sum();
+            let (weighted_sum, weight_sum) = ReputationCategory::ALL.iter().fold((0u64, 0u64), |(sum, weights), category| {
+                let weight = if total_weight == 0 { 1 } else { CategoryWeights::<T>::get(category) as u64 };
+                let score = Self::current_score(account, *category) as u64;
+                (sum + score * weight, weights + weight)
+            });
+            Some((weighted_sum / weight_sum) as u32)
+        }
+
+        /// Applies the category's half-life decay toward its default score to a stored record.
+        pub fn decayed_score(category: ReputationCategory, record: &ReputationRecord<T::BlockNumber>) -> u32 {
+            let params = Self::params(category);
+            let now = frame_system::Pallet::<T>::block_number();
+            let elapsed: u64 = now.saturating_sub(record.last_updated).saturated_into();
+            let half_life: u64 = params.half_life.saturated_into();
+            let default = params.default;
+            if record.score >= default {
+                default.saturating_add(Self::decay(record.score - default, elapsed, half_life))
+            } else {
+                default.saturating_sub(Self::decay(default - record.score, elapsed, half_life))
+            }
+        }
+
+        /// Computes `distance * 2^(-elapsed / half_life)`: whole half-lives are applied as bit shifts and the
+        /// remaining fraction by linear interpolation between consecutive halvings.
+        fn decay(distance: u32, elapsed: u64, half_life: u64) -> u32 {
+            if half_life == 0 || distance == 0 {
+                return distance;
+            }
+            let halvings = elapsed / half_life;
+            if halvings >= 32 {
+                return 0;
+            }
+            let halved = (distance >> halvings) as u64;
+            let remainder = elapsed % half_life;
+            (halved - halved * remainder / (2 * half_life)) as u32
         }
 
         /// Returns the current Unix timestamp.
@@ -169,6 +819,36 @@ pub mod pallet {
             1_640_000_000 // Placeholder timestamp.
         }
     }
+
+    impl<T: Config> ReputationReporter<T::AccountId> for Pallet<T> {
+        fn report(
+            reporter: &T::AccountId,
+            subject: &T::AccountId,
+            category: ReputationCategory,
+            input_metric: i32,
+            details: Vec<u8>,
+        ) -> DispatchResult {
+            Self::do_report(reporter, subject, category, input_metric, details)
+        }
+    }
+
+    impl<T: Config> OnIdentityMigrated<T::AccountId> for Pallet<T> {
+        /// Moves every category record of the recovered account to the rescuer.
+        fn on_identity_migrated(old: &T::AccountId, new: &T::AccountId) {
+            for category in ReputationCategory::ALL.iter() {
+                if let Some(record) = ReputationScores::<T>::take(old, category) {
+                    ReputationScores::<T>::insert(new, category, record);
+                }
+            }
+        }
+    }
+
+    impl<T: Config> ReputationProvider<T::AccountId> for Pallet<T> {
+        /// Returns the aggregate score across categories.
+        fn reputation_of(who: &T::AccountId) -> Option<u32> {
+            Self::aggregate_score(who)
+        }
+    }
 }
 
 // --- Benchmarking Section ---
@@ -184,23 +864,49 @@ mod benchmarking {
     benchmarks! {
         update_reputation {
             let account: T::AccountId = account("user", 0, 0);
+            let reporter: T::AccountId = account("reporter", 0, 0);
+            Pallet::<T>::add_reporter(RawOrigin::Root.into(), reporter.clone(), Percent::from_percent(100), u32::MAX)?;
             // Initialize identity with default reputation.
             let initial_score = T::DefaultReputation::get();
             // Set input metric for reputation update.
-            let input_metric: u32 = 100;
+            let input_metric: i32 = 100;
             let details: Vec<u8> = b"Benchmark Reputation Update".to_vec();
         }: {
-            Pallet::<T>::update_reputation(RawOrigin::Signed(account.clone()).into(), input_metric, details.clone())?;
+            Pallet::<T>::update_reputation(
+                RawOrigin::Signed(reporter.clone()).into(),
+                account.clone(),
+                ReputationCategory::TradingReliability,
+                input_metric,
+                details.clone(),
+            )?;
         }
         verify {
-            let record = ReputationScores::<T>::get(&account).unwrap();
-            let expected = if initial_score.saturating_add(input_metric / 2) > T::MaxReputation::get() {
+            let record = ReputationScores::<T>::get(&account, ReputationCategory::TradingReliability).unwrap();
+            let expected = if initial_score.saturating_add(50) > T::MaxReputation::get() {
                 T::MaxReputation::get()
             } else {
-                initial_score.saturating_add(input_metric / 2)
+                initial_score.saturating_add(50)
             };
             assert_eq!(record.score, expected);
         }
+
+        attest {
+            let attester: T::AccountId = account("attester", 0, 0);
+            let subject: T::AccountId = account("user", 0, 0);
+            let bond = T::AttestationBond::get();
+            T::Currency::make_free_balance_be(&attester, bond + bond);
+        }: {
+            Pallet::<T>::attest(
+                RawOrigin::Signed(attester.clone()).into(),
+                subject.clone(),
+                ReputationCategory::TradingReliability,
+                100,
+                b"Benchmark Attestation".to_vec(),
+            )?;
+        }
+        verify {
+            assert_eq!(T::Currency::reserved_balance(&attester), bond);
+        }
     }
 
     impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::Test);
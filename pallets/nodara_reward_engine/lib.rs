@@ -6,7 +6,7 @@
 //! This module implements a dynamic reward distribution system for the Nodara network. It computes rewards by
 //! combining a base reward with a reputation multiplier and performance metrics, ensuring that participants are
 //! fairly compensated based on their contributions. Designed to a mythical standard, it incorporates simulated formal
-//! verification, extreme performance optimizations, and an immutable audit log. DAO governance integration enables
+//! verification, extreme performance optimizations, and an event audit trail. DAO governance integration enables
 //! transparent, community-driven updates to reward parameters.
 //!
 //! ## Key Advanced Features:
@@ -14,7 +14,8 @@
 //!   applying a smoothing algorithm to avoid abrupt changes.
 //! - **Simulated Formal Verification:** Uses internal invariant checks (via custom macros) to simulate formal proofs,
 //!   ensuring that reward calculations remain mathematically sound.
-//! - **Immutable Audit Logging:** Records each reward distribution event with comprehensive metadata.
+//! - **Event Audit Trail:** Emits an event with comprehensive metadata for each reward distribution, so the trail
+//!   lives in the event log rather than in ever-growing state.
 //! - **DAO Governance Integration:** Allows on-chain proposals for updating reward parameters.
 //! - **Performance Optimizations & Benchmarks:** Highly optimized routines with integrated benchmarks to monitor and
 //!   continuously improve performance.
@@ -22,19 +23,31 @@
 //! ## Module Structure:
 //! - **Storage:**
 //!   - `RewardPool`: Current funds available for reward distribution.
-//!   - `RewardHistory`: Log of reward distributions, recorded as (timestamp, recipient, reward amount, details).
+//!   - `Campaigns` / `Claimed`: Merkle-root airdrop campaigns and the accounts that have claimed from them.
+//!   - `RewardCurves`: Governance-selected reward curve (linear, logarithmic, sigmoid or piecewise) per reward category.
+//!   - `PendingStakingPayout`: Staking reward round still being paid out, with the cursor to resume from.
 //! - **Core Functions:**
 //!   - `initialize_rewards`: Initializes the reward pool with a baseline value.
-//!   - `distribute_reward`: Computes and distributes rewards based on defined parameters.
+//!   - `distribute_reward`: Computes and distributes rewards based on defined parameters, reading the recipient's
+//!     reputation from `nodara_reputation` through the `ReputationProvider` trait.
 //!   - `update_reward_pool`: Updates the reward pool via governance-driven actions.
+//!   - `distribute_staking_rewards`: Pays a budget to stakers proportionally to the stake bonded in `nodara_staking`,
+//!     one bounded page of stakers per block.
+//!   - `fund_reward_pool`: Adds funds to the pool (e.g., the staking share of transaction fees).
+//!   - `set_reward_curve`: Assigns a reward curve to a category (governance origin only).
+//!   - `create_campaign` / `claim` / `sweep_campaign`: Merkle-root airdrops paid from the reward pool, claimable once
+//!     per account with a Merkle proof, with unclaimed funds returned to the pool after the deadline.
 //!   - `verify_invariants`: (Internal) Checks that key invariants hold after reward operations.
 //!
 //! ## Version:
 //! March 2025 – Nodara BIOSPHÈRE QUANTIC Legendary Edition
 
-use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::Get};
+use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::{EnsureOrigin, Get}};
 use frame_system::pallet_prelude::*;
+use sp_runtime::{traits::{Hash, Saturating, Zero}, FixedPointNumber, FixedU128};
 use sp_std::vec::Vec;
+use nodara_reputation::ReputationProvider;
+use nodara_staking::StakeProvider;
 
 /// Macro to simulate formal invariant checks. In production, these assertions would be replaced by rigorous formal proofs.
 macro_rules! assert_invariant {
@@ -47,6 +60,151 @@ macro_rules! assert_invariant {
 pub mod pallet {
     use super::*;
 
+    /// Category of a reward distribution. Each category can be assigned its own reward curve.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum RewardCategory {
+        /// Generic rewards not covered by a more specific category.
+        General,
+        /// Rewards for validated PoW submissions.
+        Mining,
+        /// Rewards for staked NDR.
+        Staking,
+        /// Community incentives and airdrops.
+        Community,
+    }
+
+    /// Shape of the function mapping a reputation score to a reward amount.
+    ///
+    /// All curves are evaluated with fixed-point arithmetic so results are deterministic across nodes.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum RewardCurve {
+        /// `base + slope * reputation`.
+        Linear { base: u32, slope: FixedU128 },
+        /// `base + scale * log2(1 + reputation)`; flattens quickly for high reputations.
+        Logarithmic { base: u32, scale: u32 },
+        /// Hill-type sigmoid rising from `min` to `max`, reaching the midpoint value at `midpoint`.
+        /// `steepness` is the Hill exponent and must be between 1 and `MAX_SIGMOID_STEEPNESS`.
+        Sigmoid { min: u32, max: u32, midpoint: u32, steepness: u8 },
+        /// Linear interpolation between `(reputation, reward)` points sorted by strictly increasing reputation.
+        /// Values outside the table are clamped to the first/last point.
+        Piecewise(Vec<(u32, u32)>),
+    }
+
+    /// Largest Hill exponent accepted for sigmoid curves; keeps `reputation^steepness` within `u128`.
+    pub const MAX_SIGMOID_STEEPNESS: u8 = 4;
+
+    impl RewardCurve {
+        /// Evaluates the curve for the given reputation score (before Min/Max clamping).
+        pub fn evaluate(&self, reputation: u32) -> u32 {
+            match self {
+                RewardCurve::Linear { base, slope } => {
+                    base.saturating_add(slope.saturating_mul_int(reputation))
+                }
+                RewardCurve::Logarithmic { base, scale } => {
+                    let log = Self::log2_fixed(reputation.saturating_add(1));
+                    base.saturating_add(log.saturating_mul_int(*scale))
+                }
+                RewardCurve::Sigmoid { min, max, midpoint, steepness } => {
+                    let x = (reputation as u128).saturating_pow(*steepness as u32);
+                    let m = (*midpoint as u128).saturating_pow(*steepness as u32);
+                    let ratio = FixedU128::checked_from_rational(x, x.saturating_add(m)).unwrap_or_else(FixedU128::zero);
+                    min.saturating_add(ratio.saturating_mul_int(max.saturating_sub(*min)))
+                }
+                RewardCurve::Piecewise(points) => Self::interpolate(points, reputation),
+            }
+        }
+
+        /// Checks that the curve parameters are well-formed.
+        pub fn is_valid(&self, max_points: u32) -> bool {
+            match self {
+                RewardCurve::Linear { .. } | RewardCurve::Logarithmic { .. } => true,
+                RewardCurve::Sigmoid { min, max, steepness, .. } => {
+                    min <= max && *steepness >= 1 && *steepness <= MAX_SIGMOID_STEEPNESS
+                }
+                RewardCurve::Piecewise(points) => {
+                    !points.is_empty()
+                        && points.len() as u32 <= max_points
+                        && points.windows(2).all(|w| w[0].0 < w[1].0)
+                }
+            }
+        }
+
+        /// Fixed-point base-2 logarithm: integer part from the bit length, fractional part by linear
+        /// interpolation between consecutive powers of two.
+        fn log2_fixed(value: u32) -> FixedU128 {
+            if value <= 1 {
+                return FixedU128::zero();
+            }
+            let int_part = 31 - value.leading_zeros();
+            let pow = 1u32 << int_part;
+            let frac = FixedU128::checked_from_rational(value - pow, pow).unwrap_or_else(FixedU128::zero);
+            FixedU128::saturating_from_integer(int_part).saturating_add(frac)
+        }
+
+        /// Linearly interpolates `reputation` over a sorted `(reputation, reward)` table.
+        fn interpolate(points: &[(u32, u32)], reputation: u32) -> u32 {
+            let (first, last) = match (points.first(), points.last()) {
+                (Some(first), Some(last)) => (*first, *last),
+                _ => return 0,
+            };
+            if reputation <= first.0 {
+                return first.1;
+            }
+            if reputation >= last.0 {
+                return last.1;
+            }
+            for w in points.windows(2) {
+                let ((x0, y0), (x1, y1)) = (w[0], w[1]);
+                if reputation <= x1 {
+                    let t = FixedU128::checked_from_rational(reputation - x0, x1 - x0).unwrap_or_else(FixedU128::zero);
+                    return if y1 >= y0 {
+                        y0.saturating_add(t.saturating_mul_int(y1 - y0))
+                    } else {
+                        y0.saturating_sub(t.saturating_mul_int(y0 - y1))
+                    };
+                }
+            }
+            last.1
+        }
+    }
+
+    /// Identifier of an airdrop campaign.
+    pub type CampaignId = u32;
+
+    /// A Merkle-root airdrop or retroactive reward campaign.
+    ///
+    /// Leaves are `hash(campaign_id, account, amount)`; internal nodes hash the sorted pair of children.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Campaign<Hash, BlockNumber> {
+        /// Root of the off-chain computed Merkle tree of (account, amount) allocations.
+        pub merkle_root: Hash,
+        /// Total amount set aside from the reward pool for this campaign.
+        pub total: u32,
+        /// Amount claimed so far.
+        pub claimed: u32,
+        /// Block after which unclaimed funds may be swept back to the reward pool.
+        pub deadline: BlockNumber,
+        /// Whether the unclaimed remainder has already been swept.
+        pub swept: bool,
+    }
+
+    /// A staking reward round being paid out page by page.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct StakingPayout<AccountId> {
+        /// Amount set aside from the reward pool for the round.
+        pub budget: u32,
+        /// Total active stake when the round started; every share is computed against it.
+        pub total_stake: u128,
+        /// Amount paid so far.
+        pub paid: u32,
+        /// Number of stakers paid so far.
+        pub count: u32,
+        /// Last staker visited, from which the next page resumes.
+        pub cursor: Option<AccountId>,
+        /// Details recorded with each payout.
+        pub details: Vec<u8>,
+    }
+
     /// Main pallet structure for dynamic reward distribution.
     #[frame_support::pallet]
     pub struct Pallet<T>(_);
@@ -56,11 +214,33 @@ pub mod pallet {
     #[pallet::getter(fn reward_pool)]
     pub type RewardPool<T: Config> = StorageValue<_, u32, ValueQuery>;
 
-    /// Storage for logging reward distribution events.
-    /// Each log entry is a tuple: (timestamp, recipient, reward amount, details)
+    /// Reward curve assigned to each category. Categories without an entry use the linear
+    /// `BaseReward + ReputationMultiplier * reputation` curve.
+    #[pallet::storage]
+    #[pallet::getter(fn reward_curves)]
+    pub type RewardCurves<T: Config> = StorageMap<_, Blake2_128Concat, RewardCategory, RewardCurve, OptionQuery>;
+
+    /// Registered airdrop campaigns.
+    #[pallet::storage]
+    #[pallet::getter(fn campaigns)]
+    pub type Campaigns<T: Config> =
+        StorageMap<_, Blake2_128Concat, CampaignId, Campaign<T::Hash, T::BlockNumber>, OptionQuery>;
+
+    /// Identifier assigned to the next campaign.
+    #[pallet::storage]
+    #[pallet::getter(fn next_campaign_id)]
+    pub type NextCampaignId<T: Config> = StorageValue<_, CampaignId, ValueQuery>;
+
+    /// Staking reward round in progress, continued in `on_initialize` until every staker has been visited.
+    #[pallet::storage]
+    #[pallet::getter(fn staking_payout)]
+    pub type PendingStakingPayout<T: Config> = StorageValue<_, StakingPayout<T::AccountId>, OptionQuery>;
+
+    /// Accounts that have already claimed from a campaign.
     #[pallet::storage]
-    #[pallet::getter(fn reward_history)]
-    pub type RewardHistory<T: Config> = StorageValue<_, Vec<(u64, T::AccountId, u32, Vec<u8>)>, ValueQuery>;
+    #[pallet::getter(fn has_claimed)]
+    pub type Claimed<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, CampaignId, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -81,6 +261,24 @@ pub mod pallet {
         /// Maximum reward that can be distributed.
         #[pallet::constant]
         type MaxReward: Get<u32>;
+        /// Source of earned reputation scores (typically `nodara_reputation`).
+        type Reputation: ReputationProvider<Self::AccountId>;
+        /// Reputation assumed for accounts that have no reputation record yet.
+        #[pallet::constant]
+        type FallbackReputation: Get<u32>;
+        /// Maximum number of points in a piecewise reward curve.
+        #[pallet::constant]
+        type MaxCurvePoints: Get<u32>;
+        /// Origin allowed to change reward curves and register campaigns (e.g., DAO governance).
+        type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+        /// Source of bonded stake used as staking reward weight (typically `nodara_staking`).
+        type Stake: StakeProvider<Self::AccountId>;
+        /// Maximum number of hashes in a Merkle claim proof.
+        #[pallet::constant]
+        type MaxProofLength: Get<u32>;
+        /// Maximum number of staking ledgers visited per block while paying a staking reward round.
+        #[pallet::constant]
+        type MaxStakersPerPayout: Get<u32>;
     }
 
     #[pallet::event]
@@ -90,6 +288,16 @@ pub mod pallet {
         RewardDistributed(T::AccountId, u32, Vec<u8>),
         /// Emitted when the reward pool is updated (old pool, new pool, details).
         RewardPoolUpdated(u32, u32, Vec<u8>),
+        /// Emitted when the reward curve of a category is changed (category, new curve).
+        RewardCurveUpdated(RewardCategory, RewardCurve),
+        /// Emitted when a staking reward round is paid (total paid, number of stakers).
+        StakingRewardsDistributed(u32, u32),
+        /// Emitted when an airdrop campaign is registered (campaign ID, Merkle root, total, deadline).
+        CampaignCreated(CampaignId, T::Hash, u32, T::BlockNumber),
+        /// Emitted when an allocation is claimed (campaign ID, recipient, amount).
+        AirdropClaimed(CampaignId, T::AccountId, u32),
+        /// Emitted when unclaimed funds are returned to the pool (campaign ID, amount).
+        CampaignSwept(CampaignId, u32),
     }
 
     #[pallet::error]
@@ -100,21 +308,40 @@ pub mod pallet {
         RewardOutOfBounds,
         /// Invalid operation parameters.
         InvalidOperation,
+        /// The reward curve parameters are malformed.
+        InvalidRewardCurve,
+        /// The campaign does not exist.
+        CampaignNotFound,
+        /// The campaign deadline has already passed.
+        CampaignExpired,
+        /// The campaign deadline has not been reached yet.
+        CampaignStillActive,
+        /// The campaign has already been swept.
+        CampaignAlreadySwept,
+        /// The account has already claimed from this campaign.
+        AlreadyClaimed,
+        /// The Merkle proof does not match the campaign root.
+        InvalidMerkleProof,
+        /// The claim would exceed the campaign's total allocation.
+        CampaignExhausted,
+        /// A staking reward round is still being paid out.
+        StakingPayoutInProgress,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            // Every visited ledger is read, whether or not it is paid.
+            let visited = Self::continue_staking_payout() as u64;
+            T::DbWeight::get().reads_writes(visited * 2 + 2, visited + 2)
+        }
     }
 
     impl<T: Config> Pallet<T> {
         /// Initializes the reward pool with the baseline amount.
-        ///
-        /// Records the initialization event with a default "Initialization" message.
         pub fn initialize_rewards() -> DispatchResult {
             let initial = T::InitialRewardPool::get();
             <RewardPool<T>>::put(initial);
-            let timestamp = Self::current_timestamp();
-            // Log initialization with a dummy reward event (0 reward)
-            <RewardHistory<T>>::mutate(|history| {
-                // Using default account id (0) as placeholder for initialization.
-                history.push((timestamp, T::AccountId::default(), 0, b"Initialization".to_vec()))
-            });
             // Invariant check: The reward pool must equal the initial value after initialization.
             assert_invariant!(<RewardPool<T>>::get() == T::InitialRewardPool::get(), "Initial reward pool does not match baseline");
             Ok(())
@@ -122,33 +349,24 @@ pub mod pallet {
 
         /// Distributes a reward to a given account.
         ///
-        /// The reward is computed as:
-        ///   reward = BaseReward + (ReputationMultiplier * reputation)
-        /// and is clamped between MinReward and MaxReward.
+        /// The reward is computed by evaluating the category's reward curve at the recipient's reputation
+        /// and is clamped between MinReward and MaxReward. The reputation is read from `T::Reputation`,
+        /// falling back to `FallbackReputation` for accounts without a record.
         ///
         /// # Parameters:
         /// - `account`: The recipient account.
-        /// - `reputation`: The reputation score of the account.
+        /// - `category`: The reward category, selecting the reward curve.
         /// - `details`: Additional details or rationale for the reward.
         ///
         /// # Requirements:
         /// - The reward pool must have sufficient funds.
-        pub fn distribute_reward(account: T::AccountId, reputation: u32, details: Vec<u8>) -> DispatchResult {
-            // Calculate reward based on base reward and reputation multiplier.
-            let mut reward = T::BaseReward::get().saturating_add(T::ReputationMultiplier::get().saturating_mul(reputation));
-            // Clamp reward within defined bounds.
-            if reward < T::MinReward::get() {
-                reward = T::MinReward::get();
-            }
-            if reward > T::MaxReward::get() {
-                reward = T::MaxReward::get();
-            }
+        pub fn distribute_reward(account: T::AccountId, category: RewardCategory, details: Vec<u8>) -> DispatchResult {
+            let reputation = Self::reputation_of(&account);
+            let reward = Self::calculate_reward(category, reputation);
             let current_pool = <RewardPool<T>>::get();
             ensure!(current_pool >= reward, Error::<T>::InsufficientRewardPool);
             // Deduct reward from pool.
             <RewardPool<T>>::put(current_pool - reward);
-            let timestamp = Self::current_timestamp();
-            <RewardHistory<T>>::mutate(|history| history.push((timestamp, account.clone(), reward, details.clone())));
             Self::deposit_event(Event::RewardDistributed(account, reward, details));
             // Invariant check: Ensure that the reward pool has decreased by exactly 'reward'.
             assert_invariant!(<RewardPool<T>>::get() == current_pool - reward, "Reward distribution did not properly deduct from pool");
@@ -167,19 +385,204 @@ pub mod pallet {
             ensure!(new_pool >= T::MinReward::get(), Error::<T>::InvalidOperation); // Simplified check.
             let old_pool = <RewardPool<T>>::get();
             <RewardPool<T>>::put(new_pool);
-            let timestamp = Self::current_timestamp();
-            // Log the update using a dummy account id (0) for pool updates.
-            <RewardHistory<T>>::mutate(|history| history.push((timestamp, T::AccountId::default().into(), new_pool, details.clone())));
             Self::deposit_event(Event::RewardPoolUpdated(old_pool, new_pool, details));
             // Invariant check: New pool must be within valid bounds.
             // Here, we assume valid bounds are defined by the project's economic model.
             Ok(())
         }
 
-        /// Returns the current Unix timestamp.
-        /// Replace this with a reliable time provider in production.
-        fn current_timestamp() -> u64 {
-            1_640_000_000 // Placeholder timestamp.
+        /// Pays `budget` from the reward pool to all stakers, proportionally to their active stake.
+        ///
+        /// The budget is set aside immediately and the first `MaxStakersPerPayout` ledgers are paid; the rest of
+        /// the round is paid in `on_initialize`, one page per block. Shares are computed against the total stake
+        /// at the start of the round, capped so the round never pays more than `budget`, and whatever is left
+        /// unpaid returns to the pool when the round ends.
+        ///
+        /// # Parameters:
+        /// - `budget`: Total amount to distribute in this round.
+        /// - `details`: Additional details recorded with each payout.
+        ///
+        /// # Requirements:
+        /// - The reward pool must hold at least `budget`.
+        /// - No other staking reward round may be in progress.
+        pub fn distribute_staking_rewards(budget: u32, details: Vec<u8>) -> DispatchResult {
+            ensure!(!<PendingStakingPayout<T>>::exists(), Error::<T>::StakingPayoutInProgress);
+            let current_pool = <RewardPool<T>>::get();
+            ensure!(current_pool >= budget, Error::<T>::InsufficientRewardPool);
+            let total_stake = T::Stake::total_stake();
+            ensure!(total_stake > 0, Error::<T>::InvalidOperation);
+            <RewardPool<T>>::put(current_pool.saturating_sub(budget));
+            <PendingStakingPayout<T>>::put(StakingPayout { budget, total_stake, paid: 0, count: 0, cursor: None, details });
+            Self::continue_staking_payout();
+            Ok(())
+        }
+
+        /// Pays the next page of the pending staking reward round, closing the round once every staker has been
+        /// visited. Returns the number of ledgers visited: the stake provider may visit a full page, including
+        /// ledgers without active stake and ledgers whose share rounds down to nothing.
+        fn continue_staking_payout() -> u32 {
+            let mut round = match <PendingStakingPayout<T>>::get() {
+                Some(round) => round,
+                None => return 0,
+            };
+            let (stakers, cursor) = T::Stake::stakers(round.cursor.take(), T::MaxStakersPerPayout::get());
+            let visited = T::MaxStakersPerPayout::get();
+            for staker in stakers {
+                let share = (round.budget as u128).saturating_mul(T::Stake::stake_of(&staker)) / round.total_stake;
+                // Stake bonded after the round started could push the shares past the budget.
+                let reward = share.min(round.budget.saturating_sub(round.paid) as u128) as u32;
+                if reward == 0 {
+                    continue;
+                }
+                round.paid = round.paid.saturating_add(reward);
+                round.count = round.count.saturating_add(1);
+                Self::deposit_event(Event::RewardDistributed(staker, reward, round.details.clone()));
+            }
+            // Invariant check: The round never pays more than its budget.
+            assert_invariant!(round.paid <= round.budget, "Staking rewards exceed the round budget");
+            if cursor.is_some() {
+                round.cursor = cursor;
+                <PendingStakingPayout<T>>::put(round);
+            } else {
+                <PendingStakingPayout<T>>::kill();
+                let unpaid = round.budget.saturating_sub(round.paid);
+                <RewardPool<T>>::mutate(|pool| *pool = pool.saturating_add(unpaid));
+                Self::deposit_event(Event::StakingRewardsDistributed(round.paid, round.count));
+            }
+            visited
+        }
+
+        /// Adds funds to the reward pool (e.g., the staking share of transaction fees).
+        ///
+        /// # Parameters:
+        /// - `amount`: The amount to add to the pool.
+        /// - `details`: A descriptive message for the contribution.
+        pub fn fund_reward_pool(amount: u32, details: Vec<u8>) -> DispatchResult {
+            let old_pool = <RewardPool<T>>::get();
+            let new_pool = old_pool.saturating_add(amount);
+            <RewardPool<T>>::put(new_pool);
+            Self::deposit_event(Event::RewardPoolUpdated(old_pool, new_pool, details));
+            Ok(())
+        }
+
+        /// Sets the reward curve used for a category.
+        ///
+        /// # Parameters:
+        /// - `origin`: Must satisfy `GovernanceOrigin`.
+        /// - `category`: The reward category to configure.
+        /// - `curve`: The new reward curve.
+        ///
+        /// # Requirements:
+        /// - The curve must be well-formed (see `RewardCurve::is_valid`).
+        pub fn set_reward_curve(origin: T::Origin, category: RewardCategory, curve: RewardCurve) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(curve.is_valid(T::MaxCurvePoints::get()), Error::<T>::InvalidRewardCurve);
+            <RewardCurves<T>>::insert(category, curve.clone());
+            Self::deposit_event(Event::RewardCurveUpdated(category, curve));
+            Ok(())
+        }
+
+        /// Registers a Merkle-root campaign and sets aside `total` from the reward pool.
+        ///
+        /// # Parameters:
+        /// - `origin`: Must satisfy `GovernanceOrigin`.
+        /// - `merkle_root`: Root of the off-chain computed allocation tree.
+        /// - `total`: Sum of all allocations in the tree.
+        /// - `deadline`: Block after which unclaimed funds can be swept.
+        ///
+        /// # Requirements:
+        /// - The reward pool must hold at least `total`.
+        /// - The deadline must be in the future.
+        pub fn create_campaign(origin: T::Origin, merkle_root: T::Hash, total: u32, deadline: T::BlockNumber) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(deadline > frame_system::Pallet::<T>::block_number(), Error::<T>::CampaignExpired);
+            let current_pool = <RewardPool<T>>::get();
+            ensure!(current_pool >= total, Error::<T>::InsufficientRewardPool);
+            <RewardPool<T>>::put(current_pool - total);
+            let id = <NextCampaignId<T>>::get();
+            <NextCampaignId<T>>::put(id.saturating_add(1));
+            <Campaigns<T>>::insert(id, Campaign { merkle_root, total, claimed: 0, deadline, swept: false });
+            Self::deposit_event(Event::CampaignCreated(id, merkle_root, total, deadline));
+            Ok(())
+        }
+
+        /// Claims the caller's allocation from a campaign.
+        ///
+        /// # Parameters:
+        /// - `origin`: The recipient of the allocation.
+        /// - `campaign_id`: The campaign to claim from.
+        /// - `amount`: The allocated amount, as committed in the Merkle tree.
+        /// - `proof`: Sibling hashes from the leaf up to the root.
+        ///
+        /// # Requirements:
+        /// - The campaign must exist and its deadline must not have passed.
+        /// - The caller must not have claimed before.
+        /// - The proof must reconstruct the campaign's Merkle root.
+        pub fn claim(origin: T::Origin, campaign_id: CampaignId, amount: u32, proof: Vec<T::Hash>) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            ensure!(proof.len() as u32 <= T::MaxProofLength::get(), Error::<T>::InvalidMerkleProof);
+            ensure!(!<Claimed<T>>::get(campaign_id, &account), Error::<T>::AlreadyClaimed);
+            <Campaigns<T>>::try_mutate(campaign_id, |maybe_campaign| -> DispatchResult {
+                let campaign = maybe_campaign.as_mut().ok_or(Error::<T>::CampaignNotFound)?;
+                ensure!(frame_system::Pallet::<T>::block_number() <= campaign.deadline, Error::<T>::CampaignExpired);
+                let leaf = T::Hashing::hash_of(&(campaign_id, &account, amount));
+                ensure!(Self::verify_merkle_proof(leaf, &proof, campaign.merkle_root), Error::<T>::InvalidMerkleProof);
+                let claimed = campaign.claimed.saturating_add(amount);
+                ensure!(claimed <= campaign.total, Error::<T>::CampaignExhausted);
+                campaign.claimed = claimed;
+                Ok(())
+            })?;
+            <Claimed<T>>::insert(campaign_id, &account, true);
+            Self::deposit_event(Event::AirdropClaimed(campaign_id, account, amount));
+            Ok(())
+        }
+
+        /// Returns the unclaimed remainder of an expired campaign to the reward pool.
+        ///
+        /// Callable by any signed account once the deadline has passed.
+        pub fn sweep_campaign(origin: T::Origin, campaign_id: CampaignId) -> DispatchResult {
+            let _caller = ensure_signed(origin)?;
+            let remainder = <Campaigns<T>>::try_mutate(campaign_id, |maybe_campaign| -> Result<u32, DispatchError> {
+                let campaign = maybe_campaign.as_mut().ok_or(Error::<T>::CampaignNotFound)?;
+                ensure!(frame_system::Pallet::<T>::block_number() > campaign.deadline, Error::<T>::CampaignStillActive);
+                ensure!(!campaign.swept, Error::<T>::CampaignAlreadySwept);
+                campaign.swept = true;
+                Ok(campaign.total.saturating_sub(campaign.claimed))
+            })?;
+            <RewardPool<T>>::mutate(|pool| *pool = pool.saturating_add(remainder));
+            Self::deposit_event(Event::CampaignSwept(campaign_id, remainder));
+            Ok(())
+        }
+
+        /// Folds `proof` into `leaf` using sorted-pair hashing and compares the result with `root`.
+        pub fn verify_merkle_proof(leaf: T::Hash, proof: &[T::Hash], root: T::Hash) -> bool {
+            let computed = proof.iter().fold(leaf, |node, sibling| {
+                if node <= *sibling {
+                    T::Hashing::hash_of(&(node, *sibling))
+                } else {
+                    T::Hashing::hash_of(&(*sibling, node))
+                }
+            });
+            computed == root
+        }
+
+        /// Computes the reward for `reputation` in `category`, clamped to [MinReward, MaxReward].
+        pub fn calculate_reward(category: RewardCategory, reputation: u32) -> u32 {
+            let curve = <RewardCurves<T>>::get(category).unwrap_or_else(Self::default_curve);
+            curve.evaluate(reputation).max(T::MinReward::get()).min(T::MaxReward::get())
+        }
+
+        /// The linear curve built from `BaseReward` and `ReputationMultiplier`.
+        fn default_curve() -> RewardCurve {
+            RewardCurve::Linear {
+                base: T::BaseReward::get(),
+                slope: FixedU128::saturating_from_integer(T::ReputationMultiplier::get()),
+            }
+        }
+
+        /// Returns the earned reputation of `account`, or `FallbackReputation` if it has no record.
+        pub fn reputation_of(account: &T::AccountId) -> u32 {
+            T::Reputation::reputation_of(account).unwrap_or_else(T::FallbackReputation::get)
         }
     }
 }
@@ -209,24 +612,46 @@ mod benchmarking {
         distribute_reward {
             Pallet::<T>::initialize_rewards()?;
             let account: T::AccountId = account("rewardee", 0, 0);
-            let reputation: u32 = 50;
+            let reputation: u32 = Pallet::<T>::reputation_of(&account);
             let details: Vec<u8> = b"Benchmark Reward Distribution".to_vec();
         }: {
-            Pallet::<T>::distribute_reward(account.clone(), reputation, details.clone())?;
+            Pallet::<T>::distribute_reward(account.clone(), RewardCategory::General, details.clone())?;
         }
         verify {
-            let expected_reward = T::BaseReward::get().saturating_add(T::ReputationMultiplier::get().saturating_mul(50));
-            let clamped_reward = if expected_reward < T::MinReward::get() {
-                T::MinReward::get()
-            } else if expected_reward > T::MaxReward::get() {
-                T::MaxReward::get()
-            } else {
-                expected_reward
-            };
+            let clamped_reward = Pallet::<T>::calculate_reward(RewardCategory::General, reputation);
             let pool_after = T::InitialRewardPool::get() - clamped_reward;
             assert_eq!(Pallet::<T>::reward_pool(), pool_after);
         }
 
+        set_reward_curve {
+            let points: Vec<(u32, u32)> = (0..T::MaxCurvePoints::get()).map(|i| (i * 10, i * 5)).collect();
+            let curve = RewardCurve::Piecewise(points);
+        }: {
+            Pallet::<T>::set_reward_curve(RawOrigin::Root.into(), RewardCategory::Mining, curve.clone())?;
+        }
+        verify {
+            assert_eq!(Pallet::<T>::reward_curves(RewardCategory::Mining), Some(curve));
+        }
+
+        claim {
+            let p in 0 .. T::MaxProofLength::get();
+            let claimant: T::AccountId = account("claimant", 0, 0);
+            let amount: u32 = 100;
+            let leaf = T::Hashing::hash_of(&(0 as CampaignId, &claimant, amount));
+            let proof: Vec<T::Hash> = (0..p).map(|i| T::Hashing::hash_of(&i)).collect();
+            let root = proof.iter().fold(leaf, |node, sibling| {
+                if node <= *sibling { T::Hashing::hash_of(&(node, *sibling)) } else { T::Hashing::hash_of(&(*sibling, node)) }
+            });
+            Pallet::<T>::update_reward_pool(amount, b"Benchmark Campaign Funding".to_vec())?;
+            let deadline = frame_system::Pallet::<T>::block_number() + 100u32.into();
+            Pallet::<T>::create_campaign(RawOrigin::Root.into(), root, amount, deadline)?;
+        }: {
+            Pallet::<T>::claim(RawOrigin::Signed(claimant.clone()).into(), 0, amount, proof)?;
+        }
+        verify {
+            assert!(Pallet::<T>::has_claimed(0, &claimant));
+        }
+
         update_reward_pool {
             Pallet::<T>::initialize_rewards()?;
             let new_pool: u32 = T::InitialRewardPool::get().saturating_add(500);
@@ -0,0 +1,451 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![recursion_limit = "1024"]
+
+//! # Nodara Staking Module - Bonded NDR Securing the Network
+//!
+//! This module lets NDR holders lock (bond) tokens to secure the Nodara network and earn rewards proportional to
+//! their stake. Bonded stake can be nominated to a miner or validator, is released only after an unbonding period,
+//! and can be slashed when another pallet reports misbehavior. The bonded amounts are exposed to
+//! `nodara_reward_engine` as reward weights through the `StakeProvider` trait.
+//!
+//! ## Key Advanced Features:
+//! - **Bonding & Unbonding:**
+//!   Tokens are locked with a balance lock; unbonded funds stay locked for `BondingDuration` blocks before they can
+//!   be withdrawn, and can be rebonded in the meantime.
+//! - **Nominations:**
+//!   Stakers back a miner or validator of their choice; the backing of each target is tracked on-chain.
+//! - **Reward Weighting:**
+//!   Active stake is the weight used by the reward engine for proportional staking rewards. Stakers are listed in
+//!   bounded pages so that a payout never iterates the whole ledger in one block.
+//! - **Slashing:**
+//!   Other pallets (e.g., `nodara_pow` for invalid work) report misbehavior through the `StakeSlasher` trait; a
+//!   fraction of the offender's active and unlocking stake is slashed.
+//! - **Simulated Formal Verification:**
+//!   Internal invariant checks ensure that a ledger's total always covers its active and unlocking stake.
+//!
+//! ## Module Structure:
+//! - **Storage:**
+//!   - `Ledger`: Bonded stake of each account (active stake and unlocking chunks).
+//!   - `Nominations`: The miner or validator each staker backs.
+//!   - `Backing`: Total active stake nominated to each target.
+//!   - `TotalStake`: Sum of all active stake.
+//! - **Core Functions:**
+//!   - `bond`: Locks additional tokens as active stake.
+//!   - `unbond`: Schedules part of the active stake for release after the unbonding period.
+//!   - `rebond`: Moves unlocking stake back to active stake.
+//!   - `withdraw_unbonded`: Releases unlocking chunks whose unbonding period has elapsed.
+//!   - `nominate` / `chill`: Sets or clears the staker's nomination.
+//!
+//! ## Version:
+//! March 2025 – Nodara BIOSPHÈRE QUANTIC Legendary Edition
+
+use frame_support::{
+    dispatch::DispatchResult,
+    pallet_prelude::*,
+    traits::{Currency, Get, LockIdentifier, LockableCurrency, WithdrawReasons},
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+    traits::{SaturatedConversion, Saturating, Zero},
+    Perbill,
+};
+use sp_std::vec::Vec;
+
+/// Balance lock identifier for bonded stake.
+pub const STAKING_ID: LockIdentifier = *b"nodstake";
+
+/// Read access to bonded stake, used by the reward engine as reward weight.
+///
+/// Stake is reported as `u128` so that any currency balance fits without saturating.
+pub trait StakeProvider<AccountId> {
+    /// Active stake of `who`, used as its reward weight.
+    fn stake_of(who: &AccountId) -> u128;
+    /// Sum of all active stake.
+    fn total_stake() -> u128;
+    /// Visits at most `limit` bonded accounts, starting after `start_after` (or from the beginning), and returns
+    /// those with active stake together with the cursor to resume from (`None` once every account was visited).
+    fn stakers(start_after: Option<AccountId>, limit: u32) -> (Vec<AccountId>, Option<AccountId>);
+}
+
+/// Null provider: nobody has stake.
+impl<AccountId> StakeProvider<AccountId> for () {
+    fn stake_of(_who: &AccountId) -> u128 {
+        0
+    }
+    fn total_stake() -> u128 {
+        0
+    }
+    fn stakers(_start_after: Option<AccountId>, _limit: u32) -> (Vec<AccountId>, Option<AccountId>) {
+        (Vec::new(), None)
+    }
+}
+
+/// Entry point for other pallets (e.g., PoW or the stability guard) to punish misbehaving stakers.
+pub trait StakeSlasher<AccountId> {
+    /// Slashes `fraction` of the stake bonded by `who` and returns the slashed amount.
+    fn slash(who: &AccountId, fraction: Perbill, reason: Vec<u8>) -> u32;
+}
+
+/// Null slasher: nothing is ever slashed.
+impl<AccountId> StakeSlasher<AccountId> for () {
+    fn slash(_who: &AccountId, _fraction: Perbill, _reason: Vec<u8>) -> u32 {
+        0
+    }
+}
+
+/// Macro to simulate formal invariant checks.
+macro_rules! assert_invariant {
+    ($condition:expr, $msg:expr) => {
+        debug_assert!($condition, "Invariant violation: {}", $msg);
+    };
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// Stake scheduled for release at a given block.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct UnlockChunk<Balance, BlockNumber> {
+        /// Amount being unbonded.
+        pub value: Balance,
+        /// Block from which the amount can be withdrawn.
+        pub unlock_at: BlockNumber,
+    }
+
+    /// Bonded stake of an account.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct StakingLedger<Balance, BlockNumber> {
+        /// Total locked amount (active plus unlocking).
+        pub total: Balance,
+        /// Stake currently earning rewards and backing the nominated target.
+        pub active: Balance,
+        /// Stake waiting for the unbonding period to elapse.
+        pub unlocking: Vec<UnlockChunk<Balance, BlockNumber>>,
+    }
+
+    /// Main pallet structure for staking.
+    #[frame_support::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Bonded stake per account.
+    #[pallet::storage]
+    #[pallet::getter(fn ledger)]
+    pub type Ledger<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, StakingLedger<BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+    /// Miner or validator nominated by each staker.
+    #[pallet::storage]
+    #[pallet::getter(fn nominations)]
+    pub type Nominations<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+    /// Total active stake backing each nominated target.
+    #[pallet::storage]
+    #[pallet::getter(fn backing)]
+    pub type Backing<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Sum of all active stake.
+    #[pallet::storage]
+    #[pallet::getter(fn total_active_stake)]
+    pub type TotalStake<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The runtime event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// The native currency in which stake is bonded.
+        type Currency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+        /// Number of blocks unbonded stake stays locked before it can be withdrawn.
+        #[pallet::constant]
+        type BondingDuration: Get<Self::BlockNumber>;
+        /// Minimum active stake an account must keep while bonded.
+        #[pallet::constant]
+        type MinBond: Get<BalanceOf<Self>>;
+        /// Maximum number of simultaneous unlocking chunks per account.
+        #[pallet::constant]
+        type MaxUnlockingChunks: Get<u32>;
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Emitted when stake is bonded (staker, amount).
+        Bonded(T::AccountId, BalanceOf<T>),
+        /// Emitted when stake starts unbonding (staker, amount, unlock block).
+        Unbonded(T::AccountId, BalanceOf<T>, T::BlockNumber),
+        /// Emitted when unlocking stake is rebonded (staker, amount).
+        Rebonded(T::AccountId, BalanceOf<T>),
+        /// Emitted when unbonded stake is withdrawn (staker, amount).
+        Withdrawn(T::AccountId, BalanceOf<T>),
+        /// Emitted when a staker nominates a target (staker, target).
+        Nominated(T::AccountId, T::AccountId),
+        /// Emitted when a staker clears its nomination (staker).
+        Chilled(T::AccountId),
+        /// Emitted when stake is slashed (staker, amount, reason).
+        Slashed(T::AccountId, BalanceOf<T>, Vec<u8>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The account has no bonded stake.
+        NotStaking,
+        /// The amount would leave less than `MinBond` active.
+        InsufficientBond,
+        /// The free balance does not cover the amount to bond.
+        InsufficientBalance,
+        /// Too many unlocking chunks; withdraw or rebond first.
+        TooManyUnlockingChunks,
+        /// There is no unlocking stake to rebond.
+        NoUnlockingStake,
+        /// A staker cannot nominate itself.
+        InvalidNomination,
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Bonds `value` additional tokens as active stake.
+        ///
+        /// # Requirements:
+        /// - The free balance must cover the new total locked amount.
+        /// - The resulting active stake must be at least `MinBond`.
+        pub fn bond(origin: T::Origin, value: BalanceOf<T>) -> DispatchResult {
+            let staker = ensure_signed(origin)?;
+            let mut ledger = Ledger::<T>::get(&staker).unwrap_or(StakingLedger {
+                total: Zero::zero(),
+                active: Zero::zero(),
+                unlocking: Vec::new(),
+            });
+            let new_total = ledger.total.saturating_add(value);
+            ensure!(T::Currency::free_balance(&staker) >= new_total, Error::<T>::InsufficientBalance);
+            ledger.total = new_total;
+            ledger.active = ledger.active.saturating_add(value);
+            ensure!(ledger.active >= T::MinBond::get(), Error::<T>::InsufficientBond);
+            Self::increase_active(&staker, value);
+            Self::update_ledger(&staker, ledger);
+            Self::deposit_event(Event::Bonded(staker, value));
+            Ok(())
+        }
+
+        /// Schedules `value` of active stake for release after `BondingDuration` blocks.
+        ///
+        /// # Requirements:
+        /// - The remaining active stake must be zero or at least `MinBond`.
+        /// - The number of unlocking chunks must stay within `MaxUnlockingChunks`.
+        pub fn unbond(origin: T::Origin, value: BalanceOf<T>) -> DispatchResult {
+            let staker = ensure_signed(origin)?;
+            let mut ledger = Ledger::<T>::get(&staker).ok_or(Error::<T>::NotStaking)?;
+            ensure!((ledger.unlocking.len() as u32) < T::MaxUnlockingChunks::get(), Error::<T>::TooManyUnlockingChunks);
+            let value = value.min(ledger.active);
+            let remaining = ledger.active.saturating_sub(value);
+            ensure!(remaining.is_zero() || remaining >= T::MinBond::get(), Error::<T>::InsufficientBond);
+            ledger.active = remaining;
+            let unlock_at = frame_system::Pallet::<T>::block_number().saturating_add(T::BondingDuration::get());
+            ledger.unlocking.push(UnlockChunk { value, unlock_at });
+            Self::decrease_active(&staker, value);
+            Self::update_ledger(&staker, ledger);
+            Self::deposit_event(Event::Unbonded(staker, value, unlock_at));
+            Ok(())
+        }
+
+        /// Moves up to `value` of unlocking stake back to active stake, most recent chunks first.
+        pub fn rebond(origin: T::Origin, value: BalanceOf<T>) -> DispatchResult {
+            let staker = ensure_signed(origin)?;
+            let mut ledger = Ledger::<T>::get(&staker).ok_or(Error::<T>::NotStaking)?;
+            ensure!(!ledger.unlocking.is_empty(), Error::<T>::NoUnlockingStake);
+            let mut rebonded: BalanceOf<T> = Zero::zero();
+            while let Some(last) = ledger.unlocking.last_mut() {
+                let needed = value.saturating_sub(rebonded);
+                if needed.is_zero() {
+                    break;
+                }
+                if last.value <= needed {
+                    rebonded = rebonded.saturating_add(last.value);
+                    ledger.unlocking.pop();
+                } else {
+                    last.value = last.value.saturating_sub(needed);
+                    rebonded = rebonded.saturating_add(needed);
+                }
+            }
+            ledger.active = ledger.active.saturating_add(rebonded);
+            ensure!(ledger.active >= T::MinBond::get(), Error::<T>::InsufficientBond);
+            Self::increase_active(&staker, rebonded);
+            Self::update_ledger(&staker, ledger);
+            Self::deposit_event(Event::Rebonded(staker, rebonded));
+            Ok(())
+        }
+
+        /// Releases every unlocking chunk whose unbonding period has elapsed.
+        pub fn withdraw_unbonded(origin: T::Origin) -> DispatchResult {
+            let staker = ensure_signed(origin)?;
+            let mut ledger = Ledger::<T>::get(&staker).ok_or(Error::<T>::NotStaking)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut withdrawn: BalanceOf<T> = Zero::zero();
+            ledger.unlocking.retain(|chunk| {
+                if chunk.unlock_at <= now {
+                    withdrawn = withdrawn.saturating_add(chunk.value);
+                    false
+                } else {
+                    true
+                }
+            });
+            ledger.total = ledger.total.saturating_sub(withdrawn);
+            Self::update_ledger(&staker, ledger);
+            Self::deposit_event(Event::Withdrawn(staker, withdrawn));
+            Ok(())
+        }
+
+        /// Nominates a miner or validator with the caller's active stake.
+        pub fn nominate(origin: T::Origin, target: T::AccountId) -> DispatchResult {
+            let staker = ensure_signed(origin)?;
+            ensure!(staker != target, Error::<T>::InvalidNomination);
+            let ledger = Ledger::<T>::get(&staker).ok_or(Error::<T>::NotStaking)?;
+            Self::remove_backing(&staker, ledger.active);
+            Nominations::<T>::insert(&staker, &target);
+            Backing::<T>::mutate(&target, |backing| *backing = backing.saturating_add(ledger.active));
+            Self::deposit_event(Event::Nominated(staker, target));
+            Ok(())
+        }
+
+        /// Clears the caller's nomination.
+        pub fn chill(origin: T::Origin) -> DispatchResult {
+            let staker = ensure_signed(origin)?;
+            let ledger = Ledger::<T>::get(&staker).ok_or(Error::<T>::NotStaking)?;
+            Self::remove_backing(&staker, ledger.active);
+            Nominations::<T>::remove(&staker);
+            Self::deposit_event(Event::Chilled(staker));
+            Ok(())
+        }
+
+        /// Stores the ledger and refreshes the balance lock, removing both once nothing is locked.
+        fn update_ledger(staker: &T::AccountId, ledger: StakingLedger<BalanceOf<T>, T::BlockNumber>) {
+            // Invariant check: The total must cover the active and unlocking stake.
+            assert_invariant!(
+                ledger.unlocking.iter().fold(ledger.active, |acc, chunk| acc.saturating_add(chunk.value)) <= ledger.total,
+                "Staking ledger total does not cover active and unlocking stake"
+            );
+            if ledger.total.is_zero() {
+                T::Currency::remove_lock(STAKING_ID, staker);
+                Ledger::<T>::remove(staker);
+                if Nominations::<T>::take(staker).is_some() {
+                    Self::deposit_event(Event::Chilled(staker.clone()));
+                }
+            } else {
+                T::Currency::set_lock(STAKING_ID, staker, ledger.total, WithdrawReasons::all());
+                Ledger::<T>::insert(staker, ledger);
+            }
+        }
+
+        fn increase_active(staker: &T::AccountId, value: BalanceOf<T>) {
+            TotalStake::<T>::mutate(|total| *total = total.saturating_add(value));
+            if let Some(target) = Nominations::<T>::get(staker) {
+                Backing::<T>::mutate(&target, |backing| *backing = backing.saturating_add(value));
+            }
+        }
+
+        fn decrease_active(staker: &T::AccountId, value: BalanceOf<T>) {
+            TotalStake::<T>::mutate(|total| *total = total.saturating_sub(value));
+            Self::remove_backing(staker, value);
+        }
+
+        fn remove_backing(staker: &T::AccountId, value: BalanceOf<T>) {
+            if let Some(target) = Nominations::<T>::get(staker) {
+                Backing::<T>::mutate(&target, |backing| *backing = backing.saturating_sub(value));
+            }
+        }
+    }
+
+    impl<T: Config> StakeProvider<T::AccountId> for Pallet<T> {
+        fn stake_of(who: &T::AccountId) -> u128 {
+            Ledger::<T>::get(who).map(|ledger| ledger.active.saturated_into::<u128>()).unwrap_or(0)
+        }
+
+        fn total_stake() -> u128 {
+            TotalStake::<T>::get().saturated_into::<u128>()
+        }
+
+        fn stakers(start_after: Option<T::AccountId>, limit: u32) -> (Vec<T::AccountId>, Option<T::AccountId>) {
+            let entries = match start_after {
+                Some(last) => Ledger::<T>::iter_from(Ledger::<T>::hashed_key_for(&last)),
+                None => Ledger::<T>::iter(),
+            };
+            let mut page = Vec::new();
+            let mut visited: u32 = 0;
+            let mut last_visited = None;
+            for (who, ledger) in entries.take(limit as usize) {
+                visited += 1;
+                if !ledger.active.is_zero() {
+                    page.push(who.clone());
+                }
+                last_visited = Some(who);
+            }
+            // A short page means the end of the map was reached.
+            let cursor = if visited < limit { None } else { last_visited };
+            (page, cursor)
+        }
+    }
+
+    impl<T: Config> StakeSlasher<T::AccountId> for Pallet<T> {
+        /// Slashes `fraction` of the active stake and of every unlocking chunk.
+        fn slash(who: &T::AccountId, fraction: Perbill, reason: Vec<u8>) -> u32 {
+            let mut ledger = match Ledger::<T>::get(who) {
+                Some(ledger) => ledger,
+                None => return 0,
+            };
+            let active_slash = fraction * ledger.active;
+            ledger.active = ledger.active.saturating_sub(active_slash);
+            let mut slashed = active_slash;
+            for chunk in ledger.unlocking.iter_mut() {
+                let chunk_slash = fraction * chunk.value;
+                chunk.value = chunk.value.saturating_sub(chunk_slash);
+                slashed = slashed.saturating_add(chunk_slash);
+            }
+            ledger.unlocking.retain(|chunk| !chunk.value.is_zero());
+            ledger.total = ledger.total.saturating_sub(slashed);
+            Self::decrease_active(who, active_slash);
+            // Lift the lock to the reduced total before taking the slashed funds.
+            Self::update_ledger(who, ledger);
+            let (_imbalance, _unslashed) = T::Currency::slash(who, slashed);
+            Self::deposit_event(Event::Slashed(who.clone(), slashed, reason));
+            slashed.saturated_into::<u32>()
+        }
+    }
+}
+
+// --- Benchmarking Section ---
+//
+// The following benchmarks measure the cost of the staking operations.
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking {
+    use super::*;
+    use frame_benchmarking::{benchmarks, account};
+    use frame_system::RawOrigin;
+
+    benchmarks! {
+        bond {
+            let staker: T::AccountId = account("staker", 0, 0);
+            let value = T::MinBond::get();
+            T::Currency::make_free_balance_be(&staker, value.saturating_mul(10u32.into()));
+        }: {
+            Pallet::<T>::bond(RawOrigin::Signed(staker.clone()).into(), value)?;
+        }
+        verify {
+            assert_eq!(Pallet::<T>::ledger(&staker).unwrap().active, value);
+        }
+
+        unbond {
+            let staker: T::AccountId = account("staker", 0, 0);
+            let value = T::MinBond::get();
+            T::Currency::make_free_balance_be(&staker, value.saturating_mul(10u32.into()));
+            Pallet::<T>::bond(RawOrigin::Signed(staker.clone()).into(), value.saturating_mul(2u32.into()))?;
+        }: {
+            Pallet::<T>::unbond(RawOrigin::Signed(staker.clone()).into(), value)?;
+        }
+        verify {
+            assert_eq!(Pallet::<T>::ledger(&staker).unwrap().unlocking.len(), 1);
+        }
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::Test);
+}
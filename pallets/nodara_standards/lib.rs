@@ -10,6 +10,8 @@
 //!
 //! ## Key Advanced Features:
 //! - **Standard Definitions:** Stores and manages a comprehensive set of standards for assets, transactions, and security.
+//! - **Payload Schemas:** Standards can carry a payload schema (field names, wire types, units, ranges) so that
+//!   structured data such as IoT readings can be decoded and validated against it.
 //! - **Compliance Verification:** Provides functions to verify that operations meet established standards, with simulated
 //!   formal invariant checks ensuring mathematical correctness.
 //! - **Immutable Audit Logging:** Records every compliance check with detailed metadata for full transparency.
@@ -19,10 +21,13 @@
 //! ## Module Structure:
 //! - **Storage:**
 //!   - `Standards`: A mapping from standard IDs (Vec<u8>) to standard definitions.
+//!   - `StandardCreators`: The account that defined each standard.
+//!   - `PayloadSchemas`: A mapping from standard IDs to the payload schema they define.
 //!   - `ComplianceHistory`: A log of compliance verification events (timestamp, standard ID, operation details, outcome).
 //! - **Core Functions:**
 //!   - `define_standard`: Registers a new standard with its rules and parameters.
 //!   - `update_standard`: Updates an existing standard via DAO governance.
+//!   - `define_payload_schema`: Attaches a payload schema to an existing standard (its creator or governance only).
 //!   - `verify_compliance`: Checks that a given operation meets a defined standard.
 //!   - `current_timestamp`: Provides a placeholder for the current Unix timestamp.
 //!
@@ -39,6 +44,19 @@ use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use sp_std::collections::btree_map::BTreeMap;
 
+/// Read-only access to payload schemas, consumed by pallets that validate structured data (e.g., the IoT bridge).
+pub trait SchemaProvider {
+    /// Returns the payload schema defined by the standard `id`, if any.
+    fn payload_schema(id: &[u8]) -> Option<pallet::PayloadSchema>;
+}
+
+/// Null provider: no schemas are defined.
+impl SchemaProvider for () {
+    fn payload_schema(_id: &[u8]) -> Option<pallet::PayloadSchema> {
+        None
+    }
+}
+
 /// Macro to simulate formal invariant checks.
 macro_rules! assert_invariant {
     ($condition:expr, $msg:expr) => {
@@ -61,6 +79,86 @@ pub mod pallet {
         pub parameters: Vec<u8>,
     }
 
+    /// Wire type of a payload field. Fields are SCALE-encoded back to back, in schema order.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum FieldType {
+        Bool,
+        U8,
+        U16,
+        U32,
+        U64,
+        I8,
+        I16,
+        I32,
+        I64,
+    }
+
+    /// A field of a payload schema.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct SchemaField {
+        /// Field name (e.g., "temperature").
+        pub name: Vec<u8>,
+        /// Wire type of the field.
+        pub field_type: FieldType,
+        /// Unit of the value (e.g., "cC" for hundredths of a degree Celsius).
+        pub unit: Vec<u8>,
+        /// Smallest accepted value, if bounded.
+        pub min: Option<i64>,
+        /// Largest accepted value, if bounded.
+        pub max: Option<i64>,
+    }
+
+    /// Schema of a structured payload.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PayloadSchema {
+        /// Fields, in wire order.
+        pub fields: Vec<SchemaField>,
+    }
+
+    /// Reason a payload does not match its schema.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum PayloadError {
+        /// The payload is truncated, has trailing bytes or holds an invalid value for a field's type.
+        Malformed,
+        /// The value of the field at this index lies outside the field's range.
+        OutOfRange(u32),
+    }
+
+    impl PayloadSchema {
+        /// Decodes a payload into one value per field, checking each value against its field's range.
+        /// Every value must also fit in an `i64`.
+        pub fn decode_payload(&self, payload: &[u8]) -> Result<Vec<i64>, PayloadError> {
+            let input = &mut &payload[..];
+            let mut values = Vec::with_capacity(self.fields.len());
+            for (index, field) in self.fields.iter().enumerate() {
+                let value: i128 = match field.field_type {
+                    FieldType::Bool => bool::decode(input).map(|v| v as i128),
+                    FieldType::U8 => u8::decode(input).map(|v| v as i128),
+                    FieldType::U16 => u16::decode(input).map(|v| v as i128),
+                    FieldType::U32 => u32::decode(input).map(|v| v as i128),
+                    FieldType::U64 => u64::decode(input).map(|v| v as i128),
+                    FieldType::I8 => i8::decode(input).map(|v| v as i128),
+                    FieldType::I16 => i16::decode(input).map(|v| v as i128),
+                    FieldType::I32 => i32::decode(input).map(|v| v as i128),
+                    FieldType::I64 => i64::decode(input).map(|v| v as i128),
+                }
+                .map_err(|_| PayloadError::Malformed)?;
+                let in_range = field.min.map_or(true, |min| value >= min as i128)
+                    && field.max.map_or(true, |max| value <= max as i128);
+                // `u64` values above `i64::MAX` are rejected as well.
+                let value = i64::try_from(value)
+                    .ok()
+                    .filter(|_| in_range)
+                    .ok_or(PayloadError::OutOfRange(index as u32))?;
+                values.push(value);
+            }
+            if !input.is_empty() {
+                return Err(PayloadError::Malformed);
+            }
+            Ok(values)
+        }
+    }
+
     /// Main pallet structure for standards management.
     #[frame_support::pallet]
     pub struct Pallet<T>(_);
@@ -70,6 +168,16 @@ pub mod pallet {
     #[pallet::getter(fn standards)]
     pub type Standards<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, Standard, OptionQuery>;
 
+    /// Account that defined each standard, keyed by standard ID.
+    #[pallet::storage]
+    #[pallet::getter(fn standard_creators)]
+    pub type StandardCreators<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, T::AccountId, OptionQuery>;
+
+    /// Payload schemas, keyed by the ID of the standard defining them.
+    #[pallet::storage]
+    #[pallet::getter(fn payload_schemas)]
+    pub type PayloadSchemas<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, PayloadSchema, OptionQuery>;
+
     /// Storage for logging compliance verification events.
     /// Each record: (timestamp, standard ID, operation details, verification outcome)
     #[pallet::storage]
@@ -83,6 +191,11 @@ pub mod pallet {
         /// Maximum allowed length for standard definitions (description + parameters).
         #[pallet::constant]
         type MaxStandardLength: Get<u32>;
+        /// Maximum number of fields in a payload schema.
+        #[pallet::constant]
+        type MaxSchemaFields: Get<u32>;
+        /// Origin allowed to attach a payload schema to any standard (e.g., DAO governance).
+        type GovernanceOrigin: EnsureOrigin<Self::Origin>;
     }
 
     #[pallet::event]
@@ -94,6 +207,8 @@ pub mod pallet {
         StandardUpdated(Vec<u8>),
         /// Emitted when a compliance check is performed (standard ID, outcome).
         ComplianceChecked(Vec<u8>, bool),
+        /// Emitted when a payload schema is attached to a standard (standard ID).
+        PayloadSchemaDefined(Vec<u8>),
     }
 
     #[pallet::error]
@@ -106,6 +221,12 @@ pub mod pallet {
         StandardNotFound,
         /// Compliance check failed.
         ComplianceCheckFailed,
+        /// The standard already has a payload schema.
+        SchemaAlreadyExists,
+        /// The schema has no fields, too many fields, an empty or oversized name or unit, or a range with min > max.
+        InvalidSchema,
+        /// Only the standard's creator or the governance origin may define its payload schema.
+        NotStandardCreator,
     }
 
     impl<T: Config> Pallet<T> {
@@ -126,7 +247,7 @@ pub mod pallet {
             description: Vec<u8>,
             parameters: Vec<u8>,
         ) -> DispatchResult {
-            let _sender = ensure_signed(origin)?;
+            let sender = ensure_signed(origin)?;
             ensure!(
                 (description.len() + parameters.len()) as u32 <= T::MaxStandardLength::get(),
                 Error::<T>::StandardTooLong
@@ -134,6 +255,7 @@ pub mod pallet {
             ensure!(!Standards::<T>::contains_key(&id), Error::<T>::StandardAlreadyExists);
             let standard = Standard { id: id.clone(), description, parameters };
             Standards::<T>::insert(&id, standard);
+            StandardCreators::<T>::insert(&id, sender);
             Self::deposit_event(Event::StandardDefined(id));
             Ok(())
         }
@@ -170,6 +292,51 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Attaches a payload schema to an existing standard.
+        ///
+        /// Schemas cannot be changed once defined, since devices and data consumers rely on them; a new schema
+        /// version is published as a new standard.
+        ///
+        /// # Parameters:
+        /// - `origin`: The standard's creator, or the governance origin.
+        /// - `standard_id`: The standard the schema belongs to.
+        /// - `fields`: The schema fields, in wire order.
+        ///
+        /// # Requirements:
+        /// - The standard must exist and must not have a schema yet.
+        /// - Signed origins must be the account that defined the standard.
+        /// - There must be between 1 and `MaxSchemaFields` fields, each with a non-empty name, a name and unit within
+        ///   `MaxStandardLength`, and `min <= max` when both are set.
+        pub fn define_payload_schema(origin: T::Origin, standard_id: Vec<u8>, fields: Vec<SchemaField>) -> DispatchResult {
+            let governance = T::GovernanceOrigin::try_origin(origin.clone()).is_ok();
+            let sender = if governance { None } else { Some(ensure_signed(origin)?) };
+            ensure!(Standards::<T>::contains_key(&standard_id), Error::<T>::StandardNotFound);
+            if let Some(sender) = sender {
+                ensure!(
+                    StandardCreators::<T>::get(&standard_id).as_ref() == Some(&sender),
+                    Error::<T>::NotStandardCreator
+                );
+            }
+            ensure!(!PayloadSchemas::<T>::contains_key(&standard_id), Error::<T>::SchemaAlreadyExists);
+            ensure!(
+                !fields.is_empty() && fields.len() as u32 <= T::MaxSchemaFields::get(),
+                Error::<T>::InvalidSchema
+            );
+            let max_length = T::MaxStandardLength::get() as usize;
+            for field in &fields {
+                ensure!(
+                    !field.name.is_empty() && field.name.len() <= max_length && field.unit.len() <= max_length,
+                    Error::<T>::InvalidSchema
+                );
+                if let (Some(min), Some(max)) = (field.min, field.max) {
+                    ensure!(min <= max, Error::<T>::InvalidSchema);
+                }
+            }
+            PayloadSchemas::<T>::insert(&standard_id, PayloadSchema { fields });
+            Self::deposit_event(Event::PayloadSchemaDefined(standard_id));
+            Ok(())
+        }
+
         /// Verifies compliance of an operation against a defined standard.
         ///
         /// # Parameters:
@@ -195,6 +362,12 @@ pub mod pallet {
             1_640_000_000 // Placeholder timestamp.
         }
     }
+
+    impl<T: Config> SchemaProvider for Pallet<T> {
+        fn payload_schema(id: &[u8]) -> Option<PayloadSchema> {
+            PayloadSchemas::<T>::get(id)
+        }
+    }
 }
 
 // --- Benchmarking Section ---
@@ -235,6 +408,20 @@ mod benchmarking {
             assert_eq!(standard.description, new_description);
         }
 
+        define_payload_schema {
+            let sender: T::AccountId = account("sender", 0, 0);
+            let id: Vec<u8> = b"Standard1".to_vec();
+            Pallet::<T>::define_standard(RawOrigin::Signed(sender.clone()).into(), id.clone(), b"Desc".to_vec(), b"Params".to_vec())?;
+            let fields: Vec<SchemaField> = (0..T::MaxSchemaFields::get())
+                .map(|_| SchemaField { name: b"field".to_vec(), field_type: FieldType::I64, unit: b"unit".to_vec(), min: Some(0), max: Some(100) })
+                .collect();
+        }: {
+            Pallet::<T>::define_payload_schema(RawOrigin::Signed(sender.clone()).into(), id.clone(), fields)?;
+        }
+        verify {
+            assert!(PayloadSchemas::<T>::contains_key(&id));
+        }
+
         verify_compliance {
             let sender: T::AccountId = account("sender", 0, 0);
             let id: Vec<u8> = b"Standard1".to_vec();
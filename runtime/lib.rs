@@ -16,11 +16,15 @@
 
 use sp_runtime::{
     create_runtime_str, generic, traits::{BlakeTwo256, Block as BlockT, IdentityLookup},
-    RuntimeVersion,
+    Percent, RuntimeVersion,
 };
 use sp_core::OpaqueMetadata;
-use frame_support::{construct_runtime, parameter_types, traits::KeyOwnerProofSystem};
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::{Currency, Imbalance, KeyOwnerProofSystem, OnUnbalanced},
+};
 use frame_system as system;
+use sp_std::vec::Vec;
 
 // Import global types from our node_primitives (assumed to be defined elsewhere)
 pub use node_primitives::{AccountId, Balance, BlockNumber, Hash};
@@ -53,6 +57,19 @@ parameter_types! {
     pub const AvailableBlockRatio: sp_runtime::Perbill = sp_runtime::Perbill::from_percent(75);
 }
 
+parameter_types! {
+    /// Tokenomics defaults for the fee split; adjustable afterwards through `FeeSplit::set_fee_split`.
+    pub const DefaultReserveShare: Percent = Percent::from_percent(10);
+    pub const DefaultFoundersShare: Percent = Percent::from_percent(5);
+    pub const DefaultBurnShare: Percent = Percent::from_percent(0);
+    /// Account receiving the founders' share of transaction fees.
+    pub FoundersAccount: AccountId = AccountId::from([0x4e; 32]);
+    /// Account holding the tokens backing the reserve fund.
+    pub ReserveFundAccount: AccountId = AccountId::from([0x52; 32]);
+    /// Account holding the tokens backing the staking/community reward pool.
+    pub RewardPoolAccount: AccountId = AccountId::from([0x50; 32]);
+}
+
 // Construct the runtime by integrating all the modules.
 construct_runtime!(
     pub enum Runtime where
@@ -66,6 +83,10 @@ construct_runtime!(
         // Timestamp for block time.
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 
+        // Native NDR balances and transaction fee payment.
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        TransactionPayment: pallet_transaction_payment::{Pallet, Storage, Event<T>},
+
         // Core modules of Nodara BIOSPHÈRE QUANTIC:
         Biosphere: nodara_biosphere::{Pallet, Call, Storage, Event<T>},
         GrowthModel: nodara_growth_model::{Pallet, Call, Storage, Event<T>},
@@ -75,12 +96,14 @@ construct_runtime!(
         RewardEngine: nodara_reward_engine::{Pallet, Call, Storage, Event<T>},
         ID: nodara_id::{Pallet, Call, Storage, Event<T>},
         Marketplace: nodara_marketplace::{Pallet, Call, Storage, Event<T>},
-        IoTBridge: nodara_iot_bridge::{Pallet, Call, Storage, Event<T>},
+        IoTBridge: nodara_iot_bridge::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
         Interop: nodara_interop::{Pallet, Call, Storage, Event<T>},
         PredictiveGuard: nodara_predictive_guard::{Pallet, Call, Storage, Event<T>},
         Reputation: nodara_reputation::{Pallet, Call, Storage, Event<T>},
         Standards: nodara_standards::{Pallet, Call, Storage, Event<T>},
         POW: nodara_pow::{Pallet, Call, Storage, Event<T>},
+        FeeSplit: nodara_fee_split::{Pallet, Call, Storage, Event<T>},
+        Staking: nodara_staking::{Pallet, Call, Storage, Event<T>},
         // Additional modules can be added here.
     }
 );
@@ -97,4 +120,83 @@ pub mod opaque {
 /// Opaque metadata.
 pub const OPAQUE_METADATA: OpaqueMetadata = OpaqueMetadata::new(sp_runtime::OpaqueExtrinsic::default().encode());
 
+/// Negative imbalance of the native currency, as produced by fee withdrawal.
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Fee handler plugged into `pallet_transaction_payment`.
+///
+/// Fees and tips are split by `nodara_fee_split` in the native `Balance`: the founders share is paid to
+/// `FoundersAccount`, the burn share is dropped (reducing total issuance), and the reserve and staking shares are
+/// deposited into the accounts backing `nodara_reserve_fund` and `nodara_reward_engine` once their pools have been
+/// credited. If a pool rejects its share, or a share does not fit in the pools' `u32` accounting,
+/// `distribute_native_fees` reports the error and those shares are burned instead of being deposited without matching
+/// pool accounting.
+pub struct DealWithFees;
+
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+    fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+        if let Some(mut fees) = fees_then_tips.next() {
+            if let Some(tips) = fees_then_tips.next() {
+                tips.merge_into(&mut fees);
+            }
+            let distribution = FeeSplit::split_amount(fees.peek());
+            let (founders, rest) = fees.split(distribution.founders);
+            let (burned, rest) = rest.split(distribution.burned);
+            let (reserve, staking) = rest.split(distribution.reserve);
+            Balances::resolve_creating(&FoundersAccount::get(), founders);
+            // Dropping the burn share destroys it.
+            drop(burned);
+            if FeeSplit::distribute_native_fees(distribution).is_ok() {
+                Balances::resolve_creating(&ReserveFundAccount::get(), reserve);
+                Balances::resolve_creating(&RewardPoolAccount::get(), staking);
+            }
+        }
+    }
+}
+
+/// Credits the reserve share of fees to `nodara_reserve_fund`.
+pub struct ReserveFundSink;
+
+impl nodara_fee_split::FeeSink for ReserveFundSink {
+    fn credit(amount: u32, reason: Vec<u8>) -> sp_runtime::DispatchResult {
+        ReserveFund::contribute(amount, reason)
+    }
+}
+
+/// Credits the staking/community share of fees to the `nodara_reward_engine` pool.
+pub struct RewardPoolSink;
+
+impl nodara_fee_split::FeeSink for RewardPoolSink {
+    fn credit(amount: u32, reason: Vec<u8>) -> sp_runtime::DispatchResult {
+        RewardEngine::fund_reward_pool(amount, reason)
+    }
+}
+
+impl pallet_transaction_payment::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, DealWithFees>;
+    type OperationalFeeMultiplier = frame_support::traits::ConstU8<5>;
+    type WeightToFee = frame_support::weights::IdentityFee<Balance>;
+    type LengthToFee = frame_support::weights::IdentityFee<Balance>;
+    type FeeMultiplierUpdate = ();
+}
+
+impl nodara_fee_split::pallet::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type GovernanceOrigin = frame_system::EnsureRoot<AccountId>;
+    type ReserveFund = ReserveFundSink;
+    type RewardPool = RewardPoolSink;
+    type DefaultReserveShare = DefaultReserveShare;
+    type DefaultFoundersShare = DefaultFoundersShare;
+    type DefaultBurnShare = DefaultBurnShare;
+}
+
+sp_api::impl_runtime_apis! {
+    impl nodara_id::NodaraIdApi<opaque::Block, Hash> for Runtime {
+        fn credential_status(credential_id: Hash) -> nodara_id::CredentialStatus {
+            ID::credential_status(credential_id)
+        }
+    }
+}
+
 /// Additional runtime configurations can be added here (e.g., implementation of pallet_timestamp::Config, etc.).
@@ -8,6 +8,8 @@ use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
 };
 use frame_system as system;
+use nodara_interop::light_client::{BlsPublicKey, BlsSignature};
+use std::cell::RefCell;
 
 // Define basic types for the test runtime.
 pub type UncheckedExtrinsic = system::mocking::MockUncheckedExtrinsic<Test>;
@@ -20,6 +22,7 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
         NodaraBiosphere: nodara_biosphere::{Pallet, Call, Storage, Event<T>},
         NodaraStabilityGuard: nodara_stability_guard::{Pallet, Call, Storage, Event<T>},
         NodaraLiquidityFlow: nodara_liquidity_flow::{Pallet, Call, Storage, Event<T>},
@@ -27,8 +30,13 @@ frame_support::construct_runtime!(
         NodaraRewardEngine: nodara_reward_engine::{Pallet, Call, Storage, Event<T>},
         NodaraID: nodara_id::{Pallet, Call, Storage, Event<T>},
         NodaraMarketplace: nodara_marketplace::{Pallet, Call, Storage, Event<T>},
-        NodaraIoTBridge: nodara_iot_bridge::{Pallet, Call, Storage, Event<T>},
+        NodaraIoTBridge: nodara_iot_bridge::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
         NodaraStandards: nodara_standards::{Pallet, Call, Storage, Event<T>},
+        NodaraFeeSplit: nodara_fee_split::{Pallet, Call, Storage, Event<T>},
+        NodaraReputation: nodara_reputation::{Pallet, Call, Storage, Event<T>},
+        NodaraInterop: nodara_interop::{Pallet, Call, Storage, Event<T>},
+        NodaraStaking: nodara_staking::{Pallet, Call, Storage, Event<T>},
+        NodaraPow: nodara_pow::{Pallet, Call, Storage, Event<T>},
         // ... Intégrer ici les autres modules si nécessaire.
     }
 );
@@ -39,6 +47,66 @@ parameter_types! {
     pub const MaximumBlockWeight: u32 = 1024;
     pub const MaximumBlockLength: u32 = 2 * 1024 * 1024;
     pub const AvailableBlockRatio: sp_runtime::Perbill = sp_runtime::Perbill::from_percent(75);
+    pub const InitialRewardPool: u32 = 10_000;
+    pub const BaseReward: u32 = 10;
+    pub const ReputationMultiplier: u32 = 1;
+    pub const MinReward: u32 = 1;
+    pub const MaxReward: u32 = 1_000;
+    pub const FallbackReputation: u32 = 50;
+    pub const MaxCurvePoints: u32 = 16;
+    pub const MaxProofLength: u32 = 32;
+    pub const MaxStakersPerPayout: u32 = 2;
+    pub const BondingDuration: u64 = 10;
+    pub const MinBond: u64 = 10;
+    pub const MaxUnlockingChunks: u32 = 4;
+    pub const BaselineDifficulty: u32 = 50;
+    pub const MaxDifficulty: u32 = 1_000;
+    pub const MinDifficulty: u32 = 1;
+    pub const InvalidWorkSlash: sp_runtime::Perbill = sp_runtime::Perbill::from_percent(10);
+    pub const DefaultReserveShare: sp_runtime::Percent = sp_runtime::Percent::from_percent(10);
+    pub const DefaultFoundersShare: sp_runtime::Percent = sp_runtime::Percent::from_percent(5);
+    pub const DefaultBurnShare: sp_runtime::Percent = sp_runtime::Percent::from_percent(20);
+    pub const DefaultReputation: u32 = 100;
+    pub const MaxReputation: u32 = 1_000;
+    pub const MinReputation: u32 = 0;
+    pub const ReputationHalfLife: u64 = 100;
+    pub const ReportingPeriod: u64 = 1_000;
+    pub const ExistentialDeposit: u64 = 1;
+    pub const AttestationBond: u64 = 50;
+    pub const DisputeBond: u64 = 20;
+    pub const AttestationWeight: sp_runtime::Percent = sp_runtime::Percent::from_percent(50);
+    pub const AttestationCap: u32 = 150;
+    pub const DisputeWindow: u64 = 10;
+    pub const OverturnPenalty: u32 = 30;
+    pub const ShareIdentityReputation: bool = true;
+    pub const MaxRegistrars: u32 = 8;
+    pub const MaxDidEntries: u32 = 4;
+    pub const MaxDidFieldLength: u32 = 128;
+    pub const MaxSubAccounts: u32 = 4;
+    pub const SubAccountDeposit: u64 = 5;
+    pub const MaxFriends: u32 = 5;
+    pub const RecoveryDeposit: u64 = 10;
+    pub const JudgementValidity: u64 = 100;
+    pub const MaxKycLength: u32 = 256;
+    pub const MaxPayloadLength: u32 = 256;
+    pub const BaseTimeout: u64 = 60;
+    pub const MaxDeviceMetadataLength: u32 = 64;
+    pub const DeviceDeposit: u64 = 10;
+    pub const MinSubmissionInterval: u64 = 2;
+    pub const IoTUnsignedPriority: u64 = 1 << 20;
+    pub const AggregationWindow: u64 = 10;
+    pub const RetentionPeriod: u64 = 20;
+    pub const MaxPrunePerBlock: u32 = 10;
+    pub const MaxStandardLength: u32 = 256;
+    pub const MaxSchemaFields: u32 = 8;
+    pub const MaxEncryptedKeyLength: u32 = 128;
+    pub const IoTReporterAccount: u64 = 99;
+    pub const AnomalyPenalty: u32 = 10;
+    pub const QuarantineThreshold: u32 = 3;
+    pub const RebaselineAfter: u32 = 3;
+    pub const InteropTimeout: u64 = 10;
+    pub const InteropChainId: u32 = 1;
+    pub static UnverifiedGainWeight: sp_runtime::Percent = sp_runtime::Percent::from_percent(100);
 }
 
 impl system::Config for Test {
@@ -56,7 +124,7 @@ impl system::Config for Test {
     type Header = Header;
     type Index = u64;
     type BlockHashCount = BlockHashCount;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<u64>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type RuntimeEvent = ();
@@ -67,6 +135,18 @@ impl system::Config for Test {
     type MaxConsumers = ();
 }
 
+impl pallet_balances::Config for Test {
+    type Balance = u64;
+    type DustRemoval = ();
+    type RuntimeEvent = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+}
+
 // Implement dummy configurations for each module. For production-grade tests, these should be fully implemented.
 impl nodara_biosphere::pallet::Config for Test {
     type UnixTime = ();
@@ -99,17 +179,51 @@ impl nodara_reserve_fund::pallet::Config for Test {
 
 impl nodara_reward_engine::pallet::Config for Test {
     type RuntimeEvent = ();
-    type InitialRewardPool = ();
-    type BaseReward = ();
-    type ReputationMultiplier = ();
-    type MinReward = ();
-    type MaxReward = ();
+    type InitialRewardPool = InitialRewardPool;
+    type BaseReward = BaseReward;
+    type ReputationMultiplier = ReputationMultiplier;
+    type MinReward = MinReward;
+    type MaxReward = MaxReward;
+    type Reputation = NodaraReputation;
+    type FallbackReputation = FallbackReputation;
+    type MaxCurvePoints = MaxCurvePoints;
+    type GovernanceOrigin = system::EnsureRoot<u64>;
+    type Stake = NodaraStaking;
+    type MaxProofLength = MaxProofLength;
+    type MaxStakersPerPayout = MaxStakersPerPayout;
+}
+
+impl nodara_staking::pallet::Config for Test {
+    type RuntimeEvent = ();
+    type Currency = Balances;
+    type BondingDuration = BondingDuration;
+    type MinBond = MinBond;
+    type MaxUnlockingChunks = MaxUnlockingChunks;
+}
+
+impl nodara_pow::pallet::Config for Test {
+    type RuntimeEvent = ();
+    type BaselineDifficulty = BaselineDifficulty;
+    type MaxDifficulty = MaxDifficulty;
+    type MinDifficulty = MinDifficulty;
+    type Slasher = NodaraStaking;
+    type InvalidWorkSlash = InvalidWorkSlash;
 }
 
 impl nodara_id::pallet::Config for Test {
     type RuntimeEvent = ();
-    type DefaultVerification = ();
-    type MaxKycLength = ();
+    type MaxKycLength = MaxKycLength;
+    type Currency = Balances;
+    type GovernanceOrigin = system::EnsureRoot<u64>;
+    type MaxRegistrars = MaxRegistrars;
+    type MaxDidEntries = MaxDidEntries;
+    type MaxDidFieldLength = MaxDidFieldLength;
+    type MaxSubAccounts = MaxSubAccounts;
+    type SubAccountDeposit = SubAccountDeposit;
+    type MaxFriends = MaxFriends;
+    type RecoveryDeposit = RecoveryDeposit;
+    type JudgementValidity = JudgementValidity;
+    type OnIdentityMigrated = NodaraReputation;
 }
 
 impl nodara_marketplace::pallet::Config for Test {
@@ -121,11 +235,123 @@ impl nodara_marketplace::pallet::Config for Test {
 
 impl nodara_iot_bridge::pallet::Config for Test {
     type RuntimeEvent = ();
-    type MaxPayloadLength = ();
-    type BaseTimeout = ();
+    type MaxPayloadLength = MaxPayloadLength;
+    type BaseTimeout = BaseTimeout;
+    type MaxDeviceMetadataLength = MaxDeviceMetadataLength;
+    type Currency = Balances;
+    type GovernanceOrigin = system::EnsureRoot<u64>;
+    type DeviceDeposit = DeviceDeposit;
+    type MinSubmissionInterval = MinSubmissionInterval;
+    type UnsignedPriority = IoTUnsignedPriority;
+    type AggregationWindow = AggregationWindow;
+    type RetentionPeriod = RetentionPeriod;
+    type MaxPrunePerBlock = MaxPrunePerBlock;
+    type Schemas = NodaraStandards;
+    type MaxEncryptedKeyLength = MaxEncryptedKeyLength;
+    type Reputation = NodaraReputation;
+    type ReporterAccount = IoTReporterAccount;
+    type AnomalyPenalty = AnomalyPenalty;
+    type QuarantineThreshold = QuarantineThreshold;
+    type RebaselineAfter = RebaselineAfter;
 }
 
 impl nodara_standards::pallet::Config for Test {
     type RuntimeEvent = ();
-    type MaxStandardLength = ();
+    type MaxStandardLength = MaxStandardLength;
+    type MaxSchemaFields = MaxSchemaFields;
+    type GovernanceOrigin = system::EnsureRoot<u64>;
+}
+
+thread_local! {
+    /// When set, `MockReserveSink` rejects every credit, as a full reserve fund would.
+    pub static RESERVE_FULL: RefCell<bool> = RefCell::new(false);
+}
+
+/// Reserve fund sink whose availability is controlled by `RESERVE_FULL`.
+pub struct MockReserveSink;
+
+impl nodara_fee_split::FeeSink for MockReserveSink {
+    fn credit(_amount: u32, _reason: Vec<u8>) -> sp_runtime::DispatchResult {
+        if RESERVE_FULL.with(|full| *full.borrow()) {
+            return Err(sp_runtime::DispatchError::Other("reserve full"));
+        }
+        Ok(())
+    }
+}
+
+impl nodara_fee_split::pallet::Config for Test {
+    type RuntimeEvent = ();
+    type GovernanceOrigin = system::EnsureRoot<u64>;
+    type ReserveFund = MockReserveSink;
+    type RewardPool = ();
+    type DefaultReserveShare = DefaultReserveShare;
+    type DefaultFoundersShare = DefaultFoundersShare;
+    type DefaultBurnShare = DefaultBurnShare;
+}
+
+impl nodara_reputation::pallet::Config for Test {
+    type RuntimeEvent = ();
+    type DefaultReputation = DefaultReputation;
+    type MaxReputation = MaxReputation;
+    type MinReputation = MinReputation;
+    type ReputationHalfLife = ReputationHalfLife;
+    type ReportingPeriod = ReportingPeriod;
+    type GovernanceOrigin = system::EnsureRoot<u64>;
+    type Currency = Balances;
+    type ArbiterOrigin = system::EnsureRoot<u64>;
+    type AttestationBond = AttestationBond;
+    type DisputeBond = DisputeBond;
+    type AttestationWeight = AttestationWeight;
+    type AttestationCap = AttestationCap;
+    type DisputeWindow = DisputeWindow;
+    type OverturnPenalty = OverturnPenalty;
+    type Identity = NodaraID;
+    type UnverifiedGainWeight = UnverifiedGainWeight;
+    type ShareIdentityReputation = ShareIdentityReputation;
+}
+
+thread_local! {
+    /// Interop message outcomes reported to the sending side: (destination, nonce, sender, delivered).
+    pub static INTEROP_OUTCOMES: RefCell<Vec<(u32, u64, u64, bool)>> = RefCell::new(Vec::new());
+}
+
+/// Records interop message outcomes in `INTEROP_OUTCOMES`.
+pub struct RecordInteropOutcomes;
+
+impl nodara_interop::OnMessageOutcome<u64> for RecordInteropOutcomes {
+    fn on_acknowledged(destination: u32, nonce: u64, sender: &u64, _payload: &[u8]) {
+        INTEROP_OUTCOMES.with(|outcomes| outcomes.borrow_mut().push((destination, nonce, *sender, true)));
+    }
+
+    fn on_timeout(destination: u32, nonce: u64, sender: &u64, _payload: &[u8]) {
+        INTEROP_OUTCOMES.with(|outcomes| outcomes.borrow_mut().push((destination, nonce, *sender, false)));
+    }
+}
+
+/// Stand-in for BLS12-381 in sync committee tests: the aggregate signature of some keys over a message is
+/// `stub_bls_signature` of them, so a signature is bound to both its participants and its signing root.
+pub struct StubBlsVerifier;
+
+impl nodara_interop::light_client::BlsVerifier for StubBlsVerifier {
+    fn fast_aggregate_verify(public_keys: &[BlsPublicKey], message: &H256, signature: &BlsSignature) -> bool {
+        *signature == stub_bls_signature(public_keys, message)
+    }
+}
+
+/// Returns the signature `StubBlsVerifier` accepts for `public_keys` over `message`.
+pub fn stub_bls_signature(public_keys: &[BlsPublicKey], message: &H256) -> BlsSignature {
+    let digest = sp_io::hashing::sha2_256(&[message.as_bytes(), &public_keys.concat()].concat());
+    let mut signature = [0u8; 96];
+    signature[..32].copy_from_slice(&digest);
+    signature
+}
+
+impl nodara_interop::pallet::Config for Test {
+    type RuntimeEvent = ();
+    type BaseTimeout = InteropTimeout;
+    type MaxPayloadLength = MaxPayloadLength;
+    type GovernanceOrigin = system::EnsureRoot<u64>;
+    type OnOutcome = RecordInteropOutcomes;
+    type SelfChainId = InteropChainId;
+    type BlsVerifier = StubBlsVerifier;
 }
@@ -0,0 +1,83 @@
+// tests/unit_tests/nodara_fee_split_tests.rs
+
+use crate::mock::{new_test_ext, Test, RESERVE_FULL};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use nodara_fee_split::pallet::{Error, FeeSplitConfig, Pallet as FeeSplitPallet};
+use sp_runtime::Percent;
+
+#[test]
+fn test_default_fee_split() {
+    new_test_ext().execute_with(|| {
+        let distribution = FeeSplitPallet::<Test>::split_fees(1_000);
+        assert_eq!(distribution.reserve, 100);
+        assert_eq!(distribution.founders, 50);
+        assert_eq!(distribution.burned, 200);
+        assert_eq!(distribution.staking, 650);
+        assert_ok!(FeeSplitPallet::<Test>::distribute_fees(distribution));
+        assert_eq!(FeeSplitPallet::<Test>::block_fees().total(), 1_000);
+    });
+}
+
+#[test]
+fn test_set_fee_split() {
+    new_test_ext().execute_with(|| {
+        let invalid = FeeSplitConfig {
+            reserve: Percent::from_percent(60),
+            founders: Percent::from_percent(30),
+            burn: Percent::from_percent(20),
+        };
+        assert_noop!(
+            FeeSplitPallet::<Test>::set_fee_split(RawOrigin::Root.into(), invalid),
+            Error::<Test>::InvalidFeeSplit
+        );
+        let split = FeeSplitConfig {
+            reserve: Percent::from_percent(20),
+            founders: Percent::from_percent(5),
+            burn: Percent::from_percent(0),
+        };
+        assert_noop!(
+            FeeSplitPallet::<Test>::set_fee_split(RawOrigin::Signed(1).into(), split),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(FeeSplitPallet::<Test>::set_fee_split(RawOrigin::Root.into(), split));
+        assert_eq!(FeeSplitPallet::<Test>::split_fees(100).staking, 75);
+    });
+}
+
+#[test]
+fn test_split_amount_uses_the_full_balance() {
+    new_test_ext().execute_with(|| {
+        // Amounts beyond `u32::MAX` are split exactly instead of being capped.
+        let amount: u128 = 10_000_000_000_000;
+        let distribution = FeeSplitPallet::<Test>::split_amount(amount);
+        assert_eq!(distribution.reserve, 1_000_000_000_000);
+        assert_eq!(distribution.founders, 500_000_000_000);
+        assert_eq!(distribution.burned, 2_000_000_000_000);
+        assert_eq!(distribution.staking, 6_500_000_000_000);
+        assert_eq!(distribution.total(), amount);
+        // The pools account in `u32`: such shares are rejected rather than capped.
+        assert_eq!(distribution.checked(), None);
+        assert_eq!(FeeSplitPallet::<Test>::distribute_native_fees(distribution), Err(Error::<Test>::FeeAmountTooLarge.into()));
+        assert_eq!(FeeSplitPallet::<Test>::block_fees().total(), 0);
+
+        let distribution = FeeSplitPallet::<Test>::split_amount(1_000u128);
+        assert_ok!(FeeSplitPallet::<Test>::distribute_native_fees(distribution));
+        assert_eq!(FeeSplitPallet::<Test>::block_fees().total(), 1_000);
+    });
+}
+
+#[test]
+fn test_rejected_reserve_credit_is_reported() {
+    new_test_ext().execute_with(|| {
+        RESERVE_FULL.with(|full| *full.borrow_mut() = true);
+        let distribution = FeeSplitPallet::<Test>::split_fees(1_000);
+        assert_eq!(
+            FeeSplitPallet::<Test>::distribute_fees(distribution),
+            Err(sp_runtime::DispatchError::Other("reserve full"))
+        );
+        // Neither pool was credited, so nothing is recorded for the block.
+        assert_eq!(FeeSplitPallet::<Test>::block_fees().total(), 0);
+        RESERVE_FULL.with(|full| *full.borrow_mut() = false);
+    });
+}
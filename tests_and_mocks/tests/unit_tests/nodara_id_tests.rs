@@ -1,15 +1,334 @@
 // tests/unit_tests/nodara_id_tests.rs
 
 use crate::mock::{new_test_ext, Test};
-use frame_support::{assert_ok};
-use nodara_id::pallet::{Pallet as IdPallet};
+use frame_support::{assert_noop, assert_ok, traits::{Currency, ReservableCurrency}};
+use frame_system::RawOrigin;
+use nodara_id::{
+    pallet::{DidKey, Error, Judgement, KeyType, Pallet as IdPallet},
+    CredentialStatus, IdentityProvider,
+};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+const REGISTRAR: u64 = 20;
+
+fn commitment(seed: u8) -> H256 {
+    H256::repeat_byte(seed)
+}
 
 #[test]
 fn test_identity_registration_and_update() {
     new_test_ext().execute_with(|| {
         // Register identity.
-        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), b"Initial KYC".to_vec()));
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), commitment(1), b"ipfs://kyc".to_vec()));
+        assert!(!IdPallet::<Test>::is_identity_verified(&1));
         // Update identity.
-        assert_ok!(IdPallet::<Test>::update_identity(RawOrigin::Signed(1).into(), b"Updated KYC".to_vec(), true));
+        assert_ok!(IdPallet::<Test>::update_identity(RawOrigin::Signed(1).into(), commitment(2), Vec::new()));
+        assert!(!IdPallet::<Test>::is_identity_verified(&1));
+        // Only commitments are recorded in the history.
+        let history = IdPallet::<Test>::identity_history();
+        assert_eq!(history.last().unwrap().4, commitment(2));
+    });
+}
+
+#[test]
+fn test_registrar_judgement_verifies_and_collects_fee() {
+    new_test_ext().execute_with(|| {
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&1, 100);
+        assert_noop!(
+            IdPallet::<Test>::add_registrar(RawOrigin::Signed(1).into(), REGISTRAR, 10),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(IdPallet::<Test>::add_registrar(RawOrigin::Root.into(), REGISTRAR, 10));
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), commitment(1), Vec::new()));
+        assert_noop!(IdPallet::<Test>::request_judgement(RawOrigin::Signed(1).into(), 0, 5), Error::<Test>::FeeChanged);
+        assert_ok!(IdPallet::<Test>::request_judgement(RawOrigin::Signed(1).into(), 0, 10));
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 10);
+        // Only the registrar can judge, and only the current commitment.
+        assert_noop!(
+            IdPallet::<Test>::provide_judgement(RawOrigin::Signed(1).into(), 0, 1, commitment(1), Judgement::KnownGood),
+            Error::<Test>::InvalidRegistrar
+        );
+        assert_noop!(
+            IdPallet::<Test>::provide_judgement(RawOrigin::Signed(REGISTRAR).into(), 0, 1, commitment(9), Judgement::KnownGood),
+            Error::<Test>::CommitmentMismatch
+        );
+        assert_ok!(IdPallet::<Test>::provide_judgement(RawOrigin::Signed(REGISTRAR).into(), 0, 1, commitment(1), Judgement::KnownGood));
+        assert!(IdPallet::<Test>::is_identity_verified(&1));
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&REGISTRAR), 10);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 0);
+    });
+}
+
+#[test]
+fn test_changing_kyc_commitment_requires_reverification() {
+    new_test_ext().execute_with(|| {
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&1, 100);
+        assert_ok!(IdPallet::<Test>::add_registrar(RawOrigin::Root.into(), REGISTRAR, 10));
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), commitment(1), Vec::new()));
+        assert_ok!(IdPallet::<Test>::request_judgement(RawOrigin::Signed(1).into(), 0, 10));
+        assert_ok!(IdPallet::<Test>::provide_judgement(RawOrigin::Signed(REGISTRAR).into(), 0, 1, commitment(1), Judgement::Reasonable));
+        // An unchanged commitment keeps the verification.
+        assert_ok!(IdPallet::<Test>::update_identity(RawOrigin::Signed(1).into(), commitment(1), b"ipfs://moved".to_vec()));
+        assert!(IdPallet::<Test>::is_identity_verified(&1));
+        assert_ok!(IdPallet::<Test>::update_identity(RawOrigin::Signed(1).into(), commitment(2), Vec::new()));
+        assert!(!IdPallet::<Test>::is_identity_verified(&1));
+        assert!(IdPallet::<Test>::identities(&1).unwrap().judgements.is_empty());
+    });
+}
+
+#[test]
+fn test_selective_disclosure_of_kyc_field() {
+    new_test_ext().execute_with(|| {
+        let country = IdPallet::<Test>::kyc_leaf(b"country", b"FR", b"salt-1");
+        let birth_date = IdPallet::<Test>::kyc_leaf(b"birth_date", b"1990-01-01", b"salt-2");
+        let root = if country <= birth_date {
+            BlakeTwo256::hash_of(&(country, birth_date))
+        } else {
+            BlakeTwo256::hash_of(&(birth_date, country))
+        };
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), root, Vec::new()));
+        assert!(IdPallet::<Test>::verify_disclosure(&1, b"country", b"FR", b"salt-1", &[birth_date]));
+        assert!(!IdPallet::<Test>::verify_disclosure(&1, b"country", b"DE", b"salt-1", &[birth_date]));
+    });
+}
+
+fn sr25519_key(seed: u8) -> DidKey {
+    DidKey { key_type: KeyType::Sr25519, public_key: vec![seed; 32] }
+}
+
+#[test]
+fn test_did_document_key_rotation() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            IdPallet::<Test>::create_did(RawOrigin::Signed(1).into(), vec![], vec![]),
+            Error::<Test>::InvalidDidEntries
+        );
+        assert_ok!(IdPallet::<Test>::create_did(RawOrigin::Signed(1).into(), vec![sr25519_key(1)], vec![]));
+        let did = BlakeTwo256::hash_of(&(b"did:nodara", 1u64, 1u64));
+        assert_noop!(
+            IdPallet::<Test>::rotate_did_keys(RawOrigin::Signed(2).into(), did, vec![sr25519_key(2)]),
+            Error::<Test>::NotDidController
+        );
+        assert_ok!(IdPallet::<Test>::rotate_did_keys(RawOrigin::Signed(1).into(), did, vec![sr25519_key(2)]));
+        assert_eq!(IdPallet::<Test>::dids(did).unwrap().keys, vec![sr25519_key(2)]);
+        assert_ok!(IdPallet::<Test>::set_did_controller(RawOrigin::Signed(1).into(), did, 2));
+        assert_ok!(IdPallet::<Test>::deactivate_did(RawOrigin::Signed(2).into(), did));
+        assert_noop!(
+            IdPallet::<Test>::rotate_did_keys(RawOrigin::Signed(2).into(), did, vec![sr25519_key(3)]),
+            Error::<Test>::DidDeactivated
+        );
+    });
+}
+
+#[test]
+fn test_credential_issuance_expiry_and_revocation() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        assert_ok!(IdPallet::<Test>::create_did(RawOrigin::Signed(1).into(), vec![sr25519_key(1)], vec![]));
+        let did = BlakeTwo256::hash_of(&(b"did:nodara", 1u64, 1u64));
+        let claims = H256::repeat_byte(7);
+        assert_noop!(
+            IdPallet::<Test>::issue_credential(RawOrigin::Signed(REGISTRAR).into(), did, b"KycAml".to_vec(), claims, None),
+            Error::<Test>::NotAuthorizedIssuer
+        );
+        assert_ok!(IdPallet::<Test>::add_issuer(RawOrigin::Root.into(), REGISTRAR, vec![b"KycAml".to_vec()]));
+        assert_ok!(IdPallet::<Test>::issue_credential(RawOrigin::Signed(REGISTRAR).into(), did, b"KycAml".to_vec(), claims, Some(10)));
+        let credential_id = BlakeTwo256::hash_of(&(REGISTRAR, did, b"KycAml".to_vec(), claims));
+        assert_eq!(IdPallet::<Test>::credential_status(credential_id), CredentialStatus::Valid);
+        frame_system::Pallet::<Test>::set_block_number(11);
+        assert_eq!(IdPallet::<Test>::credential_status(credential_id), CredentialStatus::Expired);
+        assert_noop!(
+            IdPallet::<Test>::revoke_credential(RawOrigin::Signed(1).into(), credential_id),
+            Error::<Test>::NotCredentialIssuer
+        );
+        assert_ok!(IdPallet::<Test>::revoke_credential(RawOrigin::Signed(REGISTRAR).into(), credential_id));
+        assert_eq!(IdPallet::<Test>::credential_status(credential_id), CredentialStatus::Revoked);
+        assert_eq!(IdPallet::<Test>::credential_status(H256::zero()), CredentialStatus::Unknown);
+    });
+}
+
+#[test]
+fn test_credential_status_follows_issuer_and_subject() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        assert_ok!(IdPallet::<Test>::create_did(RawOrigin::Signed(1).into(), vec![sr25519_key(1)], vec![]));
+        let did = BlakeTwo256::hash_of(&(b"did:nodara", 1u64, 1u64));
+        let claims = H256::repeat_byte(7);
+        assert_ok!(IdPallet::<Test>::add_issuer(RawOrigin::Root.into(), REGISTRAR, vec![b"KycAml".to_vec()]));
+        assert_ok!(IdPallet::<Test>::issue_credential(RawOrigin::Signed(REGISTRAR).into(), did, b"KycAml".to_vec(), claims, None));
+        let credential_id = BlakeTwo256::hash_of(&(REGISTRAR, did, b"KycAml".to_vec(), claims));
+        assert_eq!(IdPallet::<Test>::credential_status(credential_id), CredentialStatus::Valid);
+
+        // Re-registering the issuer without the claim type counts as removal, as does removing it outright.
+        assert_ok!(IdPallet::<Test>::add_issuer(RawOrigin::Root.into(), REGISTRAR, vec![b"DeviceCertification".to_vec()]));
+        assert_eq!(IdPallet::<Test>::credential_status(credential_id), CredentialStatus::IssuerRemoved);
+        assert_ok!(IdPallet::<Test>::remove_issuer(RawOrigin::Root.into(), REGISTRAR));
+        assert_eq!(IdPallet::<Test>::credential_status(credential_id), CredentialStatus::IssuerRemoved);
+
+        assert_ok!(IdPallet::<Test>::add_issuer(RawOrigin::Root.into(), REGISTRAR, vec![b"KycAml".to_vec()]));
+        assert_eq!(IdPallet::<Test>::credential_status(credential_id), CredentialStatus::Valid);
+        assert_ok!(IdPallet::<Test>::deactivate_did(RawOrigin::Signed(1).into(), did));
+        assert_eq!(IdPallet::<Test>::credential_status(credential_id), CredentialStatus::SubjectDeactivated);
+    });
+}
+
+#[test]
+fn test_sub_accounts_share_primary_identity() {
+    new_test_ext().execute_with(|| {
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&1, 100);
+        assert_noop!(
+            IdPallet::<Test>::add_sub(RawOrigin::Signed(1).into(), 2, b"meter-42".to_vec()),
+            Error::<Test>::IdentityNotFound
+        );
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), commitment(1), Vec::new()));
+        assert_ok!(IdPallet::<Test>::add_sub(RawOrigin::Signed(1).into(), 2, b"meter-42".to_vec()));
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 5);
+        // The invitation links nothing until the sub-account accepts it.
+        assert_eq!(<IdPallet<Test> as IdentityProvider<u64>>::identity_of(&2), None);
+        assert_noop!(IdPallet::<Test>::accept_sub(RawOrigin::Signed(3).into(), 1), Error::<Test>::SubNotInvited);
+        assert_ok!(IdPallet::<Test>::accept_sub(RawOrigin::Signed(2).into(), 1));
+        assert_eq!(<IdPallet<Test> as IdentityProvider<u64>>::identity_of(&2), Some(1));
+        assert_noop!(
+            IdPallet::<Test>::register_identity(RawOrigin::Signed(2).into(), commitment(2), Vec::new()),
+            Error::<Test>::AlreadyLinked
+        );
+        assert_ok!(IdPallet::<Test>::quit_sub(RawOrigin::Signed(2).into()));
+        assert_eq!(<IdPallet<Test> as IdentityProvider<u64>>::identity_of(&2), None);
+        assert!(IdPallet::<Test>::subs_of(&1).is_empty());
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 0);
+    });
+}
+
+#[test]
+fn test_sub_invitations_count_against_the_limit_and_can_be_withdrawn() {
+    new_test_ext().execute_with(|| {
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&1, 100);
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), commitment(1), Vec::new()));
+        // MaxSubAccounts is 4, pending invitations included.
+        for sub in 2..6 {
+            assert_ok!(IdPallet::<Test>::add_sub(RawOrigin::Signed(1).into(), sub, b"device".to_vec()));
+        }
+        assert_noop!(
+            IdPallet::<Test>::add_sub(RawOrigin::Signed(1).into(), 6, b"device".to_vec()),
+            Error::<Test>::TooManySubAccounts
+        );
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 20);
+        assert_ok!(IdPallet::<Test>::remove_sub(RawOrigin::Signed(1).into(), 5));
+        assert!(IdPallet::<Test>::pending_subs(&1, &5).is_none());
+        assert_noop!(IdPallet::<Test>::accept_sub(RawOrigin::Signed(5).into(), 1), Error::<Test>::SubNotInvited);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 15);
+        // Without a free balance for the deposit, nothing can be invited.
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(7).into(), commitment(7), Vec::new()));
+        assert_noop!(
+            IdPallet::<Test>::add_sub(RawOrigin::Signed(7).into(), 8, b"device".to_vec()),
+            Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn test_social_recovery_moves_identity_after_delay() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&1, 100);
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&5, 100);
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), commitment(1), Vec::new()));
+        assert_ok!(IdPallet::<Test>::add_sub(RawOrigin::Signed(1).into(), 2, b"hot wallet".to_vec()));
+        assert_ok!(IdPallet::<Test>::accept_sub(RawOrigin::Signed(2).into(), 1));
+        assert_ok!(IdPallet::<Test>::create_recovery(RawOrigin::Signed(1).into(), vec![10, 11, 12], 2, 5));
+        assert_ok!(IdPallet::<Test>::initiate_recovery(RawOrigin::Signed(5).into(), 1));
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&5), 10);
+        assert_noop!(IdPallet::<Test>::vouch_recovery(RawOrigin::Signed(13).into(), 1, 5), Error::<Test>::NotFriend);
+        assert_ok!(IdPallet::<Test>::vouch_recovery(RawOrigin::Signed(10).into(), 1, 5));
+        assert_noop!(IdPallet::<Test>::claim_recovery(RawOrigin::Signed(5).into(), 1), Error::<Test>::ThresholdNotReached);
+        assert_ok!(IdPallet::<Test>::vouch_recovery(RawOrigin::Signed(11).into(), 1, 5));
+        assert_noop!(IdPallet::<Test>::claim_recovery(RawOrigin::Signed(5).into(), 1), Error::<Test>::RecoveryDelayPending);
+        frame_system::Pallet::<Test>::set_block_number(6);
+        assert_ok!(IdPallet::<Test>::claim_recovery(RawOrigin::Signed(5).into(), 1));
+        assert!(IdPallet::<Test>::identities(&1).is_none());
+        assert_eq!(IdPallet::<Test>::identities(&5).unwrap().kyc_commitment, commitment(1));
+        assert_eq!(<IdPallet<Test> as IdentityProvider<u64>>::identity_of(&2), Some(5));
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&5), 0);
+    });
+}
+
+#[test]
+fn test_claimed_recovery_returns_competing_rescuers_deposits() {
+    new_test_ext().execute_with(|| {
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&5, 100);
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&6, 100);
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), commitment(1), Vec::new()));
+        assert_ok!(IdPallet::<Test>::create_recovery(RawOrigin::Signed(1).into(), vec![10], 1, 0));
+        assert_ok!(IdPallet::<Test>::initiate_recovery(RawOrigin::Signed(5).into(), 1));
+        assert_ok!(IdPallet::<Test>::initiate_recovery(RawOrigin::Signed(6).into(), 1));
+        assert_ok!(IdPallet::<Test>::vouch_recovery(RawOrigin::Signed(10).into(), 1, 5));
+        assert_ok!(IdPallet::<Test>::claim_recovery(RawOrigin::Signed(5).into(), 1));
+        // The losing rescuer's attempt ends with the recovery, and the lost key cannot take its deposit.
+        assert!(IdPallet::<Test>::active_recoveries(&1, &6).is_none());
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&6), 0);
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&6), 100);
+        assert_noop!(IdPallet::<Test>::cancel_recovery(RawOrigin::Signed(1).into(), 6), Error::<Test>::RecoveryNotStarted);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&5), 0);
+    });
+}
+
+#[test]
+fn test_owner_cancels_recovery_and_keeps_deposit() {
+    new_test_ext().execute_with(|| {
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&5, 100);
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), commitment(1), Vec::new()));
+        assert_ok!(IdPallet::<Test>::create_recovery(RawOrigin::Signed(1).into(), vec![10], 1, 0));
+        assert_ok!(IdPallet::<Test>::initiate_recovery(RawOrigin::Signed(5).into(), 1));
+        assert_ok!(IdPallet::<Test>::cancel_recovery(RawOrigin::Signed(1).into(), 5));
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&5), 90);
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&1), 10);
+    });
+}
+
+#[test]
+fn test_judgements_expire_and_can_be_renewed() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&1, 100);
+        assert_ok!(IdPallet::<Test>::add_registrar(RawOrigin::Root.into(), REGISTRAR, 10));
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), commitment(1), Vec::new()));
+        assert_ok!(IdPallet::<Test>::request_judgement(RawOrigin::Signed(1).into(), 0, 10));
+        assert_ok!(IdPallet::<Test>::provide_judgement(RawOrigin::Signed(REGISTRAR).into(), 0, 1, commitment(1), Judgement::KnownGood));
+        assert_noop!(IdPallet::<Test>::request_judgement(RawOrigin::Signed(1).into(), 0, 10), Error::<Test>::StickyJudgement);
+        // JudgementValidity is 100 blocks.
+        frame_system::Pallet::<Test>::set_block_number(101);
+        assert!(!IdPallet::<Test>::is_identity_verified(&1));
+        assert_ok!(IdPallet::<Test>::request_judgement(RawOrigin::Signed(1).into(), 0, 10));
+        assert_ok!(IdPallet::<Test>::provide_judgement(RawOrigin::Signed(REGISTRAR).into(), 0, 1, commitment(1), Judgement::KnownGood));
+        assert!(IdPallet::<Test>::is_identity_verified(&1));
+    });
+}
+
+#[test]
+fn test_clear_identity_releases_funds_and_erases_history() {
+    new_test_ext().execute_with(|| {
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&1, 100);
+        let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&5, 100);
+        assert_ok!(IdPallet::<Test>::add_registrar(RawOrigin::Root.into(), REGISTRAR, 10));
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), commitment(1), Vec::new()));
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(3).into(), commitment(3), Vec::new()));
+        assert_ok!(IdPallet::<Test>::request_judgement(RawOrigin::Signed(1).into(), 0, 10));
+        assert_ok!(IdPallet::<Test>::add_sub(RawOrigin::Signed(1).into(), 2, b"device".to_vec()));
+        assert_ok!(IdPallet::<Test>::accept_sub(RawOrigin::Signed(2).into(), 1));
+        assert_ok!(IdPallet::<Test>::add_sub(RawOrigin::Signed(1).into(), 4, b"meter".to_vec()));
+        assert_ok!(IdPallet::<Test>::create_recovery(RawOrigin::Signed(1).into(), vec![10], 1, 0));
+        assert_ok!(IdPallet::<Test>::initiate_recovery(RawOrigin::Signed(5).into(), 1));
+        assert_ok!(IdPallet::<Test>::clear_identity(RawOrigin::Signed(1).into()));
+        assert!(IdPallet::<Test>::identities(&1).is_none());
+        assert!(IdPallet::<Test>::super_of(&2).is_none());
+        assert!(IdPallet::<Test>::pending_subs(&1, &4).is_none());
+        assert!(IdPallet::<Test>::recoverable(&1).is_none());
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 0);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&5), 0);
+        let history = IdPallet::<Test>::identity_history();
+        assert!(history.iter().all(|entry| entry.1 != 1));
+        assert_eq!(history.len(), 1);
+        assert_noop!(IdPallet::<Test>::clear_identity(RawOrigin::Signed(1).into()), Error::<Test>::IdentityNotFound);
     });
 }
@@ -0,0 +1,677 @@
+// tests/unit_tests/nodara_interop_tests.rs
+
+use crate::mock::{new_test_ext, stub_bls_signature, InteropChainId, StubBlsVerifier, Test, INTEROP_OUTCOMES};
+use codec::{Decode, Encode};
+use frame_support::{assert_noop, assert_ok, traits::Get};
+use frame_system::RawOrigin;
+use nodara_interop::light_client::{
+    decode_hex_prefix, is_valid_merkle_branch, mpt_get, rlp_split, AuthorityList, BeaconBlockHeader, FinalizedHeader,
+    GrandpaClient, GrandpaJustification, GrandpaState, GrandpaUpdate, LightClient, LightClientError, SubstrateHeader,
+    SyncCommittee, SyncCommitteeClient, SyncCommitteeState, SyncCommitteeUpdate, GRANDPA_ENGINE_ID,
+    SLOTS_PER_SYNC_COMMITTEE_PERIOD, SYNC_COMMITTEE_SIZE,
+};
+use nodara_interop::pallet::{Channel, ClientState, ClientUpdate, Error, Pallet as InteropPallet};
+use sp_core::{ed25519, Pair, H160, H256};
+use sp_io::hashing::{keccak_256, sha2_256};
+use sp_runtime::{
+    traits::{BlakeTwo256, Header as HeaderT},
+    Digest, DigestItem,
+};
+use sp_trie::{trie_types::TrieDBMutBuilderV1, MemoryDB, TrieMut};
+
+const CHAIN: u32 = 7;
+const ETHEREUM: u32 = 8;
+const RELAYER: u64 = 50;
+/// Bridge contract of the Ethereum counterpart.
+const BRIDGE: H160 = H160([0xbb; 20]);
+
+type SyncClient = SyncCommitteeClient<StubBlsVerifier>;
+
+/// GRANDPA authorities of the counterpart chain.
+fn authorities() -> Vec<ed25519::Pair> {
+    (1..=4).map(|seed| ed25519::Pair::from_seed(&[seed; 32])).collect()
+}
+
+/// Opens the channel to the counterpart, authorizes the relayer and tracks the counterpart from its genesis.
+fn setup_channel() {
+    assert_ok!(InteropPallet::<Test>::open_channel(RawOrigin::Root.into(), CHAIN));
+    assert_ok!(InteropPallet::<Test>::add_relayer(RawOrigin::Root.into(), RELAYER));
+    let state = GrandpaState {
+        set_id: 0,
+        authorities: authorities().iter().map(|pair| (pair.public(), 1)).collect(),
+        finalized_number: 0,
+        interop_pallet: b"Interop".to_vec(),
+    };
+    let checkpoint = FinalizedHeader { number: 0, hash: H256::zero(), state_root: H256::zero() };
+    assert_ok!(InteropPallet::<Test>::register_light_client(RawOrigin::Root.into(), CHAIN, ClientState::Grandpa(state), checkpoint));
+}
+
+/// Finalizes counterpart header `number` with state root `state_root`.
+fn finalize(number: u32, state_root: H256) {
+    let header = SubstrateHeader::new(number, H256::zero(), state_root, H256::zero(), Default::default());
+    assert_ok!(InteropPallet::<Test>::submit_finality_update(RawOrigin::Signed(RELAYER).into(), CHAIN, grandpa_update(header, &authorities())));
+}
+
+/// Builds a counterpart state holding `value` under `key`; returns its root and the encoded storage proof.
+fn storage_proof(key: &[u8], value: &[u8]) -> (H256, Vec<u8>) {
+    let mut db = MemoryDB::<BlakeTwo256>::default();
+    let mut root = H256::zero();
+    {
+        let mut trie = TrieDBMutBuilderV1::<BlakeTwo256>::new(&mut db, &mut root).build();
+        trie.insert(key, value).unwrap();
+    }
+    let nodes: Vec<Vec<u8>> = db.drain().into_values().filter(|(_, rc)| *rc > 0).map(|(node, _)| node).collect();
+    (root, nodes.encode())
+}
+
+/// Finalizes counterpart header `number` with a state in which its channel to Nodara expects inbound nonce
+/// `next_inbound_nonce`; returns the proof of that channel.
+fn finalize_channel(number: u32, next_inbound_nonce: u64) -> Vec<u8> {
+    let key = GrandpaClient::channel_storage_key(b"Interop", InteropChainId::get());
+    let channel = Channel { open: true, next_outbound_nonce: 0, next_pending_nonce: 0, next_inbound_nonce };
+    let (root, proof) = storage_proof(&key, &channel.encode());
+    finalize(number, root);
+    proof
+}
+
+/// Builds a GRANDPA update finalizing `header`, signed by `signers` as authority set `set_id`.
+fn grandpa_update_in_set(header: SubstrateHeader, signers: &[ed25519::Pair], set_id: u64) -> GrandpaUpdate {
+    let hash = header.hash();
+    let message = GrandpaClient::precommit_payload(&hash, header.number, 1, set_id);
+    let precommits = signers.iter().map(|pair| (pair.public(), pair.sign(&message))).collect();
+    let justification = GrandpaJustification { round: 1, target_hash: hash, target_number: header.number, precommits };
+    GrandpaUpdate { header, justification }
+}
+
+/// Builds a GRANDPA update finalizing `header`, signed by `signers` as the genesis authority set.
+fn grandpa_update(header: SubstrateHeader, signers: &[ed25519::Pair]) -> ClientUpdate {
+    ClientUpdate::Grandpa(grandpa_update_in_set(header, signers, 0))
+}
+
+/// Returns header `number` with the GRANDPA consensus log `log` (its index, then its data) in its digest.
+fn header_with_grandpa_log(number: u32, log: Vec<u8>) -> SubstrateHeader {
+    let digest = Digest { logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, log)] };
+    SubstrateHeader::new(number, H256::zero(), H256::zero(), H256::zero(), digest)
+}
+
+/// Sync committee whose member keys are derived from `seed`.
+fn sync_committee(seed: u8) -> SyncCommittee {
+    let pubkeys = (0..SYNC_COMMITTEE_SIZE as u16)
+        .map(|member| {
+            let mut pubkey = [seed; 48];
+            pubkey[..2].copy_from_slice(&member.to_le_bytes());
+            pubkey
+        })
+        .collect();
+    SyncCommittee { pubkeys, aggregate_pubkey: [seed; 48] }
+}
+
+/// Sync committee client state at `finalized_slot`, with committee 1 current and the next one unknown.
+fn sync_state(finalized_slot: u64) -> SyncCommitteeState {
+    SyncCommitteeState {
+        genesis_validators_root: H256::repeat_byte(9),
+        fork_version: [4, 0, 0, 0],
+        current_sync_committee: sync_committee(1),
+        next_sync_committee: None,
+        finalized_slot,
+        bridge_contract: BRIDGE,
+        messages_slot: H256::from_low_u64_be(3),
+        inbound_nonces_slot: H256::from_low_u64_be(4),
+    }
+}
+
+/// Builds an SSZ tree with `leaves` at their generalized indices and zero chunks elsewhere; returns its root and the
+/// branch of each leaf.
+fn ssz_tree(leaves: &[(u64, H256)]) -> (H256, Vec<Vec<H256>>) {
+    fn depth(gindex: u64) -> u32 {
+        63 - gindex.leading_zeros()
+    }
+    fn node(gindex: u64, max_depth: u32, leaves: &[(u64, H256)]) -> H256 {
+        if let Some((_, leaf)) = leaves.iter().find(|(leaf_gindex, _)| *leaf_gindex == gindex) {
+            return *leaf;
+        }
+        if depth(gindex) >= max_depth {
+            return H256::zero();
+        }
+        let (left, right) = (node(2 * gindex, max_depth, leaves), node(2 * gindex + 1, max_depth, leaves));
+        H256(sha2_256(&[left.as_bytes(), right.as_bytes()].concat()))
+    }
+    let max_depth = leaves.iter().map(|(gindex, _)| depth(*gindex)).max().unwrap_or(0);
+    let branches = leaves
+        .iter()
+        .map(|(gindex, _)| (0..depth(*gindex)).map(|level| node((gindex >> level) ^ 1, max_depth, leaves)).collect())
+        .collect();
+    (node(1, max_depth, leaves), branches)
+}
+
+/// Builds a sync committee update finalizing `finalized_slot` with execution state root `execution_state_root`,
+/// attested at the next slot and signed the slot after by the first `signers` members of `committee`. The attested
+/// state holds `next` as its next sync committee, which the update then proves.
+fn sync_update(
+    state: &SyncCommitteeState,
+    committee: &SyncCommittee,
+    finalized_slot: u64,
+    execution_state_root: H256,
+    next: Option<&SyncCommittee>,
+    signers: usize,
+) -> SyncCommitteeUpdate {
+    let (body_root, execution_branches) = ssz_tree(&[(802, execution_state_root)]);
+    let finalized_header =
+        BeaconBlockHeader { slot: finalized_slot, proposer_index: 0, parent_root: H256::zero(), state_root: H256::repeat_byte(2), body_root };
+    let mut leaves = vec![(105, finalized_header.hash_tree_root())];
+    if let Some(next) = next {
+        leaves.push((55, next.hash_tree_root().unwrap()));
+    }
+    let (attested_state_root, branches) = ssz_tree(&leaves);
+    let attested_header = BeaconBlockHeader {
+        slot: finalized_slot + 1,
+        proposer_index: 0,
+        parent_root: H256::zero(),
+        state_root: attested_state_root,
+        body_root: H256::zero(),
+    };
+    let mut sync_committee_bits = vec![0u8; SYNC_COMMITTEE_SIZE / 8];
+    for member in 0..signers {
+        sync_committee_bits[member / 8] |= 1 << (member % 8);
+    }
+    let signing_root = SyncClient::signing_root(state, &attested_header);
+    SyncCommitteeUpdate {
+        attested_header,
+        finalized_header,
+        finality_branch: branches[0].clone(),
+        execution_state_root,
+        execution_branch: execution_branches[0].clone(),
+        next_sync_committee: next.map(|next| (next.clone(), branches[1].clone())),
+        sync_committee_bits,
+        sync_committee_signature: stub_bls_signature(&committee.pubkeys[..signers], &signing_root),
+        signature_slot: finalized_slot + 2,
+    }
+}
+
+/// RLP encoding of a byte string.
+fn rlp_bytes(data: &[u8]) -> Vec<u8> {
+    match data {
+        [byte] if *byte < 0x80 => vec![*byte],
+        _ => [rlp_length_prefix(0x80, data.len()), data.to_vec()].concat(),
+    }
+}
+
+/// RLP encoding of a list of encoded items.
+fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    [rlp_length_prefix(0xc0, payload.len()), payload].concat()
+}
+
+fn rlp_length_prefix(offset: u8, len: usize) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+    let len: Vec<u8> = len.to_be_bytes().into_iter().skip_while(|byte| *byte == 0).collect();
+    [vec![offset + 55 + len.len() as u8], len].concat()
+}
+
+/// Hex-prefix encoding of a nibble path.
+fn hex_prefix(nibbles: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = (nibbles.len() % 2) as u8 + if leaf { 2 } else { 0 };
+    let (first, rest) = match nibbles.len() % 2 {
+        1 => (flag << 4 | nibbles[0], &nibbles[1..]),
+        _ => (flag << 4, nibbles),
+    };
+    std::iter::once(first).chain(rest.chunks(2).map(|pair| pair[0] << 4 | pair[1])).collect()
+}
+
+/// Encodes the Merkle-Patricia trie node holding `entries` (remaining nibble paths and values), adding every node
+/// referenced by hash to `nodes`.
+fn mpt_node(entries: &[(Vec<u8>, Vec<u8>)], nodes: &mut Vec<Vec<u8>>) -> Vec<u8> {
+    if let [(path, value)] = entries {
+        return rlp_list(&[rlp_bytes(&hex_prefix(path, true)), rlp_bytes(value)]);
+    }
+    let first = &entries[0].0;
+    let shared = (0..first.len()).take_while(|&i| entries.iter().all(|(path, _)| path[i] == first[i])).count();
+    if shared > 0 {
+        let rest: Vec<_> = entries.iter().map(|(path, value)| (path[shared..].to_vec(), value.clone())).collect();
+        let child = mpt_node(&rest, nodes);
+        return rlp_list(&[rlp_bytes(&hex_prefix(&first[..shared], false)), mpt_reference(child, nodes)]);
+    }
+    let mut items: Vec<Vec<u8>> = (0..16u8)
+        .map(|nibble| {
+            let group: Vec<_> = entries
+                .iter()
+                .filter(|(path, _)| path[0] == nibble)
+                .map(|(path, value)| (path[1..].to_vec(), value.clone()))
+                .collect();
+            if group.is_empty() {
+                return rlp_bytes(&[]);
+            }
+            let child = mpt_node(&group, nodes);
+            mpt_reference(child, nodes)
+        })
+        .collect();
+    items.push(rlp_bytes(&[]));
+    rlp_list(&items)
+}
+
+/// Returns how a parent refers to `node`: embedded if shorter than 32 bytes, by hash otherwise.
+fn mpt_reference(node: Vec<u8>, nodes: &mut Vec<Vec<u8>>) -> Vec<u8> {
+    if node.len() < 32 {
+        return node;
+    }
+    let reference = rlp_bytes(&keccak_256(&node));
+    nodes.push(node);
+    reference
+}
+
+/// Builds a Merkle-Patricia trie of `entries`; returns its root and all its nodes, which prove any of the keys.
+fn mpt(entries: &[([u8; 32], Vec<u8>)]) -> (H256, Vec<Vec<u8>>) {
+    let entries: Vec<(Vec<u8>, Vec<u8>)> = entries
+        .iter()
+        .map(|(key, value)| (key.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect(), value.clone()))
+        .collect();
+    let mut nodes = Vec::new();
+    let root = mpt_node(&entries, &mut nodes);
+    let hash = H256(keccak_256(&root));
+    nodes.push(root);
+    (hash, nodes)
+}
+
+/// Builds an execution state in which the bridge contract holds `slots` (slot and 32-byte word); returns the state
+/// root and the `eth_getProof` proof (account and storage nodes) of every slot.
+fn bridge_state(slots: &[(H256, [u8; 32])]) -> (H256, Vec<u8>) {
+    let storage: Vec<_> = slots
+        .iter()
+        .map(|(slot, word)| {
+            let value: Vec<u8> = word.iter().copied().skip_while(|byte| *byte == 0).collect();
+            (keccak_256(slot.as_bytes()), rlp_bytes(&value))
+        })
+        .collect();
+    let (storage_root, storage_proof) = mpt(&storage);
+    let account = |nonce: &[u8], storage_root: &H256| {
+        rlp_list(&[rlp_bytes(nonce), rlp_bytes(&[]), rlp_bytes(storage_root.as_bytes()), rlp_bytes(&[0xcc; 32])])
+    };
+    // Another account, so the contract's leaf sits below a branch.
+    let (state_root, account_proof) = mpt(&[
+        (keccak_256(BRIDGE.as_bytes()), account(&[1], &storage_root)),
+        (keccak_256(&[0xaa; 20]), account(&[7], &H256::repeat_byte(0xee))),
+    ]);
+    (state_root, (account_proof, storage_proof).encode())
+}
+
+fn outcomes() -> Vec<(u32, u64, u64, bool)> {
+    INTEROP_OUTCOMES.with(|outcomes| outcomes.borrow().clone())
+}
+
+#[test]
+fn test_messages_are_sequenced_and_acknowledged_in_order() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        assert_noop!(
+            InteropPallet::<Test>::send_message(RawOrigin::Signed(1).into(), CHAIN, b"Transfer".to_vec()),
+            Error::<Test>::ChannelNotFound
+        );
+        setup_channel();
+        assert_ok!(InteropPallet::<Test>::send_message(RawOrigin::Signed(1).into(), CHAIN, b"Transfer1".to_vec()));
+        assert_ok!(InteropPallet::<Test>::send_message(RawOrigin::Signed(2).into(), CHAIN, b"Transfer2".to_vec()));
+        assert_eq!(InteropPallet::<Test>::outbound_queue(CHAIN, 1).unwrap().sender, 2);
+        // Messages expire `BaseTimeout` (10) blocks of the destination past its latest tracked header.
+        assert_eq!(InteropPallet::<Test>::outbound_queue(CHAIN, 1).unwrap().timeout_at, 10);
+
+        // Only relayers acknowledge, only the oldest pending message, and only once the destination received it.
+        let received_one = finalize_channel(1, 1);
+        let ack = |sender: u64, nonce: u64, header: u64, proof: &Vec<u8>| {
+            InteropPallet::<Test>::acknowledge(RawOrigin::Signed(sender).into(), CHAIN, nonce, header, proof.clone())
+        };
+        assert_noop!(ack(1, 0, 1, &received_one), Error::<Test>::NotRelayer);
+        assert_noop!(ack(RELAYER, 1, 1, &received_one), Error::<Test>::UnexpectedNonce);
+        assert_noop!(ack(RELAYER, 0, 2, &received_one), Error::<Test>::HeaderNotFound);
+        let key = GrandpaClient::channel_storage_key(b"Interop", InteropChainId::get());
+        assert_noop!(ack(RELAYER, 0, 1, &storage_proof(&key, b"forged").1), Error::<Test>::VerificationFailed);
+        assert_ok!(ack(RELAYER, 0, 1, &received_one));
+        assert_noop!(ack(RELAYER, 1, 1, &received_one), Error::<Test>::NotDelivered);
+        let received_two = finalize_channel(2, 2);
+        assert_ok!(ack(RELAYER, 1, 2, &received_two));
+        assert_eq!(outcomes(), vec![(CHAIN, 0, 1, true), (CHAIN, 1, 2, true)]);
+        assert_eq!(InteropPallet::<Test>::channels(CHAIN).unwrap().next_pending_nonce, 2);
+
+        // A closed channel accepts no new outbound messages.
+        assert_ok!(InteropPallet::<Test>::close_channel(RawOrigin::Root.into(), CHAIN));
+        assert_noop!(
+            InteropPallet::<Test>::send_message(RawOrigin::Signed(1).into(), CHAIN, b"Transfer3".to_vec()),
+            Error::<Test>::ChannelClosed
+        );
+    });
+}
+
+#[test]
+fn test_unacknowledged_messages_time_out_with_rollback() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        setup_channel();
+        for sender in 1..=3 {
+            assert_ok!(InteropPallet::<Test>::send_message(RawOrigin::Signed(sender).into(), CHAIN, b"Transfer".to_vec()));
+        }
+        let timeout = |nonce: u64, header: u64, proof: &Vec<u8>| {
+            InteropPallet::<Test>::timeout_message(RawOrigin::Signed(RELAYER).into(), CHAIN, nonce, header, proof.clone())
+        };
+
+        // Local blocks do not count: messages expire at block 10 of the destination.
+        frame_system::Pallet::<Test>::set_block_number(100);
+        let received_none = finalize_channel(9, 0);
+        assert_noop!(timeout(0, 9, &received_none), Error::<Test>::TimeoutNotReached);
+        // A message the destination received cannot time out, even once expired; it is acknowledged late instead.
+        let received_one = finalize_channel(10, 1);
+        assert_noop!(timeout(0, 10, &received_one), Error::<Test>::AlreadyDelivered);
+        assert_ok!(InteropPallet::<Test>::acknowledge(RawOrigin::Signed(RELAYER).into(), CHAIN, 0, 10, received_one.clone()));
+
+        // The next message was not received by the expiry height: it times out, oldest first, and closes the channel.
+        assert_noop!(timeout(2, 10, &received_one), Error::<Test>::UnexpectedNonce);
+        assert_ok!(timeout(1, 10, &received_one));
+        assert_eq!(outcomes(), vec![(CHAIN, 0, 1, true), (CHAIN, 1, 2, false)]);
+        assert!(InteropPallet::<Test>::outbound_queue(CHAIN, 1).is_none());
+        assert!(!InteropPallet::<Test>::channels(CHAIN).unwrap().open);
+        assert_noop!(
+            InteropPallet::<Test>::acknowledge(RawOrigin::Signed(RELAYER).into(), CHAIN, 1, 10, received_one.clone()),
+            Error::<Test>::UnexpectedNonce
+        );
+        // Later messages cannot be delivered in order any more and time out in turn.
+        assert_ok!(timeout(2, 10, &received_one));
+        assert_eq!(outcomes().last(), Some(&(CHAIN, 2, 3, false)));
+
+        // The destination still expects nonce 1, so the channel can never carry messages again.
+        assert_noop!(InteropPallet::<Test>::open_channel(RawOrigin::Root.into(), CHAIN), Error::<Test>::ChannelTimedOut);
+        assert_noop!(
+            InteropPallet::<Test>::send_message(RawOrigin::Signed(1).into(), CHAIN, b"Transfer".to_vec()),
+            Error::<Test>::ChannelClosed
+        );
+    });
+}
+
+#[test]
+fn test_inbound_messages_require_inclusion_proofs_against_finalized_headers() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        setup_channel();
+        let authorities = authorities();
+
+        // The counterpart queued "Mint 100" as message 0 to Nodara, expiring at Nodara's block 50.
+        let key = GrandpaClient::message_storage_key(b"Interop", InteropChainId::get(), 0);
+        let (state_root, proof) = storage_proof(&key, &([3u8; 32], b"Mint 100".to_vec(), 5u32, 50u64).encode());
+        let header = SubstrateHeader::new(10, H256::zero(), state_root, H256::zero(), Default::default());
+
+        // Two of four authorities are not a supermajority.
+        assert_noop!(
+            InteropPallet::<Test>::submit_finality_update(RawOrigin::Signed(RELAYER).into(), CHAIN, grandpa_update(header.clone(), &authorities[..2])),
+            Error::<Test>::InvalidFinalityUpdate
+        );
+        assert_ok!(InteropPallet::<Test>::submit_finality_update(RawOrigin::Signed(RELAYER).into(), CHAIN, grandpa_update(header.clone(), &authorities[..3])));
+        assert_eq!(InteropPallet::<Test>::latest_finalized(CHAIN), Some(10));
+        assert_noop!(
+            InteropPallet::<Test>::submit_finality_update(RawOrigin::Signed(RELAYER).into(), CHAIN, grandpa_update(header, &authorities)),
+            Error::<Test>::StaleHeader
+        );
+
+        let receive = |nonce: u64, payload: &[u8], timeout_at: u64, header: u64| {
+            InteropPallet::<Test>::receive_message(RawOrigin::Signed(RELAYER).into(), CHAIN, nonce, payload.to_vec(), timeout_at, header, proof.clone())
+        };
+        // Forged payloads or expiries, untracked headers and out-of-sequence nonces are rejected.
+        assert_noop!(receive(0, b"Mint 1000000", 50, 10), Error::<Test>::VerificationFailed);
+        assert_noop!(receive(0, b"Mint 100", 500, 10), Error::<Test>::VerificationFailed);
+        assert_noop!(receive(0, b"Mint 100", 50, 9), Error::<Test>::HeaderNotFound);
+        assert_noop!(receive(1, b"Mint 100", 50, 10), Error::<Test>::UnexpectedNonce);
+        // Expiry is checked against Nodara's own block number, not the counterpart's finalized height.
+        frame_system::Pallet::<Test>::set_block_number(50);
+        assert_noop!(receive(0, b"Mint 100", 50, 10), Error::<Test>::MessageExpired);
+        frame_system::Pallet::<Test>::set_block_number(49);
+        assert_ok!(receive(0, b"Mint 100", 50, 10));
+        assert_eq!(InteropPallet::<Test>::incoming_messages(CHAIN, 0).unwrap().proven_at, 10);
+    });
+}
+
+#[test]
+fn test_merkle_branches_are_checked() {
+    let leaves: Vec<H256> = (0..4u8).map(H256::repeat_byte).collect();
+    let hash = |left: &H256, right: &H256| H256(sp_io::hashing::sha2_256(&[left.as_bytes(), right.as_bytes()].concat()));
+    let (left, right) = (hash(&leaves[0], &leaves[1]), hash(&leaves[2], &leaves[3]));
+    let root = hash(&left, &right);
+    assert!(is_valid_merkle_branch(&leaves[2], &[leaves[3], left], 2, 2, &root));
+    assert!(!is_valid_merkle_branch(&leaves[2], &[leaves[3], left], 2, 3, &root));
+    assert!(!is_valid_merkle_branch(&leaves[2], &[leaves[3]], 2, 2, &root));
+}
+
+#[test]
+fn test_grandpa_client_follows_authority_set_changes() {
+    new_test_ext().execute_with(|| {
+        let old_set = authorities();
+        let new_set: Vec<ed25519::Pair> = (5..=7).map(|seed| ed25519::Pair::from_seed(&[seed; 32])).collect();
+        let new_list: AuthorityList = new_set.iter().map(|pair| (pair.public(), 1)).collect();
+        let mut state = GrandpaState {
+            set_id: 0,
+            authorities: old_set.iter().map(|pair| (pair.public(), 1)).collect(),
+            finalized_number: 0,
+            interop_pallet: b"Interop".to_vec(),
+        };
+        let scheduled = |delay: u32| [vec![1u8], (new_list.clone(), delay).encode()].concat();
+
+        // Delayed, forced and garbled changes are refused and leave the state as it was.
+        for (log, error) in [
+            (scheduled(5), LightClientError::UnsupportedAuthorityChange),
+            ([vec![2u8], (0u32, new_list.clone(), 0u32).encode()].concat(), LightClientError::UnsupportedAuthorityChange),
+            (vec![1u8, 0xff], LightClientError::Malformed),
+        ] {
+            let update = grandpa_update_in_set(header_with_grandpa_log(1, log), &old_set, 0);
+            assert_eq!(GrandpaClient::verify_update(&mut state, update), Err(error));
+        }
+        assert_eq!((state.set_id, state.finalized_number), (0, 0));
+
+        // The outgoing set finalizes the header scheduling the change, which takes effect immediately.
+        let change = grandpa_update_in_set(header_with_grandpa_log(1, scheduled(0)), &old_set, 0);
+        assert_eq!(GrandpaClient::verify_update(&mut state, change).map(|header| header.number), Ok(1));
+        assert_eq!(state.set_id, 1);
+        assert_eq!(state.authorities, new_list);
+
+        // Later headers need precommits of the new set, for the new set ID, each counted once.
+        let next = SubstrateHeader::new(2, H256::zero(), H256::zero(), H256::zero(), Default::default());
+        let mut duplicated = grandpa_update_in_set(next.clone(), &new_set[..2], 1);
+        duplicated.justification.precommits.push(duplicated.justification.precommits[0].clone());
+        for (update, error) in [
+            (grandpa_update_in_set(next.clone(), &old_set, 1), LightClientError::UnknownAuthority),
+            (grandpa_update_in_set(next.clone(), &new_set, 0), LightClientError::BadSignature),
+            (duplicated, LightClientError::DuplicateVote),
+        ] {
+            assert_eq!(GrandpaClient::verify_update(&mut state, update), Err(error));
+        }
+        let update = grandpa_update_in_set(next, &new_set, 1);
+        assert_eq!(GrandpaClient::verify_update(&mut state, update).map(|header| header.number), Ok(2));
+    });
+}
+
+#[test]
+fn test_sync_committee_client_rotates_committees_across_periods() {
+    new_test_ext().execute_with(|| {
+        let (current, next) = (sync_committee(1), sync_committee(2));
+        let mut state = sync_state(100);
+        let root = H256::repeat_byte(7);
+        let in_next_period = SLOTS_PER_SYNC_COMMITTEE_PERIOD + 10;
+
+        // The next period's committee is unknown until an update proves it.
+        let early = sync_update(&state, &next, in_next_period, root, None, 512);
+        assert_eq!(SyncClient::verify_update(&mut state, early), Err(LightClientError::UnknownSyncCommittee));
+
+        // An update of the current period proves the next committee along with the finalized header.
+        let update = sync_update(&state, &current, 200, root, Some(&next), 512);
+        let header = SyncClient::verify_update(&mut state, update).unwrap();
+        assert_eq!((header.number, header.state_root), (200, root));
+        assert_eq!(state.current_sync_committee, current);
+        assert_eq!(state.next_sync_committee.as_ref(), Some(&next));
+
+        // Signatures made in the next period must come from the next committee, which finalizing there rotates in.
+        let forged = sync_update(&state, &current, in_next_period, root, None, 512);
+        assert_eq!(SyncClient::verify_update(&mut state, forged), Err(LightClientError::BadSignature));
+        let update = sync_update(&state, &next, in_next_period, root, None, 512);
+        assert_eq!(SyncClient::verify_update(&mut state, update).map(|header| header.number), Ok(in_next_period));
+        assert_eq!(state.current_sync_committee, next);
+        assert_eq!(state.next_sync_committee, None);
+
+        // Periods cannot be skipped, and finalized headers only move forward.
+        let skipping = sync_update(&state, &next, 3 * SLOTS_PER_SYNC_COMMITTEE_PERIOD, root, None, 512);
+        assert_eq!(SyncClient::verify_update(&mut state, skipping), Err(LightClientError::UnknownSyncCommittee));
+        let stale = sync_update(&state, &next, in_next_period, root, None, 512);
+        assert_eq!(SyncClient::verify_update(&mut state, stale), Err(LightClientError::StaleHeader));
+    });
+}
+
+#[test]
+fn test_sync_committee_updates_need_a_supermajority_and_valid_branches() {
+    new_test_ext().execute_with(|| {
+        let committee = sync_committee(1);
+        let mut state = sync_state(100);
+        let root = H256::repeat_byte(7);
+        let update = |signers: usize| sync_update(&state, &committee, 200, root, None, signers);
+
+        let mut bad_finality = update(512);
+        bad_finality.finality_branch[0] = H256::repeat_byte(0xee);
+        let mut bad_execution = update(512);
+        bad_execution.execution_state_root = H256::repeat_byte(0xee);
+        let mut bad_next = sync_update(&state, &committee, 200, root, Some(&sync_committee(2)), 512);
+        if let Some((_, branch)) = bad_next.next_sync_committee.as_mut() {
+            branch[0] = H256::repeat_byte(0xee);
+        }
+        let mut bad_bits = update(512);
+        bad_bits.sync_committee_bits.pop();
+        let mut unordered = update(512);
+        unordered.signature_slot = unordered.attested_header.slot;
+        let other_fork = SyncCommitteeState { fork_version: [5, 0, 0, 0], ..state.clone() };
+        let wrong_domain = sync_update(&other_fork, &committee, 200, root, None, 512);
+        // 341 of 512 members fall short of two thirds; 342 reach it.
+        let (short, enough) = (update(341), update(342));
+
+        for (update, error) in [
+            (short, LightClientError::InsufficientVotes),
+            (bad_finality, LightClientError::InvalidBranch),
+            (bad_execution, LightClientError::InvalidBranch),
+            (bad_next, LightClientError::InvalidBranch),
+            (bad_bits, LightClientError::Malformed),
+            (unordered, LightClientError::Malformed),
+            (wrong_domain, LightClientError::BadSignature),
+        ] {
+            assert_eq!(SyncClient::verify_update(&mut state, update), Err(error));
+        }
+        assert_eq!(state, sync_state(100));
+        assert_eq!(SyncClient::verify_update(&mut state, enough).map(|header| header.state_root), Ok(root));
+    });
+}
+
+#[test]
+fn test_sync_committee_messages_and_receipts_are_proven_against_the_execution_state() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        let state = sync_state(100);
+        let nodara = InteropChainId::get();
+        // The bridge contract queued "Mint 100" as message 0 to Nodara, and has received 3 of Nodara's messages.
+        let mut received = [0u8; 32];
+        received[31] = 3;
+        let (execution_root, proof) = bridge_state(&[
+            (SyncClient::message_slot(&state, nodara, 0), SyncClient::message_hash(50, b"Mint 100")),
+            (SyncClient::inbound_nonce_slot(&state, nodara), received),
+        ]);
+
+        // The pallet follows the beacon chain with the mock's stub BLS verifier.
+        assert_ok!(InteropPallet::<Test>::open_channel(RawOrigin::Root.into(), ETHEREUM));
+        assert_ok!(InteropPallet::<Test>::add_relayer(RawOrigin::Root.into(), RELAYER));
+        let checkpoint = FinalizedHeader { number: 100, hash: H256::zero(), state_root: H256::zero() };
+        assert_ok!(InteropPallet::<Test>::register_light_client(RawOrigin::Root.into(), ETHEREUM, ClientState::SyncCommittee(state.clone()), checkpoint));
+        assert_ok!(InteropPallet::<Test>::send_message(RawOrigin::Signed(1).into(), ETHEREUM, b"Burn 100".to_vec()));
+        let update = sync_update(&state, &state.current_sync_committee, 200, execution_root, None, 512);
+        assert_ok!(InteropPallet::<Test>::submit_finality_update(RawOrigin::Signed(RELAYER).into(), ETHEREUM, ClientUpdate::SyncCommittee(update)));
+        assert_eq!(InteropPallet::<Test>::latest_finalized(ETHEREUM), Some(200));
+        assert_ok!(InteropPallet::<Test>::receive_message(RawOrigin::Signed(RELAYER).into(), ETHEREUM, 0, b"Mint 100".to_vec(), 50, 200, proof.clone()));
+        assert_ok!(InteropPallet::<Test>::acknowledge(RawOrigin::Signed(RELAYER).into(), ETHEREUM, 0, 200, proof.clone()));
+        assert_eq!(outcomes(), vec![(ETHEREUM, 0, 1, true)]);
+
+        // The committed hash binds the payload and expiry, under the message's own slot of the bridge contract.
+        let header = InteropPallet::<Test>::finalized_headers(ETHEREUM, 200).unwrap();
+        let verify = |state: &SyncCommitteeState, nonce: u64, payload: &[u8], timeout_at: u64, proof: &[u8]| {
+            SyncClient::verify_message(state, &header, nodara, nonce, payload, timeout_at, proof)
+        };
+        assert_eq!(verify(&state, 0, b"Mint 100", 50, &proof), Ok(()));
+        assert_eq!(verify(&state, 0, b"Mint 1000000", 50, &proof), Err(LightClientError::PayloadMismatch));
+        assert_eq!(verify(&state, 0, b"Mint 100", 500, &proof), Err(LightClientError::PayloadMismatch));
+        assert_eq!(verify(&state, 1, b"Mint 100", 50, &proof), Err(LightClientError::MessageNotFound));
+        let other_contract = SyncCommitteeState { bridge_contract: H160::repeat_byte(0xdd), ..state.clone() };
+        assert_eq!(verify(&other_contract, 0, b"Mint 100", 50, &proof), Err(LightClientError::InvalidProof));
+        // Truncated proofs: without the storage nodes, or cut short.
+        let (account_proof, _) = <(Vec<Vec<u8>>, Vec<Vec<u8>>)>::decode(&mut &proof[..]).unwrap();
+        let without_storage = (account_proof, Vec::<Vec<u8>>::new()).encode();
+        assert_eq!(verify(&state, 0, b"Mint 100", 50, &without_storage), Err(LightClientError::InvalidProof));
+        assert_eq!(verify(&state, 0, b"Mint 100", 50, &proof[..proof.len() - 1]), Err(LightClientError::Malformed));
+
+        // Receipts are read from the contract's inbound nonces; a chain it never heard from has none.
+        assert_eq!(SyncClient::verify_inbound_nonce(&state, &header, nodara, &proof), Ok(3));
+        assert_eq!(SyncClient::verify_inbound_nonce(&state, &header, 99, &proof), Ok(0));
+    });
+}
+
+#[test]
+fn test_rlp_items_are_split_and_malformed_ones_rejected() {
+    assert_eq!(rlp_split(&[0x05, 0x01]), Ok((false, &[0x05][..], &[0x01][..])));
+    assert_eq!(rlp_split(b"\x83dog!"), Ok((false, &b"dog"[..], &b"!"[..])));
+    let long = [vec![0xb8, 56], vec![7; 56]].concat();
+    assert_eq!(rlp_split(&long), Ok((false, &long[2..], &[][..])));
+    let list = rlp_list(&[rlp_bytes(b"cat"), rlp_bytes(b"dog")]);
+    assert_eq!(rlp_split(&list), Ok((true, &list[1..], &[][..])));
+    // Empty input, truncated payloads and lengths, and a length overflowing the input.
+    for malformed in [
+        &[][..],
+        &b"\x83do"[..],
+        &[0xb8][..],
+        &[0xb9, 0x01][..],
+        &[0xc2, 0x01][..],
+        &[0xbf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff][..],
+    ] {
+        assert_eq!(rlp_split(malformed), Err(LightClientError::Malformed));
+    }
+}
+
+#[test]
+fn test_hex_prefix_paths_are_decoded() {
+    assert_eq!(decode_hex_prefix(&[0x00, 0x01, 0x23]), Ok((false, vec![0, 1, 2, 3])));
+    assert_eq!(decode_hex_prefix(&[0x11, 0x23]), Ok((false, vec![1, 2, 3])));
+    assert_eq!(decode_hex_prefix(&[0x20, 0x0f, 0x1c]), Ok((true, vec![0, 0x0f, 1, 0x0c])));
+    assert_eq!(decode_hex_prefix(&[0x3f, 0x1c]), Ok((true, vec![0x0f, 1, 0x0c])));
+    assert_eq!(decode_hex_prefix(&[]), Err(LightClientError::Malformed));
+    assert_eq!(decode_hex_prefix(&[0x40, 0x12]), Err(LightClientError::Malformed));
+}
+
+#[test]
+fn test_merkle_patricia_lookups_follow_branches_and_extensions() {
+    let key = |prefix: &[u8]| {
+        let mut key = [0u8; 32];
+        key[..prefix.len()].copy_from_slice(prefix);
+        key
+    };
+    // A branch at the root (nibbles 1 and f), then an extension ("23") to a branch (nibbles 4 and 5).
+    let (root, proof) = mpt(&[
+        (key(&[0x12, 0x34]), b"first".to_vec()),
+        (key(&[0x12, 0x35]), b"second".to_vec()),
+        (key(&[0xf0]), b"third".to_vec()),
+    ]);
+    assert_eq!(mpt_get(&root, &key(&[0x12, 0x34]), &proof), Ok(Some(b"first".to_vec())));
+    assert_eq!(mpt_get(&root, &key(&[0x12, 0x35]), &proof), Ok(Some(b"second".to_vec())));
+    assert_eq!(mpt_get(&root, &key(&[0xf0]), &proof), Ok(Some(b"third".to_vec())));
+    // Absent keys: an empty branch slot, a diverging extension and a diverging leaf.
+    assert_eq!(mpt_get(&root, &key(&[0x90]), &proof), Ok(None));
+    assert_eq!(mpt_get(&root, &key(&[0x13]), &proof), Ok(None));
+    assert_eq!(mpt_get(&root, &key(&[0x12, 0x34, 0x01]), &proof), Ok(None));
+
+    // A wrong root or a proof missing a node on the path does not verify.
+    assert_eq!(mpt_get(&H256::repeat_byte(1), &key(&[0x12, 0x34]), &proof), Err(LightClientError::InvalidProof));
+    let truncated: Vec<Vec<u8>> = proof.iter().filter(|node| !node.windows(5).any(|bytes| bytes == b"first")).cloned().collect();
+    assert_eq!(mpt_get(&root, &key(&[0x12, 0x34]), &truncated), Err(LightClientError::InvalidProof));
+    assert_eq!(mpt_get(&root, &key(&[0xf0]), &truncated), Ok(Some(b"third".to_vec())));
+
+    // Nodes that are not lists, have the wrong arity or are cut short are malformed.
+    let root_node = proof.last().unwrap();
+    for node in [rlp_bytes(b"not a node"), rlp_list(&[rlp_bytes(b"a"), rlp_bytes(b"b"), rlp_bytes(b"c")]), root_node[..root_node.len() - 1].to_vec()] {
+        let root = H256(keccak_256(&node));
+        assert_eq!(mpt_get(&root, &key(&[0x12]), &[node]), Err(LightClientError::Malformed));
+    }
+}
@@ -1,16 +1,405 @@
 // tests/unit_tests/nodara_iot_bridge_tests.rs
 
 use crate::mock::{new_test_ext, Test};
-use frame_support::{assert_ok};
-use nodara_iot_bridge::pallet::{Pallet as IoTBridgePallet};
+use frame_support::{assert_noop, assert_ok, pallet_prelude::*, traits::{Hooks, ReservableCurrency}};
+use frame_system::RawOrigin;
+use nodara_iot_bridge::pallet::{Call, Error, Pallet as IoTBridgePallet, PlausibilityRule, Reading};
+use nodara_reputation::{pallet::Pallet as ReputationPallet, ReputationCategory};
+use nodara_standards::pallet::{FieldType, Pallet as StandardsPallet, SchemaField};
+use sp_core::{sr25519, Pair};
+use sp_runtime::MultiSignature;
+
+fn device_pair(seed: u8) -> sr25519::Pair {
+    sr25519::Pair::from_seed(&[seed; 32])
+}
+
+fn sign(pair: &sr25519::Pair, device_id: &[u8], nonce: u64, payload: &[u8]) -> MultiSignature {
+    pair.sign(&IoTBridgePallet::<Test>::signing_payload(device_id, nonce, payload)).into()
+}
+
+fn register(owner: u64, device_id: &[u8], pair: &sr25519::Pair) {
+    pallet_balances::Pallet::<Test>::make_free_balance_be(&owner, 100);
+    assert_ok!(IoTBridgePallet::<Test>::register_device(RawOrigin::Signed(owner).into(), device_id.to_vec(), pair.public().into(), b"Thermometer".to_vec(), None));
+}
 
 #[test]
 fn test_iot_data_submission() {
     new_test_ext().execute_with(|| {
-        let id = 1;
+        frame_system::Pallet::<Test>::set_block_number(1);
+        let pair = device_pair(1);
+        let device_id = b"Device01".to_vec();
+        register(1, &device_id, &pair);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 10);
         let payload = b"IoT Payload".to_vec();
+        let signature = sign(&pair, &device_id, 0, &payload);
+        assert_ok!(IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), payload, device_id, 0, signature));
+        assert_eq!(IoTBridgePallet::<Test>::iot_data(0).unwrap().nonce, 0);
+        assert_eq!(IoTBridgePallet::<Test>::next_iot_id(), 1);
+    });
+}
+
+#[test]
+fn test_iot_data_rejects_invalid_signatures() {
+    new_test_ext().execute_with(|| {
+        let pair = device_pair(1);
         let device_id = b"Device01".to_vec();
-        let signature = b"ValidSignature".to_vec();
-        assert_ok!(IoTBridgePallet::<Test>::submit_iot_data(id, payload, device_id, signature));
+        let payload = b"IoT Payload".to_vec();
+        let signature = sign(&pair, &device_id, 0, &payload);
+        assert_noop!(
+            IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), payload.clone(), device_id.clone(), 0, signature.clone()),
+            Error::<Test>::DeviceNotFound
+        );
+        register(1, &device_id, &pair);
+        // Tampered payload.
+        assert_noop!(
+            IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), b"Tampered".to_vec(), device_id.clone(), 0, signature.clone()),
+            Error::<Test>::DataVerificationFailed
+        );
+        // Rotated key: signatures by the old key are no longer accepted.
+        let new_pair = device_pair(2);
+        assert_noop!(
+            IoTBridgePallet::<Test>::rotate_device_key(RawOrigin::Signed(2).into(), device_id.clone(), new_pair.public().into()),
+            Error::<Test>::NotDeviceOwner
+        );
+        assert_ok!(IoTBridgePallet::<Test>::rotate_device_key(RawOrigin::Signed(1).into(), device_id.clone(), new_pair.public().into()));
+        assert_noop!(
+            IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), payload.clone(), device_id.clone(), 0, signature),
+            Error::<Test>::DataVerificationFailed
+        );
+        // Revoking returns the deposit and stops the device.
+        assert_ok!(IoTBridgePallet::<Test>::revoke_device(RawOrigin::Signed(1).into(), device_id.clone()));
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 0);
+        assert_noop!(
+            IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), payload.clone(), device_id.clone(), 0, sign(&new_pair, &device_id, 0, &payload)),
+            Error::<Test>::DeviceRevoked
+        );
+    });
+}
+
+#[test]
+fn test_iot_data_rejects_replayed_and_stale_nonces() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        let pair = device_pair(1);
+        let device_id = b"Meter01".to_vec();
+        register(1, &device_id, &pair);
+        let reading = b"kWh:12".to_vec();
+        let signature = sign(&pair, &device_id, 5, &reading);
+        assert_ok!(IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), reading.clone(), device_id.clone(), 5, signature.clone()));
+        frame_system::Pallet::<Test>::set_block_number(3);
+        // The same signed reading cannot be submitted twice.
+        assert_noop!(
+            IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), reading.clone(), device_id.clone(), 5, signature),
+            Error::<Test>::InvalidNonce
+        );
+        // Nor can an older one.
+        let stale = b"kWh:11".to_vec();
+        assert_noop!(
+            IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), stale.clone(), device_id.clone(), 4, sign(&pair, &device_id, 4, &stale)),
+            Error::<Test>::InvalidNonce
+        );
+        let next = b"kWh:13".to_vec();
+        assert_ok!(IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::Signed(7).into(), next.clone(), device_id.clone(), 6, sign(&pair, &device_id, 6, &next)));
+        assert_eq!(IoTBridgePallet::<Test>::device_nonces(&device_id), 7);
+        // Each accepted reading gets its own message ID.
+        assert_eq!(IoTBridgePallet::<Test>::iot_data(0).unwrap().payload, reading);
+        assert_eq!(IoTBridgePallet::<Test>::iot_data(1).unwrap().payload, next);
+    });
+}
+
+#[test]
+fn test_iot_submissions_are_rate_limited_and_validated_unsigned() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        let pair = device_pair(1);
+        let device_id = b"Meter01".to_vec();
+        register(1, &device_id, &pair);
+        let first = b"kWh:1".to_vec();
+        let call = Call::<Test>::submit_iot_data { payload: first.clone(), device_id: device_id.clone(), nonce: 0, signature: sign(&pair, &device_id, 0, &first) };
+        assert!(IoTBridgePallet::<Test>::validate_unsigned(TransactionSource::External, &call).is_ok());
+        let forged = Call::<Test>::submit_iot_data { payload: first.clone(), device_id: device_id.clone(), nonce: 0, signature: sign(&device_pair(9), &device_id, 0, &first) };
+        assert_eq!(
+            IoTBridgePallet::<Test>::validate_unsigned(TransactionSource::External, &forged),
+            InvalidTransaction::BadProof.into()
+        );
+        assert_ok!(IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), first.clone(), device_id.clone(), 0, sign(&pair, &device_id, 0, &first)));
+
+        // A second reading within `MinSubmissionInterval` blocks is rejected.
+        let second = b"kWh:2".to_vec();
+        let signature = sign(&pair, &device_id, 1, &second);
+        frame_system::Pallet::<Test>::set_block_number(2);
+        assert_noop!(
+            IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), second.clone(), device_id.clone(), 1, signature.clone()),
+            Error::<Test>::RateLimited
+        );
+        frame_system::Pallet::<Test>::set_block_number(3);
+        assert_ok!(IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), second, device_id, 1, signature));
+    });
+}
+
+#[test]
+fn test_update_config_requires_governance() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            IoTBridgePallet::<Test>::update_config(RawOrigin::Signed(1).into(), b"Timeout:30".to_vec(), Vec::new()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(IoTBridgePallet::<Test>::update_config(RawOrigin::Root.into(), b"Timeout:30".to_vec(), Vec::new()));
+    });
+}
+
+#[test]
+fn test_readings_are_aggregated_per_window_and_raw_records_pruned() {
+    new_test_ext().execute_with(|| {
+        let pair = device_pair(1);
+        let device_id = b"Thermo01".to_vec();
+        register(1, &device_id, &pair);
+        let submit = |block: u64, nonce: u64, temperature: i64| {
+            frame_system::Pallet::<Test>::set_block_number(block);
+            let payload = vec![Reading { metric: 0, value: temperature }, Reading { metric: 1, value: 40 }].encode();
+            let signature = sign(&pair, &device_id, nonce, &payload);
+            assert_ok!(IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), payload, device_id.clone(), nonce, signature));
+        };
+        submit(1, 0, 20);
+        submit(3, 1, 26);
+        submit(5, 2, 23);
+        // Block 12 falls into the next window.
+        submit(12, 3, 30);
+
+        let temperature = IoTBridgePallet::<Test>::aggregates((device_id.clone(), 0), 0).unwrap();
+        assert_eq!((temperature.count, temperature.min, temperature.max, temperature.mean(), temperature.last), (3, 20, 26, 23, 23));
+        assert_eq!(IoTBridgePallet::<Test>::aggregates((device_id.clone(), 1), 0).unwrap().count, 3);
+        assert_eq!(IoTBridgePallet::<Test>::aggregates((device_id.clone(), 0), 1).unwrap().last, 30);
+
+        // Records older than `RetentionPeriod` are pruned; aggregates are kept.
+        IoTBridgePallet::<Test>::on_initialize(24);
+        assert!(IoTBridgePallet::<Test>::iot_data(0).is_none());
+        assert!(IoTBridgePallet::<Test>::iot_data(1).is_none());
+        assert!(IoTBridgePallet::<Test>::iot_data(2).is_some());
+        assert_eq!(IoTBridgePallet::<Test>::prune_cursor(), 2);
+        IoTBridgePallet::<Test>::on_initialize(40);
+        assert!(IoTBridgePallet::<Test>::iot_data(3).is_none());
+        assert_eq!(IoTBridgePallet::<Test>::aggregates((device_id, 0), 0).unwrap().count, 3);
+    });
+}
+
+#[test]
+fn test_schema_payloads_are_decoded_and_validated() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        let standard = b"TempSensorV1".to_vec();
+        let pair = device_pair(1);
+        let device_id = b"Thermo01".to_vec();
+        pallet_balances::Pallet::<Test>::make_free_balance_be(&1, 100);
+        assert_noop!(
+            IoTBridgePallet::<Test>::register_device(RawOrigin::Signed(1).into(), device_id.clone(), pair.public().into(), Vec::new(), Some(standard.clone())),
+            Error::<Test>::UnknownSchema
+        );
+        assert_ok!(StandardsPallet::<Test>::define_standard(RawOrigin::Signed(1).into(), standard.clone(), b"Temperature sensor".to_vec(), Vec::new()));
+        assert_ok!(StandardsPallet::<Test>::define_payload_schema(RawOrigin::Signed(1).into(), standard.clone(), vec![
+            SchemaField { name: b"temperature".to_vec(), field_type: FieldType::I16, unit: b"cC".to_vec(), min: Some(-4000), max: Some(8500) },
+            SchemaField { name: b"humidity".to_vec(), field_type: FieldType::U8, unit: b"%".to_vec(), min: Some(0), max: Some(100) },
+        ]));
+        assert_ok!(IoTBridgePallet::<Test>::register_device(RawOrigin::Signed(1).into(), device_id.clone(), pair.public().into(), Vec::new(), Some(standard)));
+
+        let submit = |payload: Vec<u8>| {
+            let signature = sign(&pair, &device_id, 0, &payload);
+            IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), payload, device_id.clone(), 0, signature)
+        };
+        // Truncated and over-long payloads.
+        assert_noop!(submit((2150i16,).encode()), Error::<Test>::MalformedPayload);
+        assert_noop!(submit((2150i16, 45u8, 0u8).encode()), Error::<Test>::MalformedPayload);
+        // Humidity above 100%.
+        assert_noop!(submit((2150i16, 120u8).encode()), Error::<Test>::ValueOutOfRange);
+
+        assert_ok!(submit((2150i16, 45u8).encode()));
+        assert_eq!(IoTBridgePallet::<Test>::aggregates((device_id.clone(), 0), 0).unwrap().last, 2150);
+        assert_eq!(IoTBridgePallet::<Test>::aggregates((device_id.clone(), 1), 0).unwrap().last, 45);
+    });
+}
+
+#[test]
+fn test_data_subscriptions_release_escrow_on_key_delivery() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        let device_id = b"Meter01".to_vec();
+        register(1, &device_id, &device_pair(1));
+        pallet_balances::Pallet::<Test>::make_free_balance_be(&2, 100);
+        assert_noop!(
+            IoTBridgePallet::<Test>::create_listing(RawOrigin::Signed(2).into(), device_id.clone(), 30, 10),
+            Error::<Test>::NotDeviceOwner
+        );
+        assert_noop!(
+            IoTBridgePallet::<Test>::create_listing(RawOrigin::Signed(1).into(), device_id.clone(), 0, 10),
+            Error::<Test>::ZeroPrice
+        );
+        assert_ok!(IoTBridgePallet::<Test>::create_listing(RawOrigin::Signed(1).into(), device_id, 30, 10));
+
+        // Subscribing escrows the first period.
+        assert_ok!(IoTBridgePallet::<Test>::subscribe(RawOrigin::Signed(2).into(), 0, [9u8; 32]));
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&2), 30);
+        assert_noop!(IoTBridgePallet::<Test>::renew_subscription(RawOrigin::Signed(2).into(), 0), Error::<Test>::PeriodNotSettled);
+        // Keys can only be delivered to paying subscribers.
+        assert_noop!(
+            IoTBridgePallet::<Test>::deliver_key(RawOrigin::Signed(1).into(), 0, 3, b"sealed-key".to_vec()),
+            Error::<Test>::NotSubscribed
+        );
+
+        // Delivering the key pays the owner.
+        let owner_balance = pallet_balances::Pallet::<Test>::free_balance(&1);
+        assert_ok!(IoTBridgePallet::<Test>::deliver_key(RawOrigin::Signed(1).into(), 0, 2, b"sealed-key".to_vec()));
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&1), owner_balance + 30);
+        assert_eq!(IoTBridgePallet::<Test>::key_deliveries(0, 2), Some((1, b"sealed-key".to_vec())));
+        assert_noop!(
+            IoTBridgePallet::<Test>::deliver_key(RawOrigin::Signed(1).into(), 0, 2, b"sealed-key".to_vec()),
+            Error::<Test>::PeriodNotSettled
+        );
+
+        // The next period starts when the current one ends; cancelling before delivery refunds it.
+        assert_ok!(IoTBridgePallet::<Test>::renew_subscription(RawOrigin::Signed(2).into(), 0));
+        let subscription = IoTBridgePallet::<Test>::subscriptions(0, 2).unwrap();
+        assert_eq!((subscription.period_start, subscription.period_end, subscription.escrow), (11, 21, 30));
+        assert_ok!(IoTBridgePallet::<Test>::cancel_subscription(RawOrigin::Signed(2).into(), 0));
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&2), 0);
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&2), 70);
+        assert!(IoTBridgePallet::<Test>::key_deliveries(0, 2).is_none());
+
+        assert_ok!(IoTBridgePallet::<Test>::close_listing(RawOrigin::Signed(1).into(), 0));
+        assert_noop!(IoTBridgePallet::<Test>::subscribe(RawOrigin::Signed(2).into(), 0, [9u8; 32]), Error::<Test>::ListingClosed);
+    });
+}
+
+#[test]
+fn test_key_delivery_reports_the_amount_actually_paid() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        let device_id = b"Meter02".to_vec();
+        register(1, &device_id, &device_pair(2));
+        pallet_balances::Pallet::<Test>::make_free_balance_be(&2, 100);
+        assert_ok!(IoTBridgePallet::<Test>::create_listing(RawOrigin::Signed(1).into(), device_id, 30, 10));
+        assert_ok!(IoTBridgePallet::<Test>::subscribe(RawOrigin::Signed(2).into(), 0, [9u8; 32]));
+        // Another pallet slashes part of the escrowed reserve before the key is delivered.
+        let _ = pallet_balances::Pallet::<Test>::slash_reserved(&2, 12);
+        let owner_balance = pallet_balances::Pallet::<Test>::free_balance(&1);
+        assert_ok!(IoTBridgePallet::<Test>::deliver_key(RawOrigin::Signed(1).into(), 0, 2, b"sealed-key".to_vec()));
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&1), owner_balance + 18);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&2), 0);
+        assert_eq!(IoTBridgePallet::<Test>::subscriptions(0, 2).unwrap().escrow, 0);
+    });
+}
+
+#[test]
+fn test_anomalous_readings_are_flagged_and_devices_quarantined() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        assert_ok!(ReputationPallet::<Test>::add_reporter(RawOrigin::Root.into(), 99, sp_runtime::Percent::from_percent(100), 1_000));
+        let pair = device_pair(1);
+        let device_id = b"Thermo01".to_vec();
+        register(1, &device_id, &pair);
+        let rule = PlausibilityRule { min: Some(-400), max: Some(850), max_delta: Some(50), max_repeats: Some(1) };
+        assert_noop!(
+            IoTBridgePallet::<Test>::set_plausibility_rule(RawOrigin::Signed(2).into(), device_id.clone(), 0, Some(rule)),
+            Error::<Test>::NotDeviceOwner
+        );
+        assert_ok!(IoTBridgePallet::<Test>::set_plausibility_rule(RawOrigin::Signed(1).into(), device_id.clone(), 0, Some(rule)));
+        let score = ReputationPallet::<Test>::current_score(&1, ReputationCategory::IotDataQuality);
+
+        let mut nonce = 0;
+        let mut submit = |value: i64| {
+            let block = frame_system::Pallet::<Test>::block_number();
+            frame_system::Pallet::<Test>::set_block_number(block + 2);
+            let payload = vec![Reading { metric: 0, value }].encode();
+            let signature = sign(&pair, &device_id, nonce, &payload);
+            nonce += 1;
+            IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), payload, device_id.clone(), nonce - 1, signature)
+        };
+        assert_ok!(submit(210));
+        // Out of range: flagged and kept out of the aggregate, but the raw record is stored.
+        assert_ok!(submit(2_000));
+        assert_eq!(IoTBridgePallet::<Test>::device_health(&device_id).anomalies, 1);
+        assert!(IoTBridgePallet::<Test>::iot_data(1).is_some());
+        assert_eq!(IoTBridgePallet::<Test>::aggregates((device_id.clone(), 0), 0).unwrap().max, 210);
+        assert!(ReputationPallet::<Test>::current_score(&1, ReputationCategory::IotDataQuality) < score);
+        // A clean reading lowers the anomaly score.
+        assert_ok!(submit(240));
+        assert_eq!(IoTBridgePallet::<Test>::device_health(&device_id).anomalies, 0);
+        // Jumping too fast, then sticking at the same value.
+        assert_ok!(submit(400));
+        assert_ok!(submit(240));
+        assert_ok!(submit(240));
+        assert_ok!(submit(240));
+        assert!(!IoTBridgePallet::<Test>::device_health(&device_id).quarantined);
+        assert_ok!(submit(240));
+        assert!(IoTBridgePallet::<Test>::device_health(&device_id).quarantined);
+        assert_eq!(submit(250), Err(Error::<Test>::DeviceQuarantined.into()));
+
+        // While quarantined, the owner can neither lift the quarantine nor remove or loosen the rule...
+        assert_noop!(
+            IoTBridgePallet::<Test>::release_quarantine(RawOrigin::Signed(1).into(), device_id.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            IoTBridgePallet::<Test>::set_plausibility_rule(RawOrigin::Signed(1).into(), device_id.clone(), 0, None),
+            Error::<Test>::RuleLoosenedWhileQuarantined
+        );
+        let loose = PlausibilityRule { max_repeats: None, ..rule };
+        assert_noop!(
+            IoTBridgePallet::<Test>::set_plausibility_rule(RawOrigin::Signed(1).into(), device_id.clone(), 0, Some(loose)),
+            Error::<Test>::RuleLoosenedWhileQuarantined
+        );
+        // ...but can tighten it.
+        let strict = PlausibilityRule { max: Some(500), ..rule };
+        assert_ok!(IoTBridgePallet::<Test>::set_plausibility_rule(RawOrigin::Signed(1).into(), device_id.clone(), 0, Some(strict)));
+        assert_eq!(IoTBridgePallet::<Test>::plausibility_rules(&device_id, 0), Some(strict));
+
+        // Governance releases the repaired device.
+        assert_ok!(IoTBridgePallet::<Test>::release_quarantine(RawOrigin::Root.into(), device_id.clone()));
+        assert_eq!(IoTBridgePallet::<Test>::device_health(&device_id).anomalies, 0);
+        assert_ok!(submit(260));
+        // Replacing the rule resets the last plausible value, as removing it does.
+        assert!(IoTBridgePallet::<Test>::last_values(&device_id, 0).is_some());
+        assert_ok!(IoTBridgePallet::<Test>::set_plausibility_rule(RawOrigin::Signed(1).into(), device_id.clone(), 0, Some(rule)));
+        assert!(IoTBridgePallet::<Test>::last_values(&device_id, 0).is_none());
+    });
+}
+
+#[test]
+fn test_consistent_readings_after_a_step_change_become_the_new_baseline() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        assert_ok!(ReputationPallet::<Test>::add_reporter(RawOrigin::Root.into(), 99, sp_runtime::Percent::from_percent(100), 1_000));
+        let pair = device_pair(3);
+        let device_id = b"Thermo02".to_vec();
+        register(1, &device_id, &pair);
+        let rule = PlausibilityRule { min: None, max: None, max_delta: Some(50), max_repeats: None };
+        assert_ok!(IoTBridgePallet::<Test>::set_plausibility_rule(RawOrigin::Signed(1).into(), device_id.clone(), 0, Some(rule)));
+
+        let mut nonce = 0;
+        let mut submit = |value: i64| {
+            let block = frame_system::Pallet::<Test>::block_number();
+            frame_system::Pallet::<Test>::set_block_number(block + 2);
+            let payload = vec![Reading { metric: 0, value }].encode();
+            let signature = sign(&pair, &device_id, nonce, &payload);
+            nonce += 1;
+            IoTBridgePallet::<Test>::submit_iot_data(RawOrigin::None.into(), payload, device_id.clone(), nonce - 1, signature)
+        };
+        assert_ok!(submit(200));
+        // An isolated spike followed by a reading near the old baseline does not move the baseline.
+        assert_ok!(submit(900));
+        assert_ok!(submit(210));
+        assert_eq!(IoTBridgePallet::<Test>::last_values(&device_id, 0), Some((210, 0)));
+        assert!(IoTBridgePallet::<Test>::pending_baselines(&device_id, 0).is_none());
+        // The sensor is moved: the first readings at the new level are flagged...
+        assert_ok!(submit(600));
+        assert_ok!(submit(620));
+        assert_eq!(IoTBridgePallet::<Test>::last_values(&device_id, 0), Some((210, 0)));
+        assert_eq!(IoTBridgePallet::<Test>::pending_baselines(&device_id, 0), Some((620, 2)));
+        // ...until RebaselineAfter (3) consistent readings make the new level the baseline.
+        assert_ok!(submit(640));
+        assert_eq!(IoTBridgePallet::<Test>::last_values(&device_id, 0), Some((640, 0)));
+        assert!(IoTBridgePallet::<Test>::pending_baselines(&device_id, 0).is_none());
+        assert_ok!(submit(650));
+        assert_eq!(IoTBridgePallet::<Test>::last_values(&device_id, 0), Some((650, 0)));
+        assert!(!IoTBridgePallet::<Test>::device_health(&device_id).quarantined);
     });
 }
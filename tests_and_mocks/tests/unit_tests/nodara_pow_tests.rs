@@ -0,0 +1,46 @@
+// tests/unit_tests/nodara_pow_tests.rs
+
+use crate::mock::{new_test_ext, Test};
+use frame_support::{assert_ok, traits::Currency};
+use frame_system::RawOrigin;
+use nodara_pow::pallet::Pallet as PowPallet;
+use nodara_staking::pallet::Pallet as StakingPallet;
+
+fn fund(who: u64, amount: u64) {
+    let _ = pallet_balances::Pallet::<Test>::deposit_creating(&who, amount);
+}
+
+#[test]
+fn test_invalid_work_slashes_the_miner_once() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        fund(1, 1_000);
+        assert_ok!(StakingPallet::<Test>::bond(RawOrigin::Signed(1).into(), 1_000));
+
+        // BaselineDifficulty is 50: hashes of at most 5 bytes fail validation.
+        assert_ok!(PowPallet::<Test>::submit_work(RawOrigin::Signed(1).into(), 1, b"weak".to_vec()));
+        assert_eq!(PowPallet::<Test>::miners(1), Some(1));
+        assert_ok!(PowPallet::<Test>::validate_work(1));
+        // InvalidWorkSlash is 10% of the miner's stake.
+        assert_eq!(StakingPallet::<Test>::ledger(1).unwrap().active, 900);
+        assert_eq!(PowPallet::<Test>::miners(1), None);
+
+        // Validating the same submission again does not slash twice.
+        assert_ok!(PowPallet::<Test>::validate_work(1));
+        assert_eq!(StakingPallet::<Test>::ledger(1).unwrap().active, 900);
+    });
+}
+
+#[test]
+fn test_valid_work_is_not_slashed() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        fund(1, 1_000);
+        assert_ok!(StakingPallet::<Test>::bond(RawOrigin::Signed(1).into(), 1_000));
+
+        assert_ok!(PowPallet::<Test>::submit_work(RawOrigin::Signed(1).into(), 2, b"strong work".to_vec()));
+        assert_ok!(PowPallet::<Test>::validate_work(2));
+        assert_eq!(StakingPallet::<Test>::ledger(1).unwrap().active, 1_000);
+        assert_eq!(PowPallet::<Test>::miners(2), None);
+    });
+}
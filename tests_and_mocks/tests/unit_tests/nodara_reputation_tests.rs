@@ -0,0 +1,254 @@
+// tests/unit_tests/nodara_reputation_tests.rs
+
+use crate::mock::{new_test_ext, Test, UnverifiedGainWeight};
+use frame_support::{assert_noop, assert_ok, traits::{Currency, ReservableCurrency}};
+use frame_system::RawOrigin;
+use nodara_id::pallet::{Judgement, Pallet as IdPallet};
+use nodara_reputation::{
+    pallet::{CategoryParams, Error, Pallet as ReputationPallet, ReportStatus},
+    ReputationCategory,
+};
+use sp_core::H256;
+use sp_runtime::Percent;
+
+const REPORTER: u64 = 10;
+const TRADING: ReputationCategory = ReputationCategory::TradingReliability;
+
+fn authorize_reporter(weight: u8, cap: u32) {
+    assert_ok!(ReputationPallet::<Test>::add_reporter(RawOrigin::Root.into(), REPORTER, Percent::from_percent(weight), cap));
+}
+
+#[test]
+fn test_reputation_decays_toward_default() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        authorize_reporter(100, 10_000);
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 1, TRADING, 200, b"Good Trades".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 200);
+        // One half-life later, half of the distance to the default (100) remains.
+        frame_system::Pallet::<Test>::set_block_number(101);
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 150);
+        frame_system::Pallet::<Test>::set_block_number(10_001);
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 100);
+    });
+}
+
+#[test]
+fn test_negative_metric_lowers_reputation() {
+    new_test_ext().execute_with(|| {
+        authorize_reporter(100, 10_000);
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 1, TRADING, -100, b"Failed Delivery".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 50);
+    });
+}
+
+#[test]
+fn test_fully_weighted_minimum_metric_does_not_overflow() {
+    new_test_ext().execute_with(|| {
+        authorize_reporter(100, u32::MAX);
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 1, TRADING, i32::MIN, b"Worst".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 0);
+    });
+}
+
+#[test]
+fn test_only_authorized_reporters_within_cap() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(1).into(), 2, TRADING, 100, b"Unauthorized".to_vec()),
+            Error::<Test>::NotAuthorizedReporter
+        );
+        authorize_reporter(50, 60);
+        assert_noop!(
+            ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), REPORTER, TRADING, 100, b"Self".to_vec()),
+            Error::<Test>::SelfReportNotAllowed
+        );
+        // Weighted metric 50 (50% of 100) fits the cap of 60; a second report in the same period does not.
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 1, TRADING, 100, b"Report".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 125);
+        assert_noop!(
+            ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 2, TRADING, 100, b"Report".to_vec()),
+            Error::<Test>::ReporterCapExceeded
+        );
+    });
+}
+
+#[test]
+fn test_category_bounds_and_aggregate() {
+    new_test_ext().execute_with(|| {
+        authorize_reporter(100, 10_000);
+        let iot = ReputationCategory::IotDataQuality;
+        assert_ok!(ReputationPallet::<Test>::set_category_params(
+            RawOrigin::Root.into(),
+            iot,
+            CategoryParams { min: 0, max: 150, default: 100, half_life: 0 }
+        ));
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 1, iot, 400, b"Clean Data".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, iot), 150);
+        assert_eq!(ReputationPallet::<Test>::aggregate_score(&2), None);
+        // IoT data quality only: the aggregate follows the IoT score.
+        assert_ok!(ReputationPallet::<Test>::set_category_weights(RawOrigin::Root.into(), vec![(iot, 1)]));
+        assert_eq!(ReputationPallet::<Test>::aggregate_score(&1), Some(150));
+        // Equal weights with the three other categories at their default (100).
+        assert_ok!(ReputationPallet::<Test>::set_category_weights(RawOrigin::Root.into(), vec![]));
+        assert_eq!(ReputationPallet::<Test>::aggregate_score(&1), Some(112));
+    });
+}
+
+fn fund(who: u64, amount: u64) {
+    let _ = pallet_balances::Pallet::<Test>::make_free_balance_be(&who, amount);
+}
+
+#[test]
+fn test_attestation_reserves_bond_and_releases_after_window() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        fund(2, 1_000);
+        // AttestationWeight is 50%: a metric of 200 becomes 100, applied as +50.
+        assert_ok!(ReputationPallet::<Test>::attest(RawOrigin::Signed(2).into(), 1, TRADING, 200, b"Reliable".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 150);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&2), 50);
+        assert_noop!(
+            ReputationPallet::<Test>::release_bond(RawOrigin::Signed(3).into(), 0),
+            Error::<Test>::DisputeWindowOpen
+        );
+        frame_system::Pallet::<Test>::set_block_number(12);
+        assert_ok!(ReputationPallet::<Test>::release_bond(RawOrigin::Signed(3).into(), 0));
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&2), 0);
+        assert_eq!(ReputationPallet::<Test>::reports(0).unwrap().status, ReportStatus::Settled);
+    });
+}
+
+#[test]
+fn test_overturned_attestation_is_reverted_and_slashed() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        fund(1, 1_000);
+        fund(2, 1_000);
+        assert_noop!(
+            ReputationPallet::<Test>::attest(RawOrigin::Signed(2).into(), 2, TRADING, -200, b"Self".to_vec()),
+            Error::<Test>::SelfReportNotAllowed
+        );
+        assert_ok!(ReputationPallet::<Test>::attest(RawOrigin::Signed(2).into(), 1, TRADING, -200, b"Scam".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 50);
+        assert_noop!(ReputationPallet::<Test>::dispute(RawOrigin::Signed(2).into(), 0), Error::<Test>::CannotDisputeOwnReport);
+        assert_ok!(ReputationPallet::<Test>::dispute(RawOrigin::Signed(1).into(), 0));
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 20);
+        assert_ok!(ReputationPallet::<Test>::resolve_dispute(RawOrigin::Root.into(), 0, true));
+        // The subject's score is restored and the attester loses its bond and OverturnPenalty (30) reputation.
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 100);
+        assert_eq!(ReputationPallet::<Test>::current_score(&2, TRADING), 70);
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&2), 950);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&2), 0);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 0);
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&1), 1_000);
+    });
+}
+
+#[test]
+fn test_accounts_of_one_identity_cannot_vouch_for_each_other() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        fund(1, 1_000);
+        fund(2, 1_000);
+        // Account 2 is a sub-account of 1's identity, so both share 1's record.
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), H256::repeat_byte(1), Vec::new()));
+        assert_ok!(IdPallet::<Test>::add_sub(RawOrigin::Signed(1).into(), 2, b"hot wallet".to_vec()));
+        assert_ok!(IdPallet::<Test>::accept_sub(RawOrigin::Signed(2).into(), 1));
+        assert_noop!(
+            ReputationPallet::<Test>::attest(RawOrigin::Signed(2).into(), 1, TRADING, 200, b"Self".to_vec()),
+            Error::<Test>::SelfReportNotAllowed
+        );
+        assert_noop!(
+            ReputationPallet::<Test>::attest(RawOrigin::Signed(1).into(), 2, TRADING, 200, b"Self".to_vec()),
+            Error::<Test>::SelfReportNotAllowed
+        );
+        // Nor can an authorized reporter report on the identity it belongs to.
+        assert_ok!(ReputationPallet::<Test>::add_reporter(RawOrigin::Root.into(), 2, Percent::from_percent(100), 10_000));
+        assert_noop!(
+            ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(2).into(), 1, TRADING, 100, b"Self".to_vec()),
+            Error::<Test>::SelfReportNotAllowed
+        );
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 100);
+    });
+}
+
+#[test]
+fn test_attestations_are_capped_and_disputable_by_third_parties() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        fund(2, 1_000);
+        fund(3, 1_000);
+        fund(5, 1_000);
+        // AttestationCap is 150 per subject and period: 100 + 100 does not fit, even from a second attester.
+        assert_ok!(ReputationPallet::<Test>::attest(RawOrigin::Signed(2).into(), 1, TRADING, 200, b"Friend".to_vec()));
+        assert_noop!(
+            ReputationPallet::<Test>::attest(RawOrigin::Signed(2).into(), 1, TRADING, 200, b"Friend".to_vec()),
+            Error::<Test>::AttestationCapExceeded
+        );
+        assert_noop!(
+            ReputationPallet::<Test>::attest(RawOrigin::Signed(5).into(), 1, TRADING, 200, b"Sybil".to_vec()),
+            Error::<Test>::AttestationCapExceeded
+        );
+        // Other subjects have their own allowance.
+        assert_ok!(ReputationPallet::<Test>::attest(RawOrigin::Signed(2).into(), 4, TRADING, 200, b"Friend".to_vec()));
+
+        // The subject would never dispute a favourable attestation, but a third party can.
+        assert_ok!(ReputationPallet::<Test>::dispute(RawOrigin::Signed(3).into(), 0));
+        assert_eq!(ReputationPallet::<Test>::reports(0).unwrap().disputer, Some(3));
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&3), 20);
+        assert_ok!(ReputationPallet::<Test>::resolve_dispute(RawOrigin::Root.into(), 0, true));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 100);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&3), 0);
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&3), 1_000);
+        // Overturning does not give the allowance back, but losses are capped separately from gains.
+        assert_noop!(
+            ReputationPallet::<Test>::attest(RawOrigin::Signed(5).into(), 1, TRADING, 200, b"Sybil".to_vec()),
+            Error::<Test>::AttestationCapExceeded
+        );
+        assert_ok!(ReputationPallet::<Test>::attest(RawOrigin::Signed(5).into(), 1, TRADING, -200, b"Late".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 0);
+
+        // The cap resets in the next reporting period.
+        frame_system::Pallet::<Test>::set_block_number(1_001);
+        assert_ok!(ReputationPallet::<Test>::attest(RawOrigin::Signed(2).into(), 1, TRADING, 200, b"Friend".to_vec()));
+    });
+}
+
+#[test]
+fn test_dispute_window_and_upheld_report() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        fund(1, 1_000);
+        authorize_reporter(100, 10_000);
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 1, TRADING, -100, b"Late".to_vec()));
+        assert_ok!(ReputationPallet::<Test>::dispute(RawOrigin::Signed(1).into(), 0));
+        assert_ok!(ReputationPallet::<Test>::resolve_dispute(RawOrigin::Root.into(), 0, false));
+        // The report stands and the dispute bond is slashed.
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 50);
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&1), 980);
+        assert_eq!(pallet_balances::Pallet::<Test>::reserved_balance(&1), 0);
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 1, TRADING, -10, b"Late".to_vec()));
+        frame_system::Pallet::<Test>::set_block_number(20);
+        assert_noop!(ReputationPallet::<Test>::dispute(RawOrigin::Signed(1).into(), 1), Error::<Test>::DisputeWindowClosed);
+    });
+}
+
+#[test]
+fn test_gains_are_gated_by_verified_identity() {
+    new_test_ext().execute_with(|| {
+        authorize_reporter(100, 10_000);
+        UnverifiedGainWeight::set(Percent::from_percent(0));
+        // Account 1 has no verified identity: gains are ignored, losses still apply.
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 1, TRADING, 100, b"Report".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 100);
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 1, TRADING, -40, b"Report".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 80);
+        assert_ok!(IdPallet::<Test>::register_identity(RawOrigin::Signed(1).into(), H256::repeat_byte(1), Vec::new()));
+        assert_ok!(IdPallet::<Test>::add_registrar(RawOrigin::Root.into(), 20, 0));
+        assert_ok!(IdPallet::<Test>::request_judgement(RawOrigin::Signed(1).into(), 0, 0));
+        assert_ok!(IdPallet::<Test>::provide_judgement(RawOrigin::Signed(20).into(), 0, 1, H256::repeat_byte(1), Judgement::KnownGood));
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(REPORTER).into(), 1, TRADING, 100, b"Report".to_vec()));
+        assert_eq!(ReputationPallet::<Test>::current_score(&1, TRADING), 130);
+    });
+}
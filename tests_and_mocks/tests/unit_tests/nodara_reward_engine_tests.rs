@@ -1,8 +1,12 @@
 // tests/unit_tests/nodara_reward_engine_tests.rs
 
-use crate::mock::{new_test_ext, Test};
-use frame_support::{assert_ok};
-use nodara_reward_engine::pallet::{Pallet as RewardEnginePallet};
+use crate::mock::{new_test_ext, BaseReward, FallbackReputation, ReputationMultiplier, Test};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use nodara_reputation::{pallet::Pallet as ReputationPallet, ReputationCategory};
+use nodara_reward_engine::pallet::{Error, Pallet as RewardEnginePallet, RewardCategory, RewardCurve};
+use sp_core::H256;
+use sp_runtime::{traits::{BlakeTwo256, Get, Hash}, FixedU128, Percent};
 
 #[test]
 fn test_reward_distribution() {
@@ -10,9 +14,104 @@ fn test_reward_distribution() {
         assert_ok!(RewardEnginePallet::<Test>::initialize_rewards());
         let initial_pool = RewardEnginePallet::<Test>::reward_pool();
         let account = 1;
-        // Distribute reward based on a reputation score.
-        assert_ok!(RewardEnginePallet::<Test>::distribute_reward(account, 50, b"Reward Test".to_vec()));
+        // Distribute reward based on the account's recorded (or fallback) reputation.
+        assert_ok!(RewardEnginePallet::<Test>::distribute_reward(account, RewardCategory::General, b"Reward Test".to_vec()));
         let new_pool = RewardEnginePallet::<Test>::reward_pool();
         assert!(new_pool < initial_pool);
     });
 }
+
+#[test]
+fn test_rewards_follow_recipient_reputation() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(RewardEnginePallet::<Test>::initialize_rewards());
+        let trading = ReputationCategory::TradingReliability;
+        assert_ok!(ReputationPallet::<Test>::add_reporter(RawOrigin::Root.into(), 10, Percent::from_percent(100), 10_000));
+        assert_ok!(ReputationPallet::<Test>::set_category_weights(RawOrigin::Root.into(), vec![(trading, 1)]));
+        assert_ok!(ReputationPallet::<Test>::update_reputation(RawOrigin::Signed(10).into(), 1, trading, 200, b"Good Trades".to_vec()));
+        let score = ReputationPallet::<Test>::current_score(&1, trading);
+        assert_eq!(RewardEnginePallet::<Test>::reputation_of(&1), score);
+
+        // Account 1 is paid on its earned reputation.
+        let before = RewardEnginePallet::<Test>::reward_pool();
+        assert_ok!(RewardEnginePallet::<Test>::distribute_reward(1, RewardCategory::General, b"Known".to_vec()));
+        let known_reward = before - RewardEnginePallet::<Test>::reward_pool();
+        assert_eq!(known_reward, BaseReward::get() + ReputationMultiplier::get() * score);
+
+        // Account 2 has no record and is paid on the fallback reputation.
+        assert_eq!(RewardEnginePallet::<Test>::reputation_of(&2), FallbackReputation::get());
+        let before = RewardEnginePallet::<Test>::reward_pool();
+        assert_ok!(RewardEnginePallet::<Test>::distribute_reward(2, RewardCategory::General, b"Unknown".to_vec()));
+        let unknown_reward = before - RewardEnginePallet::<Test>::reward_pool();
+        assert_eq!(unknown_reward, BaseReward::get() + ReputationMultiplier::get() * FallbackReputation::get());
+        assert!(known_reward > unknown_reward);
+    });
+}
+
+#[test]
+fn test_reward_curves() {
+    let linear = RewardCurve::Linear { base: 10, slope: FixedU128::from_rational(1, 2) };
+    assert_eq!(linear.evaluate(100), 60);
+    let log = RewardCurve::Logarithmic { base: 0, scale: 10 };
+    assert_eq!(log.evaluate(0), 0);
+    assert_eq!(log.evaluate(1023), 100);
+    let sigmoid = RewardCurve::Sigmoid { min: 0, max: 100, midpoint: 50, steepness: 2 };
+    assert_eq!(sigmoid.evaluate(50), 50);
+    assert!(sigmoid.evaluate(1_000_000) <= 100);
+    let table = RewardCurve::Piecewise(vec![(0, 0), (100, 50), (200, 60)]);
+    assert_eq!(table.evaluate(50), 25);
+    assert_eq!(table.evaluate(150), 55);
+    assert_eq!(table.evaluate(10_000), 60);
+}
+
+#[test]
+fn test_set_reward_curve_requires_governance() {
+    new_test_ext().execute_with(|| {
+        let curve = RewardCurve::Logarithmic { base: 5, scale: 3 };
+        assert_noop!(
+            RewardEnginePallet::<Test>::set_reward_curve(RawOrigin::Signed(1).into(), RewardCategory::Mining, curve.clone()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            RewardEnginePallet::<Test>::set_reward_curve(RawOrigin::Root.into(), RewardCategory::Mining, RewardCurve::Piecewise(vec![(5, 1), (5, 2)])),
+            Error::<Test>::InvalidRewardCurve
+        );
+        assert_ok!(RewardEnginePallet::<Test>::set_reward_curve(RawOrigin::Root.into(), RewardCategory::Mining, curve.clone()));
+        assert_eq!(RewardEnginePallet::<Test>::reward_curves(RewardCategory::Mining), Some(curve));
+    });
+}
+
+#[test]
+fn test_merkle_airdrop_claim_and_sweep() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(RewardEnginePallet::<Test>::update_reward_pool(1_000, b"Fund Airdrop".to_vec()));
+        let leaf_a: H256 = BlakeTwo256::hash_of(&(0u32, 1u64, 100u32));
+        let leaf_b: H256 = BlakeTwo256::hash_of(&(0u32, 2u64, 50u32));
+        let root = if leaf_a <= leaf_b { BlakeTwo256::hash_of(&(leaf_a, leaf_b)) } else { BlakeTwo256::hash_of(&(leaf_b, leaf_a)) };
+        assert_ok!(RewardEnginePallet::<Test>::create_campaign(RawOrigin::Root.into(), root, 150, 10));
+        assert_eq!(RewardEnginePallet::<Test>::reward_pool(), 850);
+
+        // Wrong amount does not match the committed leaf.
+        assert_noop!(
+            RewardEnginePallet::<Test>::claim(RawOrigin::Signed(1).into(), 0, 150, vec![leaf_b]),
+            Error::<Test>::InvalidMerkleProof
+        );
+        assert_ok!(RewardEnginePallet::<Test>::claim(RawOrigin::Signed(1).into(), 0, 100, vec![leaf_b]));
+        assert_noop!(
+            RewardEnginePallet::<Test>::claim(RawOrigin::Signed(1).into(), 0, 100, vec![leaf_b]),
+            Error::<Test>::AlreadyClaimed
+        );
+        assert_noop!(
+            RewardEnginePallet::<Test>::sweep_campaign(RawOrigin::Signed(3).into(), 0),
+            Error::<Test>::CampaignStillActive
+        );
+
+        frame_system::Pallet::<Test>::set_block_number(11);
+        assert_noop!(
+            RewardEnginePallet::<Test>::claim(RawOrigin::Signed(2).into(), 0, 50, vec![leaf_a]),
+            Error::<Test>::CampaignExpired
+        );
+        assert_ok!(RewardEnginePallet::<Test>::sweep_campaign(RawOrigin::Signed(3).into(), 0));
+        assert_eq!(RewardEnginePallet::<Test>::reward_pool(), 900);
+    });
+}
@@ -0,0 +1,119 @@
+// tests/unit_tests/nodara_staking_tests.rs
+
+use crate::mock::{new_test_ext, Test};
+use frame_support::{assert_noop, assert_ok, traits::{Currency, Hooks}};
+use frame_system::RawOrigin;
+use nodara_reward_engine::pallet::{Error as RewardError, Pallet as RewardEnginePallet};
+use nodara_staking::{
+    pallet::{Error, Pallet as StakingPallet},
+    StakeProvider, StakeSlasher,
+};
+use sp_runtime::Perbill;
+
+fn fund(who: u64, amount: u64) {
+    let _ = pallet_balances::Pallet::<Test>::deposit_creating(&who, amount);
+}
+
+#[test]
+fn test_bond_unbond_rebond_and_withdraw() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        fund(1, 1_000);
+        assert_noop!(StakingPallet::<Test>::bond(RawOrigin::Signed(1).into(), 5), Error::<Test>::InsufficientBond);
+        assert_noop!(StakingPallet::<Test>::bond(RawOrigin::Signed(1).into(), 2_000), Error::<Test>::InsufficientBalance);
+        assert_ok!(StakingPallet::<Test>::bond(RawOrigin::Signed(1).into(), 500));
+        assert_ok!(StakingPallet::<Test>::nominate(RawOrigin::Signed(1).into(), 7));
+        assert_eq!(StakingPallet::<Test>::backing(7), 500);
+
+        assert_ok!(StakingPallet::<Test>::unbond(RawOrigin::Signed(1).into(), 200));
+        assert_eq!(StakingPallet::<Test>::total_active_stake(), 300);
+        assert_eq!(StakingPallet::<Test>::backing(7), 300);
+        assert_ok!(StakingPallet::<Test>::rebond(RawOrigin::Signed(1).into(), 50));
+        assert_eq!(StakingPallet::<Test>::ledger(1).unwrap().active, 350);
+
+        // Nothing is released before the bonding duration has elapsed.
+        assert_ok!(StakingPallet::<Test>::withdraw_unbonded(RawOrigin::Signed(1).into()));
+        assert_eq!(StakingPallet::<Test>::ledger(1).unwrap().total, 500);
+        frame_system::Pallet::<Test>::set_block_number(11);
+        assert_ok!(StakingPallet::<Test>::withdraw_unbonded(RawOrigin::Signed(1).into()));
+        assert_eq!(StakingPallet::<Test>::ledger(1).unwrap().total, 350);
+    });
+}
+
+#[test]
+fn test_slash_reduces_active_and_unlocking_stake() {
+    new_test_ext().execute_with(|| {
+        fund(1, 1_000);
+        assert_ok!(StakingPallet::<Test>::bond(RawOrigin::Signed(1).into(), 400));
+        assert_ok!(StakingPallet::<Test>::unbond(RawOrigin::Signed(1).into(), 100));
+        let slashed = <StakingPallet<Test> as StakeSlasher<u64>>::slash(&1, Perbill::from_percent(10), b"Misbehavior".to_vec());
+        assert_eq!(slashed, 40);
+        let ledger = StakingPallet::<Test>::ledger(1).unwrap();
+        assert_eq!(ledger.active, 270);
+        assert_eq!(ledger.unlocking[0].value, 90);
+        assert_eq!(StakingPallet::<Test>::total_active_stake(), 270);
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(1), 960);
+        assert_eq!(<StakingPallet<Test> as StakeSlasher<u64>>::slash(&2, Perbill::from_percent(10), Vec::new()), 0);
+    });
+}
+
+#[test]
+fn test_stakers_are_listed_in_bounded_pages() {
+    new_test_ext().execute_with(|| {
+        for who in 1..=5 {
+            fund(who, 1_000);
+            assert_ok!(StakingPallet::<Test>::bond(RawOrigin::Signed(who).into(), 100));
+        }
+        // Account 3 is fully unbonding: it is visited but not listed.
+        assert_ok!(StakingPallet::<Test>::unbond(RawOrigin::Signed(3).into(), 100));
+        let mut listed = Vec::new();
+        let mut cursor = None;
+        let mut pages = 0;
+        loop {
+            let (page, next) = <StakingPallet<Test> as StakeProvider<u64>>::stakers(cursor, 2);
+            assert!(page.len() <= 2);
+            listed.extend(page);
+            pages += 1;
+            match next {
+                Some(last) => cursor = Some(last),
+                None => break,
+            }
+        }
+        listed.sort();
+        assert_eq!(listed, vec![1, 2, 4, 5]);
+        assert_eq!(pages, 3);
+    });
+}
+
+#[test]
+fn test_staking_rewards_use_full_balances_and_stay_within_budget() {
+    new_test_ext().execute_with(|| {
+        // Stakes far above `u32::MAX` keep their exact proportions.
+        fund(1, 30_000_000_000);
+        fund(2, 10_000_000_000);
+        fund(3, 10_000_000_000);
+        assert_ok!(StakingPallet::<Test>::bond(RawOrigin::Signed(1).into(), 30_000_000_000));
+        assert_ok!(StakingPallet::<Test>::bond(RawOrigin::Signed(2).into(), 10_000_000_000));
+        assert_ok!(StakingPallet::<Test>::bond(RawOrigin::Signed(3).into(), 10_000_000_000));
+        assert_ok!(RewardEnginePallet::<Test>::update_reward_pool(1_000, b"Fund".to_vec()));
+
+        // The budget is set aside at once; only the first page of two ledgers is paid right away.
+        assert_ok!(RewardEnginePallet::<Test>::distribute_staking_rewards(1_000, b"Round".to_vec()));
+        assert_eq!(RewardEnginePallet::<Test>::reward_pool(), 0);
+        assert!(RewardEnginePallet::<Test>::staking_payout().is_some());
+        assert_noop!(
+            RewardEnginePallet::<Test>::distribute_staking_rewards(1, b"Overlap".to_vec()),
+            RewardError::<Test>::StakingPayoutInProgress
+        );
+
+        // Stake bonded mid-round would inflate the shares; the round still pays at most its budget.
+        fund(2, 100_000_000_000);
+        assert_ok!(StakingPallet::<Test>::bond(RawOrigin::Signed(2).into(), 100_000_000_000));
+
+        RewardEnginePallet::<Test>::on_initialize(2);
+        assert!(RewardEnginePallet::<Test>::staking_payout().is_none());
+        // Whatever was not paid out returns to the pool.
+        let unpaid = RewardEnginePallet::<Test>::reward_pool();
+        assert!(unpaid < 1_000);
+    });
+}
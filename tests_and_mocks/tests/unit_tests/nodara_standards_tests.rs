@@ -1,8 +1,9 @@
 // tests/unit_tests/nodara_standards_tests.rs
 
 use crate::mock::{new_test_ext, Test};
-use frame_support::{assert_ok};
-use nodara_standards::pallet::{Pallet as StandardsPallet};
+use frame_support::{assert_noop, assert_ok, pallet_prelude::Encode};
+use frame_system::RawOrigin;
+use nodara_standards::pallet::{Error, FieldType, PayloadError, Pallet as StandardsPallet, SchemaField};
 
 #[test]
 fn test_standard_definition_and_verification() {
@@ -19,3 +20,47 @@ fn test_standard_definition_and_verification() {
         assert!(result.unwrap());
     });
 }
+
+#[test]
+fn test_payload_schema_definition_and_decoding() {
+    new_test_ext().execute_with(|| {
+        let id = b"MeterV1".to_vec();
+        let field = |name: &[u8], field_type, min, max| SchemaField { name: name.to_vec(), field_type, unit: b"Wh".to_vec(), min, max };
+        let fields = vec![field(b"energy", FieldType::U64, Some(0), None), field(b"tamper", FieldType::Bool, None, None)];
+        assert_noop!(
+            StandardsPallet::<Test>::define_payload_schema(RawOrigin::Signed(1).into(), id.clone(), fields.clone()),
+            Error::<Test>::StandardNotFound
+        );
+        assert_ok!(StandardsPallet::<Test>::define_standard(RawOrigin::Signed(1).into(), id.clone(), b"Energy meter".to_vec(), Vec::new()));
+        assert_noop!(
+            StandardsPallet::<Test>::define_payload_schema(RawOrigin::Signed(1).into(), id.clone(), vec![field(b"energy", FieldType::I32, Some(10), Some(0))]),
+            Error::<Test>::InvalidSchema
+        );
+        assert_noop!(
+            StandardsPallet::<Test>::define_payload_schema(RawOrigin::Signed(2).into(), id.clone(), fields.clone()),
+            Error::<Test>::NotStandardCreator
+        );
+        assert_ok!(StandardsPallet::<Test>::define_payload_schema(RawOrigin::Signed(1).into(), id.clone(), fields.clone()));
+        assert_noop!(
+            StandardsPallet::<Test>::define_payload_schema(RawOrigin::Signed(1).into(), id.clone(), fields),
+            Error::<Test>::SchemaAlreadyExists
+        );
+
+        let schema = StandardsPallet::<Test>::payload_schemas(&id).unwrap();
+        assert_eq!(schema.decode_payload(&(1_250u64, true).encode()), Ok(vec![1_250, 1]));
+        assert_eq!(schema.decode_payload(&(u64::MAX, false).encode()), Err(PayloadError::OutOfRange(0)));
+        assert_eq!(schema.decode_payload(&(1_250u64, 2u8).encode()), Err(PayloadError::Malformed));
+    });
+}
+
+#[test]
+fn test_governance_can_define_any_payload_schema() {
+    new_test_ext().execute_with(|| {
+        let id = b"GridV1".to_vec();
+        let fields = vec![SchemaField { name: b"load".to_vec(), field_type: FieldType::U32, unit: b"W".to_vec(), min: None, max: None }];
+        assert_ok!(StandardsPallet::<Test>::define_standard(RawOrigin::Signed(1).into(), id.clone(), b"Grid load".to_vec(), Vec::new()));
+        assert_eq!(StandardsPallet::<Test>::standard_creators(&id), Some(1));
+        assert_ok!(StandardsPallet::<Test>::define_payload_schema(RawOrigin::Root.into(), id.clone(), fields));
+        assert!(StandardsPallet::<Test>::payload_schemas(&id).is_some());
+    });
+}